@@ -1,58 +1,213 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
-use std::ffi::OsStr;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use anyhow::{Context, Result, anyhow};
 use clap::Args;
-use dialoguer::Confirm;
+use dialoguer::{Confirm, MultiSelect};
 use futures::stream::{self, StreamExt};
-use indicatif::ProgressBar;
 use once_cell::sync::Lazy;
-use owo_colors::OwoColorize;
+use owo_colors::{OwoColorize, Stream};
 use pathdiff::diff_paths;
 use regex::Regex;
+use serde_json::{Value, json};
+use similar::TextDiff;
 
 use crate::commands::{CommandOutcome, CommandResult};
-use crate::reporter::ConsoleReporter;
-use crate::util::{canonicalize_path, create_spinner, normalize_relative_path};
-use nocta_core::config::{read_config, read_config_from};
+use crate::journal::{FileChange, ensure_change_record, rollback_and_report};
+use crate::report::{DependencyRecord, utc_timestamp, write_report};
+use crate::reporter::{ConsoleReporter, prompts_enabled};
+use crate::util::{
+    Spinner, canonicalize_path, create_spinner, ensure_outside_node_modules,
+    normalize_path_lexically, normalize_relative_path, parse_duration, print_planned_install,
+    warn_cache_fallback,
+};
+use nocta_core::cache;
+use nocta_core::config::{config_path, read_config, read_config_from};
 use nocta_core::deps::{
-    DependencyScope, RequirementIssueReason, check_project_requirements,
-    get_installed_dependencies_at, plan_dependency_install,
+    DependencyInstallPlan, DependencyScope, RequirementIssue, RequirementIssueReason,
+    VersionRangeMerge, check_project_requirements, get_installed_dependencies_at,
+    installed_version_outside_range, merge_version_ranges, plan_dependency_install,
 };
+use nocta_core::formatter::{detect_formatter, plan_format};
 use nocta_core::framework::{FrameworkDetection, FrameworkKind, detect_framework};
 use nocta_core::fs::{file_exists, read_file, write_file};
-use nocta_core::paths::resolve_component_path;
-use nocta_core::registry::RegistryClient;
+use nocta_core::lockfile::{
+    LOCK_FILE_NAME, LockedComponent, LockedFile, component_content_hash, component_hash_matches,
+    content_hash, lock_key, read_lockfile_from, write_lockfile_to,
+};
+use nocta_core::paths::component_install_path;
+use nocta_core::registry::{RegistryClient, RegistryOptions, RegistrySet, split_scoped_slug};
 use nocta_core::workspace::{
-    PackageManagerContext, PackageManagerKind, detect_package_manager, find_repo_root,
-    load_workspace_manifest,
+    PackageManagerContext, PackageManagerKind, WORKSPACE_MANIFEST_FILE, detect_package_manager,
+    find_repo_root, load_workspace_manifest,
 };
 
-use nocta_core::types::{Component, Config, ExportStrategy, WorkspaceKind};
+use nocta_core::types::{
+    BarrelStyle, Component, ComponentFile, Config, ExportStrategy, FilenameCase, WorkspaceConfig,
+    WorkspaceKind, WorkspaceLink, has_binary_extension, select_framework_files,
+};
 
 #[derive(Args, Debug, Clone)]
 pub struct AddArgs {
-    #[arg(value_name = "components", required = true)]
+    #[arg(
+        value_name = "components",
+        required_unless_present_any = ["from_file", "all"]
+    )]
     pub components: Vec<String>,
+    /// Also install the components listed in this file, one slug per line (`#` starts a comment).
+    #[arg(long = "from-file", value_name = "file")]
+    pub from_file: Option<PathBuf>,
+    /// Install every component in the registry (or the `--registry` one). Asks for confirmation.
+    #[arg(long = "all", conflicts_with_all = ["components", "from_file"])]
+    pub all: bool,
+    /// Answer yes to the `--all` and overwrite confirmation prompts.
+    #[arg(long = "yes", short = 'y')]
+    pub yes: bool,
     #[arg(long = "dry-run")]
     pub dry_run: bool,
+    /// Named registry (from nocta.config.json) used for components without an `@<registry>/` prefix.
+    #[arg(long = "registry", value_name = "name")]
+    pub registry: Option<String>,
+    /// Import prefix used when rewriting component imports for this run (e.g. `@`, `~`, `#ui`).
+    #[arg(long = "alias-prefix", value_name = "prefix", value_parser = parse_alias_prefix)]
+    pub alias_prefix: Option<String>,
+    /// Run the project's formatter (biome or prettier) over the written files.
+    #[arg(long = "format")]
+    pub format: bool,
+    /// Pick which existing files to overwrite instead of a single yes/no prompt.
+    #[arg(long = "interactive", short = 'i')]
+    pub interactive: bool,
+    /// Write a JSON report of the installed components, files and dependencies to this path.
+    #[arg(long = "report", value_name = "file")]
+    pub report: Option<PathBuf>,
+    /// Print only the import statements for the requested components and exit without writing files.
+    #[arg(long = "print-imports")]
+    pub print_imports: bool,
+    /// Only print the installed component names and counts, without per-file listings.
+    #[arg(long = "summary-only")]
+    pub summary_only: bool,
+    /// Skip the summary printed after installing; only the success line and warnings remain.
+    #[arg(long = "no-summary", conflicts_with = "summary_only")]
+    pub no_summary: bool,
+    /// Keep polling the registry after installing and rewrite files whose content changes.
+    #[arg(long = "watch", conflicts_with_all = ["dry_run", "print_imports"])]
+    pub watch: bool,
+    /// How often `--watch` polls the registry, e.g. 500ms, 2s, 1m.
+    #[arg(
+        long = "watch-interval",
+        value_name = "duration",
+        default_value = "2s",
+        value_parser = parse_duration,
+        requires = "watch"
+    )]
+    pub watch_interval: Duration,
+    /// Barrel export strategy for this run, overriding the one in `exports` (`named` or `default`).
+    #[arg(long = "export-strategy", value_name = "strategy", value_parser = parse_export_strategy)]
+    pub export_strategy: Option<ExportStrategy>,
+    /// Print a unified diff of each export barrel change instead of the added statements.
+    #[arg(long = "exports-diff", conflicts_with = "summary_only")]
+    pub exports_diff: bool,
+    /// Only install dependencies the project lacks; warn about outdated ones instead of updating them.
+    #[arg(long = "limit-deps")]
+    pub limit_deps: bool,
+    /// Drop this leading segment from registry file paths (repeatable), on top of `stripPrefixes`.
+    #[arg(long = "strip-prefix", value_name = "prefix")]
+    pub strip_prefixes: Vec<String>,
+    /// Resolve the components and install their npm dependencies without writing any files.
+    #[arg(
+        long = "dependencies-only",
+        conflicts_with_all = ["watch", "print_imports", "interactive", "format", "report"]
+    )]
+    pub dependencies_only: bool,
+    /// Delete files the lockfile records for these components that are no longer part of them.
+    #[arg(long = "replace", conflicts_with_all = ["dependencies_only", "print_imports"])]
+    pub replace: bool,
+    /// Never install this npm package (repeatable), on top of `dependencies.ignore`.
+    #[arg(long = "ignore-dep", value_name = "name")]
+    pub ignore_deps: Vec<String>,
+    /// Install only the requested components, skipping the internal components they depend on.
+    #[arg(long = "no-internal-deps")]
+    pub no_internal_deps: bool,
+    /// Install the components that fetched fine when others fail, then report the failures.
+    #[arg(long = "keep-going", conflicts_with = "watch")]
+    pub keep_going: bool,
+    /// Extra argument passed to the package manager install command (repeatable).
+    #[arg(
+        long = "install-arg",
+        value_name = "arg",
+        env = "NOCTA_INSTALL_ARGS",
+        value_delimiter = ' ',
+        allow_hyphen_values = true
+    )]
+    pub install_args: Vec<String>,
+}
+
+fn parse_alias_prefix(input: &str) -> std::result::Result<String, String> {
+    let trimmed = input.trim_end_matches('/');
+    if trimmed.is_empty() {
+        return Err("alias prefix must not be empty".into());
+    }
+    if trimmed.chars().any(char::is_whitespace) {
+        return Err(format!(
+            "alias prefix `{}` must not contain whitespace",
+            input
+        ));
+    }
+    Ok(trimmed.to_string())
 }
 
+fn parse_export_strategy(input: &str) -> std::result::Result<ExportStrategy, String> {
+    ExportStrategy::ALL
+        .into_iter()
+        .find(|strategy| strategy.as_str() == input)
+        .ok_or_else(|| {
+            let known: Vec<&str> = ExportStrategy::ALL.iter().map(|s| s.as_str()).collect();
+            format!(
+                "unknown export strategy `{}` (expected one of: {})",
+                input,
+                known.join(", ")
+            )
+        })
+}
+
+static EXPORTED_DECLARATION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?m)^export\s+(?:async\s+)?(?:function\*?|const|let|var|class|enum)\s+([A-Za-z_$][\w$]*)",
+    )
+    .expect("valid exported declaration regex")
+});
+
+static IMPORT_SPECIFIER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:\bfrom\s+|\bimport\s*\(?\s*)['"]([^'"\n]+)['"]"#)
+        .expect("valid import specifier regex")
+});
+
+/// Directories a project's `@/` alias conventionally points at.
+const SOURCE_ROOTS: [&str; 2] = ["app", "src"];
+
 static IMPORT_NORMALIZE_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"(['"])@/([^'"\n]+)(['"])"#).expect("valid import normalization regex")
 });
 
+static RELATIVE_IMPORT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(\bfrom\s+|\bimport\s*\(?\s*)(['"])(\.\.?/[^'"\n]+)(['"])"#)
+        .expect("valid relative import regex")
+});
+
 struct AddCommand<'a> {
     client: &'a RegistryClient,
     reporter: &'a ConsoleReporter,
     args: AddArgs,
     dry_run: bool,
     prefix: String,
-    spinner: ProgressBar,
+    spinner: Spinner,
     written_files: Vec<FileChange>,
+    skipped_files: Vec<PathBuf>,
+    unchanged_files: Vec<PathBuf>,
+    failures: Vec<ComponentFailure>,
 }
 
 impl<'a> AddCommand<'a> {
@@ -63,7 +218,9 @@ impl<'a> AddCommand<'a> {
         } else {
             String::new()
         };
-        let label = if args.components.len() > 1 {
+        let label = if args.all {
+            format!("{}Adding all components...", prefix)
+        } else if args.components.len() > 1 {
             format!("{}Adding {} components...", prefix, args.components.len())
         } else {
             format!(
@@ -84,6 +241,9 @@ impl<'a> AddCommand<'a> {
             prefix,
             spinner,
             written_files: Vec::new(),
+            skipped_files: Vec::new(),
+            unchanged_files: Vec::new(),
+            failures: Vec::new(),
         }
     }
 
@@ -92,6 +252,7 @@ impl<'a> AddCommand<'a> {
             Some(config) => config,
             None => return Ok(CommandOutcome::NoOp),
         };
+        self.check_linked_workspaces(&config)?;
 
         self.spinner
             .set_message(format!("{}Detecting framework...", self.prefix));
@@ -102,28 +263,66 @@ impl<'a> AddCommand<'a> {
             "{}Fetching components and dependencies...",
             self.prefix
         ));
-        let lookup = self.fetch_component_lookup().await?;
-        let requested_slugs = match self.resolve_requested_components(&lookup)? {
-            Some(slugs) => slugs,
+        let registries = RegistrySet::try_new(self.client, config.registries.as_ref())
+            .context("failed to set up the configured registries")?;
+        let requested = match self.resolve_requested_components(&registries).await? {
+            Some(requested) => requested,
             None => {
                 self.finish();
                 return Ok(CommandOutcome::NoOp);
             }
         };
-        let component_entries = collect_components(self.client, &requested_slugs).await?;
-        let requested_entries: Vec<_> = component_entries
+        let (mut component_entries, failures) = collect_components(
+            &registries,
+            &requested,
+            workspace_context.framework,
+            self.args.keep_going,
+        )
+        .await?;
+        self.failures = failures;
+        let mut excluded_entries = Vec::new();
+        if self.args.no_internal_deps {
+            (component_entries, excluded_entries) = component_entries
+                .into_iter()
+                .partition(|entry| is_requested(&requested, entry));
+        }
+        for entry in &component_entries {
+            self.reporter.event(
+                "componentResolved",
+                json!({
+                    "dryRun": self.dry_run,
+                    "slug": entry.slug,
+                    "name": entry.component.name,
+                    "registry": entry.registry,
+                    "category": entry.component.category,
+                    "requested": is_requested(&requested, entry),
+                }),
+            );
+        }
+        let mut requested_entries: Vec<_> = component_entries
             .iter()
-            .filter(|entry| requested_slugs.contains(&entry.slug))
+            .filter(|entry| is_requested(&requested, entry))
             .cloned()
             .collect();
         let dependency_entries: Vec<_> = component_entries
             .iter()
-            .filter(|entry| !requested_slugs.contains(&entry.slug))
+            .filter(|entry| !is_requested(&requested, entry))
             .cloned()
             .collect();
 
         self.spinner.finish_and_clear();
+
+        if self.args.print_imports {
+            for (statement, _) in component_import_lines(&workspace_context, &requested_entries) {
+                self.reporter.info(statement);
+            }
+            return Ok(self.settle(CommandOutcome::Completed));
+        }
+
         self.print_component_plan(&requested_entries, &dependency_entries);
+        if !excluded_entries.is_empty() {
+            self.report_excluded_dependencies(&excluded_entries);
+        }
 
         let mut prep_spinner = create_spinner(if self.dry_run {
             "[dry-run] Preparing components..."
@@ -131,8 +330,68 @@ impl<'a> AddCommand<'a> {
             "Preparing components..."
         });
 
-        let (all_component_files, deps_by_workspace) =
-            gather_component_files(self.client, &component_entries, &workspace_context).await?;
+        let (all_component_files, mut deps_by_workspace, failures) = gather_component_files(
+            &registries,
+            &component_entries,
+            &workspace_context,
+            Some(&prep_spinner),
+            self.args.keep_going,
+        )
+        .await?;
+        if !failures.is_empty() {
+            self.failures.extend(failures);
+            let failed: HashSet<_> = self
+                .failures
+                .iter()
+                .map(|failure| (failure.registry.clone(), failure.slug.clone()))
+                .collect();
+            let keep = |entry: &ComponentEntry| {
+                !failed.contains(&(entry.registry.clone(), entry.slug.clone()))
+            };
+            component_entries.retain(keep);
+            requested_entries.retain(keep);
+        }
+        if component_entries.is_empty() && !self.failures.is_empty() {
+            prep_spinner.finish_and_clear();
+            return Ok(self.settle(CommandOutcome::NoOp));
+        }
+        self.drop_ignored_dependencies(&workspace_context, &mut deps_by_workspace);
+        if deps_by_workspace
+            .values()
+            .any(|deps| !deps.ignored.is_empty())
+        {
+            prep_spinner.suspend(|| {
+                report_ignored_dependencies(self.reporter, &workspace_context, &deps_by_workspace)
+            });
+        }
+        let has_conflicts = deps_by_workspace
+            .values()
+            .any(|deps| !deps.conflicts.is_empty());
+        if has_conflicts {
+            prep_spinner.suspend(|| {
+                report_dependency_conflicts(self.reporter, &workspace_context, &deps_by_workspace)
+            });
+        }
+
+        let peer_mismatches = find_peer_mismatches(&workspace_context, &component_entries);
+        if !peer_mismatches.is_empty() {
+            prep_spinner.suspend(|| {
+                report_peer_mismatches(self.reporter, &workspace_context, &peer_mismatches)
+            });
+        }
+
+        if self.args.dependencies_only {
+            prep_spinner.finish_and_clear();
+            let outcome = self.install_dependencies_only(&workspace_context, &deps_by_workspace)?;
+            return Ok(self.settle(outcome));
+        }
+
+        let split_imports = find_cross_workspace_imports(&all_component_files);
+        if !split_imports.is_empty() {
+            prep_spinner.suspend(|| {
+                report_cross_workspace_imports(self.reporter, &workspace_context, &split_imports)
+            });
+        }
 
         prep_spinner.set_message("Checking existing files...");
         let existing_files = find_existing_files(&all_component_files);
@@ -140,15 +399,36 @@ impl<'a> AddCommand<'a> {
         if !existing_files.is_empty() {
             prep_spinner.finish_and_clear();
             if !self.handle_existing_files(&existing_files, &all_component_files)? {
-                return Ok(CommandOutcome::NoOp);
+                return Ok(self.settle(CommandOutcome::NoOp));
             }
         } else {
             self.write_component_files(&mut prep_spinner, &all_component_files)?;
             prep_spinner.finish_and_clear();
         }
 
+        if self.args.replace {
+            self.remove_stale_files(&workspace_context, &component_entries, &all_component_files)?;
+        }
+
+        if let Some(strategy) = self.args.export_strategy
+            && workspace_context
+                .handles()
+                .all(|handle| handle.config.exports.is_none())
+        {
+            self.reporter.warn(format!(
+                "{}",
+                format!(
+                    "--export-strategy {} ignored: no `exports` configured in {}",
+                    strategy.as_str(),
+                    config_path().display()
+                )
+                .if_supports_color(Stream::Stdout, |text| text.yellow())
+            ));
+        }
+
         let export_updates = sync_component_exports(
             self.dry_run,
+            self.args.export_strategy,
             &workspace_context,
             &requested_entries,
             &all_component_files,
@@ -156,14 +436,29 @@ impl<'a> AddCommand<'a> {
         )?;
         self.report_export_updates(&export_updates);
 
-        if deps_by_workspace.values().any(|deps| !deps.is_empty()) {
+        if self.args.format || config.format == Some(true) {
+            self.format_written_files(&workspace_context, &all_component_files);
+        }
+
+        self.update_lockfile(
+            &registries,
+            &workspace_context,
+            &component_entries,
+            &all_component_files,
+        )
+        .await?;
+
+        let installed_dependencies = if deps_by_workspace.values().any(|deps| !deps.is_empty()) {
             handle_workspace_dependencies(
                 self.dry_run,
+                self.args.limit_deps,
                 &workspace_context,
                 &deps_by_workspace,
                 self.reporter,
-            )?;
-        }
+            )?
+        } else {
+            Vec::new()
+        };
 
         let final_spinner = create_spinner(format!(
             "{}{}",
@@ -189,67 +484,444 @@ impl<'a> AddCommand<'a> {
             }
         ));
 
-        print_add_summary(
-            self.reporter,
+        if !self.args.no_summary {
+            if self.args.summary_only {
+                print_add_summary_counts(
+                    self.reporter,
+                    self.dry_run,
+                    &requested_entries,
+                    all_component_files.len()
+                        - self.skipped_files.len()
+                        - self.unchanged_files.len(),
+                    installed_dependencies.len(),
+                    export_updates.len(),
+                );
+            } else {
+                print_add_summary(
+                    self.reporter,
+                    self.dry_run,
+                    &workspace_context,
+                    &requested_entries,
+                    &component_entries,
+                    &all_component_files,
+                    &self.skipped_files,
+                    &self.unchanged_files,
+                );
+            }
+        }
+
+        if let Some(report_path) = self.args.report.clone() {
+            self.write_report(
+                &report_path,
+                &registries,
+                &requested_entries,
+                &component_entries,
+                &all_component_files,
+                &installed_dependencies,
+                &export_updates,
+            )
+            .await?;
+            self.reporter.info(format!(
+                "\n{}",
+                format!("Install report written to {}", report_path.display())
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+
+        if self.args.watch {
+            self.watch(
+                &config,
+                &workspace_context,
+                &requested,
+                &all_component_files,
+            )
+            .await;
+        }
+
+        Ok(self.settle(CommandOutcome::Completed))
+    }
+
+    /// `outcome`, unless `--keep-going` skipped components: those are listed and the run
+    /// ends as a partial failure.
+    fn settle(&self, outcome: CommandOutcome) -> CommandOutcome {
+        if self.failures.is_empty() {
+            return outcome;
+        }
+        self.report_failures();
+        CommandOutcome::PartialFailure
+    }
+
+    /// `--keep-going`: lists the components that were skipped and why.
+    fn report_failures(&self) {
+        self.reporter.error(format!(
+            "\n{}",
+            format!(
+                "{} {} could not be installed:",
+                self.failures.len(),
+                if self.failures.len() == 1 {
+                    "component"
+                } else {
+                    "components"
+                }
+            )
+            .if_supports_color(Stream::Stderr, |text| text.red())
+        ));
+        for failure in &self.failures {
+            let name = match failure.registry.as_deref() {
+                Some(registry) => format!("{} (@{})", failure.name, registry),
+                None => failure.name.clone(),
+            };
+            self.reporter.error(format!(
+                "   {}",
+                format!("{}: {}", name, failure.error)
+                    .if_supports_color(Stream::Stderr, |text| text.red())
+            ));
+        }
+    }
+
+    /// Removes packages listed in each workspace's `dependencies.ignore`, plus those passed
+    /// with `--ignore-dep`, from the sets planned for install.
+    fn drop_ignored_dependencies(
+        &self,
+        context: &WorkspaceContext,
+        deps_by_workspace: &mut HashMap<String, WorkspaceDependencySet>,
+    ) {
+        for handle in context.handles() {
+            let Some(spec) = deps_by_workspace.get_mut(&handle.id) else {
+                continue;
+            };
+            let names: HashSet<String> = handle
+                .config
+                .dependencies
+                .as_ref()
+                .and_then(|dependencies| dependencies.ignore.clone())
+                .unwrap_or_default()
+                .into_iter()
+                .chain(self.args.ignore_deps.iter().cloned())
+                .collect();
+            if !names.is_empty() {
+                spec.ignore(&names);
+            }
+        }
+    }
+
+    /// `--dependencies-only`: installs the resolved components' npm dependencies and
+    /// leaves component files, barrels and the lockfile untouched.
+    fn install_dependencies_only(
+        &mut self,
+        workspace_context: &WorkspaceContext,
+        deps_by_workspace: &HashMap<String, WorkspaceDependencySet>,
+    ) -> CommandResult {
+        if deps_by_workspace.values().all(|deps| deps.is_empty()) {
+            self.reporter.info(format!(
+                "{}",
+                "No npm dependencies required by these components."
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+            return Ok(CommandOutcome::NoOp);
+        }
+
+        let installed = handle_workspace_dependencies(
             self.dry_run,
-            &workspace_context,
-            &requested_entries,
-            &all_component_files,
-        );
+            self.args.limit_deps,
+            workspace_context,
+            deps_by_workspace,
+            self.reporter,
+        )?;
+
+        self.reporter.info(format!(
+            "{}",
+            format!(
+                "{}{} {} for {}",
+                self.prefix,
+                installed.len(),
+                if self.dry_run {
+                    "dependencies would be installed"
+                } else {
+                    "dependencies installed"
+                },
+                self.args.components.join(", ")
+            )
+            .if_supports_color(Stream::Stdout, |text| text.green())
+        ));
 
         Ok(CommandOutcome::Completed)
     }
 
+    /// Polls the registry until Ctrl-C, rewriting installed files whose registry content
+    /// changed. Every poll revalidates with the registry (ETag/Last-Modified), so unchanged
+    /// registries cost a 304 per request.
+    async fn watch(
+        &self,
+        config: &Config,
+        context: &WorkspaceContext,
+        requested: &[RequestedComponent],
+        installed: &[ComponentFileWithContent],
+    ) {
+        let options = RegistryOptions {
+            force_refresh: true,
+            max_age: None,
+            ..self.client.options().clone()
+        };
+        let mut known: HashMap<PathBuf, String> = installed
+            .iter()
+            .filter(|file| !self.skipped_files.contains(&file.display_path))
+            .map(|file| (file.absolute_path.clone(), file.content.clone()))
+            .collect();
+
+        self.reporter.info(format!(
+            "\n{}",
+            format!(
+                "Watching for registry changes every {:?}. Press Ctrl-C to stop.",
+                self.args.watch_interval
+            )
+            .if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
+
+        loop {
+            let poll = async {
+                tokio::time::sleep(self.args.watch_interval).await;
+                // A fresh client per poll so the in-memory registry and manifest are re-read.
+                let client =
+                    RegistryClient::try_with_options(self.client.base_url(), options.clone())?;
+                let registries = RegistrySet::try_new(&client, config.registries.as_ref())?;
+                let (entries, _) =
+                    collect_components(&registries, requested, context.framework, false).await?;
+                gather_component_files(&registries, &entries, context, None, false)
+                    .await
+                    .map(|(files, _, _)| files)
+            };
+            let polled = tokio::select! {
+                _ = tokio::signal::ctrl_c() => break,
+                polled = poll => polled,
+            };
+
+            let timestamp = utc_timestamp(SystemTime::now());
+            let files = match polled {
+                Ok(files) => files,
+                Err(err) => {
+                    self.reporter.warn(format!(
+                        "[{}] {}",
+                        timestamp,
+                        format!("Registry poll failed: {:#}", err)
+                            .if_supports_color(Stream::Stdout, |text| text.yellow())
+                    ));
+                    continue;
+                }
+            };
+
+            let mut updated = Vec::new();
+            for file in files {
+                let Some(previous) = known.get_mut(&file.absolute_path) else {
+                    continue;
+                };
+                if *previous == file.content {
+                    continue;
+                }
+                match write_file(&file.absolute_path, &file.content) {
+                    Ok(()) => {
+                        *previous = file.content;
+                        updated.push(file.display_path.display().to_string());
+                    }
+                    Err(err) => self.reporter.warn(format!(
+                        "[{}] {}",
+                        timestamp,
+                        format!("Failed to write {}: {}", file.display_path.display(), err)
+                            .if_supports_color(Stream::Stdout, |text| text.yellow())
+                    )),
+                }
+            }
+
+            if !updated.is_empty() {
+                self.reporter.info(format!(
+                    "[{}] {} {}",
+                    timestamp,
+                    "updated".if_supports_color(Stream::Stdout, |text| text.green()),
+                    updated.join(", ")
+                ));
+            }
+        }
+
+        self.reporter.info(format!(
+            "\n{}",
+            "Stopped watching.".if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+    }
+
     fn load_config(&mut self) -> Result<Option<Config>> {
         match read_config()? {
             Some(config) => Ok(Some(config)),
             None => {
                 self.spinner.finish_and_clear();
-                self.reporter
-                    .error(format!("{}", "nocta.config.json not found".red()));
-                self.reporter
-                    .warn(format!("{}", "Run \"npx nocta-ui init\" first".yellow()));
+                self.reporter.error(format!(
+                    "{}",
+                    format!("{} not found", config_path().display())
+                        .if_supports_color(Stream::Stderr, |text| text.red())
+                ));
+                self.reporter.warn(format!(
+                    "{}",
+                    "Run \"npx nocta-ui init\" first"
+                        .if_supports_color(Stream::Stdout, |text| text.yellow())
+                ));
                 Ok(None)
             }
         }
     }
 
+    /// Fails before any work starts when linked workspaces point at missing roots or
+    /// missing/invalid configs, listing every broken link at once.
+    fn check_linked_workspaces(&mut self, config: &Config) -> Result<()> {
+        let broken = find_broken_links(config)?;
+        if broken.is_empty() {
+            return Ok(());
+        }
+
+        self.spinner.finish_and_clear();
+        self.reporter.error(format!(
+            "{}",
+            format!(
+                "Linked workspaces in {} are broken:",
+                config_path().display()
+            )
+            .if_supports_color(Stream::Stderr, |text| text.red())
+        ));
+        for link in &broken {
+            self.reporter.info(format!(
+                "   {}",
+                format!("• {}: {}", link.label, link.problem)
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+        self.reporter.warn(format!(
+            "{}",
+            format!(
+                "Re-run \"npx nocta-ui init\" in this workspace to relink, or fix workspace.linkedWorkspaces in {} and {}.",
+                config_path().display(),
+                WORKSPACE_MANIFEST_FILE
+            )
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        anyhow::bail!("{} linked workspace(s) could not be resolved", broken.len())
+    }
+
     fn build_workspace_context(
         &self,
         config: &Config,
         detection: &FrameworkDetection,
     ) -> Result<WorkspaceContext> {
-        build_workspace_context(config, detection)
-    }
-
-    async fn fetch_component_lookup(&self) -> Result<HashMap<String, String>> {
-        let registry = self.client.fetch_registry().await?;
-        Ok(build_component_lookup(&registry.components))
+        let mut context = build_workspace_context(config, detection)?;
+        if let Some(prefix) = self.args.alias_prefix.as_deref() {
+            context.override_alias_prefix(prefix);
+        }
+        if !self.args.strip_prefixes.is_empty() {
+            context.add_strip_prefixes(&self.args.strip_prefixes);
+        }
+        if !self.args.install_args.is_empty() {
+            context.add_install_args(&self.args.install_args);
+        }
+        Ok(context)
     }
 
-    fn resolve_requested_components(
+    async fn resolve_requested_components(
         &mut self,
-        lookup: &HashMap<String, String>,
-    ) -> Result<Option<Vec<String>>> {
-        let mut slugs = Vec::new();
-        for name in &self.args.components {
-            match lookup.get(&name.to_lowercase()) {
-                Some(slug) => slugs.push(slug.clone()),
+        registries: &RegistrySet<'_>,
+    ) -> Result<Option<Vec<RequestedComponent>>> {
+        if self.args.all {
+            let client = registries.client(self.args.registry.as_deref())?;
+            let mut slugs: Vec<String> = client
+                .fetch_registry()
+                .await?
+                .components
+                .into_keys()
+                .collect();
+            slugs.sort();
+            if !self.confirm_install_all(slugs.len())? {
+                return Ok(None);
+            }
+            self.args.components = slugs;
+        }
+
+        let mut lookups: HashMap<Option<String>, HashMap<String, String>> = HashMap::new();
+        let mut requested = Vec::new();
+        let mut names = Vec::new();
+        for name in self.args.components.clone() {
+            let (scope, component_name) = split_scoped_slug(&name);
+            let registry = scope
+                .map(str::to_string)
+                .or_else(|| self.args.registry.clone());
+
+            if !lookups.contains_key(&registry) {
+                let client = registries.client(registry.as_deref())?;
+                let components = client.fetch_registry().await?.components;
+                lookups.insert(registry.clone(), build_component_lookup(&components));
+            }
+
+            match lookups[&registry].get(&component_name.to_lowercase()) {
+                Some(slug) => {
+                    let component = RequestedComponent {
+                        registry,
+                        slug: slug.clone(),
+                    };
+                    // `Button` and `button` name the same component; count it once.
+                    if !requested.contains(&component) {
+                        requested.push(component);
+                        names.push(name);
+                    }
+                }
                 None => {
                     self.spinner.finish_and_clear();
                     self.reporter.error(format!(
                         "{}",
-                        format!("Component \"{}\" not found", name).red()
+                        format!("Component \"{}\" not found", name)
+                            .if_supports_color(Stream::Stderr, |text| text.red())
                     ));
                     self.reporter.warn(format!(
                         "{}",
-                        "Run \"npx nocta-ui list\" to see available components".yellow()
+                        "Run \"npx nocta-ui list\" to see available components"
+                            .if_supports_color(Stream::Stdout, |text| text.yellow())
                     ));
                     return Ok(None);
                 }
             }
         }
-        Ok(Some(slugs))
+        self.args.components = names;
+        Ok(Some(requested))
+    }
+
+    /// Shows how many components `--all` resolves to and asks before installing them,
+    /// unless `--yes` or `--dry-run` was passed.
+    fn confirm_install_all(&self, count: usize) -> Result<bool> {
+        self.spinner.suspend(|| {
+            self.reporter.info(format!(
+                "{}",
+                format!("{}--all selects {} components", self.prefix, count)
+                    .if_supports_color(Stream::Stdout, |text| text.blue())
+            ))
+        });
+        if self.args.yes || self.dry_run {
+            return Ok(true);
+        }
+        if !prompts_enabled() {
+            anyhow::bail!(
+                "--all selects {} components; pass --yes to install them without a prompt",
+                count
+            );
+        }
+
+        let proceed = self.spinner.suspend(|| {
+            Confirm::new()
+                .with_prompt(format!("Install all {} components?", count))
+                .default(false)
+                .interact()
+        })?;
+        if !proceed {
+            self.spinner.finish_and_clear();
+            self.reporter.warn(format!(
+                "{}",
+                "Installation cancelled".if_supports_color(Stream::Stdout, |text| text.red())
+            ));
+        }
+        Ok(proceed)
     }
 
     fn print_component_plan(
@@ -267,23 +939,28 @@ impl<'a> AddCommand<'a> {
                     self.args.components[0].clone()
                 }
             )
-            .blue()
+            .if_supports_color(Stream::Stdout, |text| text.blue())
         ));
 
         for entry in requested_entries {
             self.reporter.info(format!(
                 "   {}",
-                format!("• {} (requested)", entry.component.name).green()
+                format!("• {}", entry.labeled_name("requested"))
+                    .if_supports_color(Stream::Stdout, |text| text.green())
             ));
         }
 
         if !dependency_entries.is_empty() {
-            self.reporter
-                .info(format!("{}", "\nWith internal dependencies:".blue()));
+            self.reporter.info(format!(
+                "{}",
+                "\nWith internal dependencies:"
+                    .if_supports_color(Stream::Stdout, |text| text.blue())
+            ));
             for entry in dependency_entries {
                 self.reporter.info(format!(
                     "   {}",
-                    format!("• {}", entry.component.name).dimmed()
+                    format!("• {}", entry.labeled_name(""))
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
                 ));
             }
         }
@@ -291,70 +968,525 @@ impl<'a> AddCommand<'a> {
         self.reporter.blank();
     }
 
+    /// `--no-internal-deps`: lists the internal dependencies left out of the install, since the
+    /// requested components may not work unless they are already present.
+    fn report_excluded_dependencies(&self, excluded: &[ComponentEntry]) {
+        self.reporter.warn(format!(
+            "{}",
+            "Skipping internal dependencies (--no-internal-deps):"
+                .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        for entry in excluded {
+            self.reporter.info(format!(
+                "   {}",
+                format!("• {}", entry.labeled_name(""))
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+        self.reporter.warn(format!(
+            "{}",
+            "The requested components may not work unless these are already installed."
+                .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        self.reporter.blank();
+    }
+
     fn handle_existing_files(
         &mut self,
         existing_files: &[PathBuf],
         component_files: &[ComponentFileWithContent],
     ) -> Result<bool> {
-        self.reporter
-            .warn(format!("{}", "The following files already exist:".yellow()));
+        self.reporter.warn(format!(
+            "{}",
+            "The following files already exist:"
+                .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
         for path in existing_files {
-            self.reporter
-                .info(format!("   {}", path.display().to_string().dimmed()));
+            self.reporter.info(format!(
+                "   {}",
+                path.display()
+                    .to_string()
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
         }
 
         if self.dry_run {
             self.reporter.info(format!(
                 "\n{}",
-                "[dry-run] Would overwrite the files above".blue()
+                "[dry-run] Would overwrite the files above"
+                    .if_supports_color(Stream::Stdout, |text| text.blue())
             ));
             self.reporter.blank();
             let spinner = create_spinner("[dry-run] Preparing file writes...");
-            write_component_files(component_files, true, &mut self.written_files)?;
+            self.unchanged_files = write_component_files(
+                self.reporter,
+                component_files,
+                true,
+                &mut self.written_files,
+            )?;
             spinner.finish_and_clear();
             Ok(true)
-        } else {
-            let overwrite = Confirm::new()
-                .with_prompt("Do you want to overwrite these files?")
-                .default(false)
+        } else if self.args.interactive && prompts_enabled() {
+            let items: Vec<String> = existing_files
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            let selection = MultiSelect::new()
+                .with_prompt("Select files to overwrite (space to toggle, enter to confirm)")
+                .items(&items)
                 .interact()?;
 
-            if !overwrite {
-                self.reporter
-                    .warn(format!("{}", "Installation cancelled".red()));
-                return Ok(false);
-            }
+            let overwrite: HashSet<&Path> = selection
+                .into_iter()
+                .filter_map(|index| existing_files.get(index))
+                .map(PathBuf::as_path)
+                .collect();
+            let (to_write, skipped): (Vec<_>, Vec<_>) =
+                component_files.iter().cloned().partition(|file| {
+                    !existing_files.contains(&file.display_path)
+                        || overwrite.contains(file.display_path.as_path())
+                });
+            self.skipped_files = skipped.into_iter().map(|file| file.display_path).collect();
 
             let spinner = create_spinner("Installing component files...");
-            write_component_files(component_files, false, &mut self.written_files)?;
+            self.unchanged_files =
+                write_component_files(self.reporter, &to_write, false, &mut self.written_files)?;
             spinner.finish_and_clear();
             Ok(true)
-        }
-    }
+        } else {
+            let overwrite = if self.args.yes {
+                true
+            } else if !prompts_enabled() {
+                let paths: Vec<String> = existing_files
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                anyhow::bail!(
+                    "refusing to overwrite existing files without a prompt: {} (pass --yes to overwrite them)",
+                    paths.join(", ")
+                );
+            } else {
+                Confirm::new()
+                    .with_prompt("Do you want to overwrite these files?")
+                    .default(false)
+                    .interact()?
+            };
 
-    fn write_component_files(
-        &mut self,
-        spinner: &mut ProgressBar,
-        component_files: &[ComponentFileWithContent],
+            if !overwrite {
+                self.reporter.warn(format!(
+                    "{}",
+                    "Installation cancelled".if_supports_color(Stream::Stdout, |text| text.red())
+                ));
+                return Ok(false);
+            }
+
+            let spinner = create_spinner("Installing component files...");
+            self.unchanged_files = write_component_files(
+                self.reporter,
+                component_files,
+                false,
+                &mut self.written_files,
+            )?;
+            spinner.finish_and_clear();
+            Ok(true)
+        }
+    }
+
+    fn write_component_files(
+        &mut self,
+        spinner: &mut Spinner,
+        component_files: &[ComponentFileWithContent],
     ) -> Result<()> {
         if self.dry_run {
             spinner.set_message("[dry-run] Preparing file writes...");
         } else {
             spinner.set_message("Installing component files...");
         }
-        write_component_files(component_files, self.dry_run, &mut self.written_files)?;
+        self.unchanged_files = write_component_files(
+            self.reporter,
+            component_files,
+            self.dry_run,
+            &mut self.written_files,
+        )?;
+        Ok(())
+    }
+
+    /// `--replace`: deletes files the lockfile records for `entries` that the components
+    /// no longer ship, so renamed or dropped upstream files do not linger.
+    fn remove_stale_files(
+        &mut self,
+        context: &WorkspaceContext,
+        entries: &[ComponentEntry],
+        files: &[ComponentFileWithContent],
+    ) -> Result<()> {
+        let lock_path = context.lock_path();
+        let Some(lockfile) = read_lockfile_from(&lock_path)
+            .with_context(|| format!("failed to read {}", lock_path.display()))?
+        else {
+            self.reporter.warn(format!(
+                "{}",
+                format!(
+                    "--replace: no {} found, so previously installed files cannot be determined; existing files are only overwritten",
+                    LOCK_FILE_NAME
+                )
+                .if_supports_color(Stream::Stdout, |text| text.yellow())
+            ));
+            return Ok(());
+        };
+
+        let current: HashSet<String> = files
+            .iter()
+            .map(|file| context.lock_entry_path(&file.display_path))
+            .collect();
+        let mut stale = Vec::new();
+        for locked in entries
+            .iter()
+            .filter_map(|entry| {
+                lockfile.component(&lock_key(entry.registry.as_deref(), &entry.slug))
+            })
+            .flat_map(|locked| &locked.files)
+            .filter(|file| !current.contains(&file.path))
+        {
+            let Some(absolute) = context.lock_entry_abs(&locked.path) else {
+                self.reporter.warn(format!(
+                    "{}",
+                    format!(
+                        "--replace: skipping {}, which is outside the workspace",
+                        locked.path
+                    )
+                    .if_supports_color(Stream::Stdout, |text| text.yellow())
+                ));
+                continue;
+            };
+            if !absolute.is_file() {
+                continue;
+            }
+            if !matches_locked_hash(&absolute, &locked.hash)? {
+                self.reporter.warn(format!(
+                    "{}",
+                    format!(
+                        "--replace: keeping {}, which has local changes",
+                        locked.path
+                    )
+                    .if_supports_color(Stream::Stdout, |text| text.yellow())
+                ));
+                continue;
+            }
+            stale.push((locked.path.as_str(), absolute));
+        }
+        if stale.is_empty() {
+            return Ok(());
+        }
+
+        self.reporter.info(format!(
+            "{}",
+            if self.dry_run {
+                "[dry-run] Would remove files no longer part of these components:"
+            } else {
+                "Removing files no longer part of these components:"
+            }
+            .if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
+        for (path, absolute) in stale {
+            self.reporter.info(format!(
+                "   {}",
+                path.if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+            if self.dry_run {
+                continue;
+            }
+            ensure_change_record(&absolute, &mut self.written_files)?;
+            fs::remove_file(&absolute)
+                .with_context(|| format!("failed to remove {}", absolute.display()))?;
+        }
+        Ok(())
+    }
+
+    async fn update_lockfile(
+        &mut self,
+        registries: &RegistrySet<'_>,
+        context: &WorkspaceContext,
+        entries: &[ComponentEntry],
+        files: &[ComponentFileWithContent],
+    ) -> Result<()> {
+        let lock_path = context.lock_path();
+        if self.dry_run {
+            self.reporter.info(format!(
+                "{}",
+                format!("[dry-run] Would update {}", LOCK_FILE_NAME)
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+            return Ok(());
+        }
+
+        let mut lockfile = read_lockfile_from(&lock_path)
+            .with_context(|| format!("failed to read {}", lock_path.display()))?
+            .unwrap_or_default();
+
+        let mut versions: HashMap<Option<String>, String> = HashMap::new();
+        for entry in entries {
+            let registry_version = match versions.get(&entry.registry) {
+                Some(version) => version.clone(),
+                None => {
+                    let client = registries.client(entry.registry.as_deref())?;
+                    let version = client.fetch_registry().await?.version;
+                    versions.insert(entry.registry.clone(), version.clone());
+                    version
+                }
+            };
+
+            let key = lock_key(entry.registry.as_deref(), &entry.slug);
+            let fresh = files
+                .iter()
+                .filter(|file| file.component_slug == entry.slug && file.registry == entry.registry)
+                .map(|file| {
+                    let locked = LockedFile {
+                        path: context.lock_entry_path(&file.display_path),
+                        hash: written_content_hash(file, self.dry_run),
+                    };
+                    (locked, self.skipped_files.contains(&file.display_path))
+                })
+                .collect();
+            let locked_files = merge_locked_files(fresh, lockfile.component(&key));
+
+            lockfile.record(
+                key,
+                LockedComponent {
+                    name: entry.component.name.clone(),
+                    registry: entry.registry.clone(),
+                    registry_version,
+                    files: locked_files,
+                },
+            );
+        }
+
+        ensure_change_record(&lock_path, &mut self.written_files)?;
+        write_lockfile_to(&lock_path, &lockfile)
+            .with_context(|| format!("failed to write {}", lock_path.display()))?;
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn write_report(
+        &self,
+        path: &Path,
+        registries: &RegistrySet<'_>,
+        requested_entries: &[ComponentEntry],
+        entries: &[ComponentEntry],
+        files: &[ComponentFileWithContent],
+        dependencies: &[DependencyRecord],
+        export_updates: &[ExportUpdate],
+    ) -> Result<()> {
+        let mut registry_versions: BTreeMap<Option<String>, (String, String)> = BTreeMap::new();
+        for entry in entries {
+            if registry_versions.contains_key(&entry.registry) {
+                continue;
+            }
+            let client = registries.client(entry.registry.as_deref())?;
+            let version = client.fetch_registry().await?.version;
+            registry_versions.insert(
+                entry.registry.clone(),
+                (client.base_url().to_string(), version),
+            );
+        }
+
+        let report_registries: Vec<Value> = registry_versions
+            .iter()
+            .map(|(name, (url, version))| json!({ "name": name, "url": url, "version": version }))
+            .collect();
+        let components: Vec<Value> = entries
+            .iter()
+            .map(|entry| {
+                json!({
+                    "name": entry.component.name,
+                    "slug": entry.slug,
+                    "registry": entry.registry,
+                    "registryVersion": registry_versions
+                        .get(&entry.registry)
+                        .map(|(_, version)| version),
+                    "requested": requested_entries.iter().any(|requested| {
+                        requested.slug == entry.slug && requested.registry == entry.registry
+                    }),
+                })
+            })
+            .collect();
+        let report_files: Vec<Value> = files
+            .iter()
+            .filter(|file| !self.skipped_files.contains(&file.display_path))
+            .map(|file| {
+                json!({
+                    "path": normalize_relative_path(&file.display_path),
+                    "component": file.component_slug,
+                    "registry": file.registry,
+                    "hash": written_content_hash(file, self.dry_run),
+                })
+            })
+            .collect();
+        let skipped: Vec<String> = self
+            .skipped_files
+            .iter()
+            .map(|path| normalize_relative_path(path))
+            .collect();
+        let unchanged: Vec<String> = self
+            .unchanged_files
+            .iter()
+            .map(|path| normalize_relative_path(path))
+            .collect();
+        let exports: Vec<Value> = export_updates
+            .iter()
+            .map(|update| {
+                json!({
+                    "path": normalize_relative_path(&update.display_path),
+                    "workspace": update.workspace_label,
+                    "change": match update.change {
+                        ExportChangeKind::Created => "created",
+                        ExportChangeKind::Updated => "updated",
+                    },
+                    "statements": update.statements,
+                })
+            })
+            .collect();
+
+        write_report(
+            path,
+            "add",
+            self.dry_run,
+            json!({
+                "registries": report_registries,
+                "components": components,
+                "files": report_files,
+                "skippedFiles": skipped,
+                "failedComponents": self.failures.iter().map(|failure| json!({
+                    "component": failure.slug,
+                    "registry": failure.registry,
+                    "error": failure.error,
+                })).collect::<Vec<_>>(),
+                "unchangedFiles": unchanged,
+                "dependencies": dependencies.iter().map(DependencyRecord::to_json).collect::<Vec<_>>(),
+                "exports": exports,
+            }),
+        )
+    }
+
+    /// Formats written component and barrel files with each workspace's formatter.
+    /// Failures only warn, since the files are already in place.
+    fn format_written_files(
+        &self,
+        context: &WorkspaceContext,
+        component_files: &[ComponentFileWithContent],
+    ) {
+        let targets: Vec<PathBuf> = if self.dry_run {
+            component_files
+                .iter()
+                .map(|file| file.absolute_path.clone())
+                .collect()
+        } else {
+            // Files removed by `--replace` are recorded too; only format what exists.
+            self.written_files
+                .iter()
+                .map(|change| change.path.clone())
+                .filter(|path| path.exists())
+                .collect()
+        };
+
+        let targets = targets
+            .into_iter()
+            .filter(|path| !has_binary_extension(path))
+            .collect();
+
+        for (handle, files) in group_files_by_workspace(context, targets) {
+            let Some(formatter) = detect_formatter(&handle.root_abs) else {
+                self.reporter.warn(format!(
+                    "{}",
+                    format!(
+                        "No prettier or biome setup found for {}; skipping formatting",
+                        handle.label
+                    )
+                    .if_supports_color(Stream::Stdout, |text| text.yellow())
+                ));
+                continue;
+            };
+            let Some(plan) = plan_format(formatter, &handle.root_abs, &files) else {
+                continue;
+            };
+
+            if self.dry_run {
+                self.reporter.info(format!(
+                    "{}",
+                    format!(
+                        "[dry-run] Would format {} file(s) in {} with {}",
+                        files.len(),
+                        handle.label,
+                        formatter.as_str()
+                    )
+                    .if_supports_color(Stream::Stdout, |text| text.blue())
+                ));
+                self.reporter.info(format!(
+                    "{}",
+                    format!("   Command: {}", plan.command_line().join(" "))
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
+                continue;
+            }
+
+            let spinner = create_spinner(format!(
+                "Formatting {} file(s) with {}...",
+                files.len(),
+                formatter.as_str()
+            ));
+            let result = plan.execute();
+            spinner.finish_and_clear();
+            match result {
+                Ok(()) => self.reporter.info(format!(
+                    "{}",
+                    format!(
+                        "Formatted {} file(s) in {} with {}",
+                        files.len(),
+                        handle.label,
+                        formatter.as_str()
+                    )
+                    .if_supports_color(Stream::Stdout, |text| text.green())
+                )),
+                Err(err) => self.reporter.warn(format!(
+                    "{}",
+                    format!("Formatting skipped for {}: {:#}", handle.label, err)
+                        .if_supports_color(Stream::Stdout, |text| text.yellow())
+                )),
+            }
+        }
+    }
+
     fn report_export_updates(&self, updates: &[ExportUpdate]) {
-        if updates.is_empty() {
+        for update in updates {
+            self.reporter.event(
+                "exportUpdated",
+                json!({
+                    "dryRun": self.dry_run,
+                    "path": normalize_relative_path(&update.display_path),
+                    "workspace": update.workspace_label,
+                    "change": match update.change {
+                        ExportChangeKind::Created => "created",
+                        ExportChangeKind::Updated => "updated",
+                    },
+                    "statements": update.statements,
+                }),
+            );
+        }
+
+        if updates.is_empty() || self.args.summary_only {
             return;
         }
 
         let heading = if self.dry_run {
-            format!("{}", "[dry-run] Export barrels:".blue())
+            format!(
+                "{}",
+                "[dry-run] Export barrels:".if_supports_color(Stream::Stdout, |text| text.blue())
+            )
         } else {
-            format!("{}", "Export barrels updated:".green())
+            format!(
+                "{}",
+                "Export barrels updated:".if_supports_color(Stream::Stdout, |text| text.green())
+            )
         };
         self.reporter.info(format!("\n{}", heading));
 
@@ -372,35 +1504,77 @@ impl<'a> AddCommand<'a> {
                 update.display_path.display(),
                 update.workspace_label
             );
-            self.reporter.info(format!("   {}", summary.dimmed()));
+            self.reporter.info(format!(
+                "   {}",
+                summary.if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+            if self.args.exports_diff {
+                self.print_export_diff(update);
+                continue;
+            }
             for stmt in &update.statements {
-                self.reporter.info(format!("      {}", stmt.dimmed()));
+                self.reporter.info(format!(
+                    "      {}",
+                    stmt.if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
             }
         }
     }
 
+    /// Prints the barrel's current content against the content `add` writes (or would
+    /// write), so hand-written exports around the managed block can be checked.
+    fn print_export_diff(&self, update: &ExportUpdate) {
+        let path = normalize_relative_path(&update.display_path);
+        let old_header = match update.previous_content {
+            Some(_) => format!("a/{}", path),
+            None => "/dev/null".to_string(),
+        };
+        let new_header = format!("b/{}", path);
+        let previous = update.previous_content.as_deref().unwrap_or_default();
+        let diff = TextDiff::from_lines(previous, update.new_content.as_str());
+        let rendered = diff
+            .unified_diff()
+            .header(&old_header, &new_header)
+            .to_string();
+
+        for line in rendered.lines() {
+            let line = if line.starts_with("+++") || line.starts_with("---") {
+                format!(
+                    "{}",
+                    line.if_supports_color(Stream::Stdout, |text| text.bold())
+                )
+            } else if line.starts_with("@@") {
+                format!(
+                    "{}",
+                    line.if_supports_color(Stream::Stdout, |text| text.cyan())
+                )
+            } else if line.starts_with('+') {
+                format!(
+                    "{}",
+                    line.if_supports_color(Stream::Stdout, |text| text.green())
+                )
+            } else if line.starts_with('-') {
+                format!(
+                    "{}",
+                    line.if_supports_color(Stream::Stdout, |text| text.red())
+                )
+            } else {
+                format!(
+                    "{}",
+                    line.if_supports_color(Stream::Stdout, |text| text.dimmed())
+                )
+            };
+            self.reporter.info(format!("      {}", line));
+        }
+    }
+
     fn finish(&mut self) {
         self.spinner.finish_and_clear();
     }
 
     fn rollback(&self) {
-        if self.dry_run || self.written_files.is_empty() {
-            return;
-        }
-
-        match rollback_file_changes(&self.written_files) {
-            Ok(_) => {
-                self.reporter.warn(format!(
-                    "{}",
-                    "Rolled back written component files".yellow()
-                ));
-            }
-            Err(err) => {
-                self.reporter.error(format!(
-                    "{}",
-                    format!("Failed to roll back written files: {}", err).red()
-                ));
-            }
+        if !self.dry_run {
+            rollback_and_report(self.reporter, &self.written_files);
         }
     }
 }
@@ -408,8 +1582,18 @@ impl<'a> AddCommand<'a> {
 pub async fn run(
     client: &RegistryClient,
     reporter: &ConsoleReporter,
-    args: AddArgs,
+    mut args: AddArgs,
 ) -> CommandResult {
+    ensure_outside_node_modules("add")?;
+    warn_cache_fallback(reporter);
+    if let Some(path) = args.from_file.as_deref() {
+        let listed = read_component_list(path)?;
+        if listed.is_empty() && args.components.is_empty() {
+            anyhow::bail!("{} does not list any components", path.display());
+        }
+        args.components.extend(listed);
+    }
+
     let mut command = AddCommand::new(client, reporter, args);
     match command.execute().await {
         Ok(outcome) => Ok(outcome),
@@ -421,29 +1605,84 @@ pub async fn run(
     }
 }
 
+/// Reads component slugs for `--from-file`: one per line, blank lines and `#` comments skipped.
+fn read_component_list(path: &Path) -> Result<Vec<String>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read component list {}", path.display()))?;
+    Ok(contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct RequestedComponent {
+    registry: Option<String>,
+    slug: String,
+}
+
 #[derive(Clone)]
 struct ComponentEntry {
+    registry: Option<String>,
     slug: String,
     component: Component,
 }
 
+impl ComponentEntry {
+    fn display_name(&self) -> String {
+        match self.registry.as_deref() {
+            Some(registry) => format!("{} (@{})", self.component.name, registry),
+            None => self.component.name.clone(),
+        }
+    }
+
+    /// `display_name` followed by the component's category and an optional `note`,
+    /// e.g. `Spinner (feedback)` or `Button (forms, requested)`.
+    fn labeled_name(&self, note: &str) -> String {
+        let labels: Vec<&str> = [self.component.category.trim(), note]
+            .into_iter()
+            .filter(|label| !label.is_empty())
+            .collect();
+        if labels.is_empty() {
+            self.display_name()
+        } else {
+            format!("{} ({})", self.display_name(), labels.join(", "))
+        }
+    }
+}
+
+fn is_requested(requested: &[RequestedComponent], entry: &ComponentEntry) -> bool {
+    requested
+        .iter()
+        .any(|request| request.registry == entry.registry && request.slug == entry.slug)
+}
+
 #[derive(Clone)]
-struct WorkspaceHandle {
+pub(crate) struct WorkspaceHandle {
     id: String,
     label: String,
     kind: WorkspaceKind,
     root_abs: PathBuf,
+    /// Root as written in the config, joined onto the repo root without resolving symlinks.
+    root_display: PathBuf,
     root_rel: String,
     config: Config,
     alias_prefix: String,
     component_import_alias: Option<String>,
+    /// Prefix for imports of the utils module; `aliasPrefixes.utils`, else the components prefix.
+    utils_alias_prefix: String,
+    utils_import_alias: Option<String>,
     package_name: Option<String>,
     package_manager_context: PackageManagerContext,
 }
 
-struct WorkspaceContext {
+pub(crate) struct WorkspaceContext {
     current_dir: PathBuf,
     handles: Vec<WorkspaceHandle>,
+    /// Framework detected for this run; selects framework-specific component files.
+    framework: FrameworkKind,
 }
 
 impl WorkspaceContext {
@@ -457,6 +1696,68 @@ impl WorkspaceContext {
         self.handles.iter()
     }
 
+    /// Path shown to the user for a file under `handle`, relative to the current directory.
+    ///
+    /// Uses the configured (unresolved) workspace root so symlinked workspaces keep
+    /// their in-repo location instead of the symlink target.
+    fn display_path(&self, handle: &WorkspaceHandle, relative: &Path) -> PathBuf {
+        let logical = handle.root_display.join(relative);
+        diff_paths(&logical, &self.current_dir).unwrap_or(logical)
+    }
+
+    /// `nocta.lock.json` in the primary workspace root, wherever the command runs from.
+    pub(crate) fn lock_path(&self) -> PathBuf {
+        self.primary().root_display.join(LOCK_FILE_NAME)
+    }
+
+    /// Lockfile form of `display_path`: relative to the lockfile's directory, `/`-separated.
+    pub(crate) fn lock_entry_path(&self, display_path: &Path) -> String {
+        let logical = normalize_path_lexically(&self.current_dir.join(display_path));
+        let lock_dir = &self.primary().root_display;
+        normalize_relative_path(&diff_paths(&logical, lock_dir).unwrap_or(logical))
+    }
+
+    /// Absolute path of a lockfile entry path, or `None` when the entry is absolute or
+    /// resolves outside every workspace root.
+    fn lock_entry_abs(&self, path: &str) -> Option<PathBuf> {
+        let relative = Path::new(path);
+        if relative.has_root() || relative.is_absolute() {
+            return None;
+        }
+        let absolute = normalize_path_lexically(&self.primary().root_display.join(relative));
+        self.handles()
+            .any(|handle| {
+                absolute.starts_with(&handle.root_display) || absolute.starts_with(&handle.root_abs)
+            })
+            .then_some(absolute)
+    }
+
+    /// Replaces the configured import prefix on every workspace handle.
+    fn override_alias_prefix(&mut self, prefix: &str) {
+        for handle in &mut self.handles {
+            handle.alias_prefix = prefix.to_string();
+            handle.utils_alias_prefix = prefix.to_string();
+        }
+    }
+
+    /// Flag prefixes are tried before the ones in each workspace's config.
+    fn add_strip_prefixes(&mut self, prefixes: &[String]) {
+        for handle in &mut self.handles {
+            let configured = handle.config.strip_prefixes.take().unwrap_or_default();
+            handle.config.strip_prefixes =
+                Some(prefixes.iter().cloned().chain(configured).collect());
+        }
+    }
+
+    fn add_install_args(&mut self, install_args: &[String]) {
+        for handle in &mut self.handles {
+            handle
+                .package_manager_context
+                .extra_install_args
+                .extend(install_args.iter().cloned());
+        }
+    }
+
     fn handle_by_id(&self, id: &str) -> Option<&WorkspaceHandle> {
         self.handles.iter().find(|handle| handle.id == id)
     }
@@ -472,39 +1773,144 @@ struct ComponentFileWithContent {
     absolute_path: PathBuf,
     display_path: PathBuf,
     content: String,
+    /// Raw contents of a binary asset, written as-is; `content` is empty for these.
+    bytes: Option<Vec<u8>>,
     component_name: String,
     component_slug: String,
+    registry: Option<String>,
     file_type: String,
+    registry_path: String,
+    /// Import specifiers as written in the registry source, before alias normalization.
+    registry_imports: Vec<String>,
+}
+
+enum FetchedFile {
+    Text(String),
+    Binary(Vec<u8>),
 }
 
 #[derive(Clone)]
 struct PendingComponentFile {
     workspace_handle: WorkspaceHandle,
     workspace_id: String,
+    registry: Option<String>,
     absolute_path: PathBuf,
     display_path: PathBuf,
     component_name: String,
     component_slug: String,
     file_type: String,
     registry_path: String,
-}
-
-#[derive(Clone)]
-struct FileChange {
-    path: PathBuf,
-    previous_contents: Option<Vec<u8>>,
+    binary: bool,
 }
 
 #[derive(Clone, Default)]
 struct WorkspaceDependencySet {
     regular: BTreeMap<String, String>,
     dev: BTreeMap<String, String>,
+    required_by: HashMap<(DependencyScope, String), String>,
+    conflicts: Vec<DependencyConflict>,
+    /// Dependencies removed by `dependencies.ignore` or `--ignore-dep`; reported, never installed.
+    ignored: Vec<IgnoredDependency>,
+}
+
+#[derive(Clone, Debug)]
+struct IgnoredDependency {
+    name: String,
+    version: String,
+    required_by: String,
+}
+
+#[derive(Clone, Debug)]
+struct DependencyConflict {
+    name: String,
+    kept: String,
+    kept_by: String,
+    dropped: String,
+    dropped_by: String,
 }
 
 impl WorkspaceDependencySet {
     fn is_empty(&self) -> bool {
         self.regular.is_empty() && self.dev.is_empty()
     }
+
+    /// Moves every dependency named in `names` out of the install sets into `ignored`.
+    fn ignore(&mut self, names: &HashSet<String>) {
+        for (scopes, map) in [
+            (
+                [DependencyScope::Regular, DependencyScope::Peer].as_slice(),
+                &mut self.regular,
+            ),
+            ([DependencyScope::Dev].as_slice(), &mut self.dev),
+        ] {
+            map.retain(|name, version| {
+                if !names.contains(name) {
+                    return true;
+                }
+                let required_by = scopes
+                    .iter()
+                    .find_map(|scope| self.required_by.get(&(*scope, name.clone())))
+                    .cloned()
+                    .unwrap_or_default();
+                self.ignored.push(IgnoredDependency {
+                    name: name.clone(),
+                    version: version.clone(),
+                    required_by,
+                });
+                false
+            });
+        }
+    }
+
+    fn require(&mut self, scope: DependencyScope, name: &str, version: &str, component: &str) {
+        let map = match scope {
+            DependencyScope::Dev => &mut self.dev,
+            _ => &mut self.regular,
+        };
+        let key = (scope, name.to_string());
+
+        let Some(existing) = map.get(name).cloned() else {
+            map.insert(name.to_string(), version.to_string());
+            self.required_by.insert(key, component.to_string());
+            return;
+        };
+
+        let use_incoming = match merge_version_ranges(&existing, version) {
+            VersionRangeMerge::KeepExisting => false,
+            VersionRangeMerge::UseIncoming => true,
+            VersionRangeMerge::Conflict { use_incoming } => {
+                let existing_by = self.required_by.get(&key).cloned().unwrap_or_default();
+                let (kept, kept_by, dropped, dropped_by) = if use_incoming {
+                    (
+                        version.to_string(),
+                        component.to_string(),
+                        existing,
+                        existing_by,
+                    )
+                } else {
+                    (
+                        existing,
+                        existing_by,
+                        version.to_string(),
+                        component.to_string(),
+                    )
+                };
+                self.conflicts.push(DependencyConflict {
+                    name: name.to_string(),
+                    kept,
+                    kept_by,
+                    dropped,
+                    dropped_by,
+                });
+                use_incoming
+            }
+        };
+
+        if use_incoming {
+            map.insert(name.to_string(), version.to_string());
+            self.required_by.insert(key, component.to_string());
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -513,6 +1919,8 @@ struct ExportUpdate {
     display_path: PathBuf,
     statements: Vec<String>,
     change: ExportChangeKind,
+    previous_content: Option<String>,
+    new_content: String,
 }
 
 #[derive(Debug)]
@@ -522,16 +1930,16 @@ enum ExportChangeKind {
 }
 
 fn resolve_alias_prefix(config: &Config, detection: Option<&FrameworkDetection>) -> String {
-    if let Some(prefixes) = config.alias_prefixes.as_ref() {
-        if let Some(prefix) = prefixes.components.as_ref() {
-            return prefix.clone();
-        }
+    if let Some(prefixes) = config.alias_prefixes.as_ref()
+        && let Some(prefix) = prefixes.components.as_ref()
+    {
+        return prefix.clone();
     }
 
-    if let Some(details) = detection {
-        if details.framework == FrameworkKind::ReactRouter {
-            return "~".into();
-        }
+    if let Some(details) = detection
+        && details.framework == FrameworkKind::ReactRouter
+    {
+        return "~".into();
     }
 
     "@".into()
@@ -545,13 +1953,109 @@ fn resolve_component_import_alias(config: &Config) -> Option<String> {
         .map(|alias| alias.trim_end_matches('/').to_string())
 }
 
-fn build_workspace_context(
+fn resolve_utils_alias_prefix(config: &Config, detection: Option<&FrameworkDetection>) -> String {
+    config
+        .alias_prefixes
+        .as_ref()
+        .and_then(|prefixes| prefixes.utils.clone())
+        .unwrap_or_else(|| resolve_alias_prefix(config, detection))
+}
+
+fn resolve_utils_import_alias(config: &Config) -> Option<String> {
+    config
+        .aliases
+        .utils
+        .import_alias()
+        .map(|alias| alias.trim_end_matches('/').to_string())
+}
+
+/// A `linkedWorkspaces` entry whose root or config cannot be used.
+struct BrokenLink {
+    label: String,
+    problem: String,
+}
+
+/// Why [`resolve_link`] could not use a `linkedWorkspaces` entry.
+enum LinkProblem {
+    MissingRoot,
+    MissingConfig,
+    UnreadableConfig(String),
+}
+
+/// A usable `linkedWorkspaces` entry.
+struct ResolvedLink {
+    root_abs: PathBuf,
+    /// Root as written in the config, joined onto the repo root without resolving symlinks.
+    root_display: PathBuf,
+    config: Config,
+}
+
+/// The current directory and the repo root above it, both canonicalized.
+fn locate_repo_root() -> Result<(PathBuf, PathBuf)> {
+    let current_dir = canonicalize_path(&std::env::current_dir()?);
+    let repo_root = canonicalize_path(&find_repo_root(&current_dir).unwrap_or(current_dir.clone()));
+    Ok((current_dir, repo_root))
+}
+
+/// Resolves `link`, whose root is relative to `repo_root` and whose config is relative to
+/// the linking workspace's root, `workspace_root_abs`.
+fn resolve_link(
+    repo_root: &Path,
+    workspace_root_abs: &Path,
+    link: &WorkspaceLink,
+) -> Result<ResolvedLink, LinkProblem> {
+    let root = repo_root.join(Path::new(&link.root));
+    if !root.is_dir() {
+        return Err(LinkProblem::MissingRoot);
+    }
+    let config_path = canonicalize_path(&workspace_root_abs.join(Path::new(&link.config)));
+    let config = read_config_from(&config_path)
+        .map_err(|err| LinkProblem::UnreadableConfig(err.to_string()))?
+        .ok_or(LinkProblem::MissingConfig)?;
+    Ok(ResolvedLink {
+        root_abs: canonicalize_path(&root),
+        root_display: normalize_path_lexically(&root),
+        config,
+    })
+}
+
+fn find_broken_links(config: &Config) -> Result<Vec<BrokenLink>> {
+    let Some(workspace_cfg) = config.workspace.as_ref() else {
+        return Ok(Vec::new());
+    };
+    let (_, repo_root) = locate_repo_root()?;
+    Ok(broken_links(&repo_root, workspace_cfg))
+}
+
+fn broken_links(repo_root: &Path, workspace_cfg: &WorkspaceConfig) -> Vec<BrokenLink> {
+    let root_abs = canonicalize_path(&repo_root.join(Path::new(&workspace_cfg.root)));
+    workspace_cfg
+        .linked_workspaces
+        .iter()
+        .filter_map(|link| {
+            let problem = match resolve_link(repo_root, &root_abs, link).err()? {
+                LinkProblem::MissingRoot => format!("root {} does not exist", link.root),
+                LinkProblem::MissingConfig => format!("config {} not found", link.config),
+                LinkProblem::UnreadableConfig(err) => {
+                    format!("config {} could not be read: {}", link.config, err)
+                }
+            };
+            Some(BrokenLink {
+                label: link
+                    .package_name
+                    .clone()
+                    .unwrap_or_else(|| link.root.clone()),
+                problem,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn build_workspace_context(
     config: &Config,
     detection: &FrameworkDetection,
 ) -> Result<WorkspaceContext> {
-    let current_dir = canonicalize_path(&std::env::current_dir()?);
-    let repo_root_candidate = find_repo_root(&current_dir).unwrap_or(current_dir.clone());
-    let repo_root = canonicalize_path(&repo_root_candidate);
+    let (current_dir, repo_root) = locate_repo_root()?;
 
     let manifest = load_workspace_manifest(&repo_root)
         .map_err(|err| anyhow!("failed to read workspace manifest: {}", err))?
@@ -564,15 +2068,18 @@ fn build_workspace_context(
     let mut handles = Vec::new();
 
     if let Some(workspace_cfg) = config.workspace.as_ref() {
-        let root_rel = if workspace_cfg.root.is_empty() {
-            ".".into()
+        let root_rel = normalize_workspace_root(&workspace_cfg.root);
+        let root_abs = canonicalize_path(&repo_root.join(Path::new(&workspace_cfg.root)));
+        let root_display = if root_abs == current_dir {
+            current_dir.clone()
         } else {
-            workspace_cfg.root.clone()
+            normalize_path_lexically(&repo_root.join(Path::new(&workspace_cfg.root)))
         };
-        let root_abs = canonicalize_path(&repo_root.join(Path::new(&workspace_cfg.root)));
 
         let alias_prefix = resolve_alias_prefix(config, Some(detection));
         let component_import_alias = resolve_component_import_alias(config);
+        let utils_alias_prefix = resolve_utils_alias_prefix(config, Some(detection));
+        let utils_import_alias = resolve_utils_import_alias(config);
         let mut pm_context = PackageManagerContext::new(repo_root.clone());
         pm_context.package_manager = Some(package_manager);
         pm_context.workspace_root = Some(root_abs.clone());
@@ -588,37 +2095,45 @@ fn build_workspace_context(
                 .unwrap_or_else(|| root_rel.clone()),
             kind: workspace_cfg.kind,
             root_abs: root_abs.clone(),
+            root_display,
             root_rel: root_rel.clone(),
             config: config.clone(),
             alias_prefix,
             component_import_alias,
+            utils_alias_prefix,
+            utils_import_alias,
             package_name: workspace_cfg.package_name.clone(),
             package_manager_context: pm_context,
         });
 
         let current_root_abs = root_abs;
         for (index, link) in workspace_cfg.linked_workspaces.iter().enumerate() {
-            let link_root_abs = canonicalize_path(&repo_root.join(Path::new(&link.root)));
-            let link_config_path =
-                canonicalize_path(&current_root_abs.join(Path::new(&link.config)));
-            let link_config = read_config_from(&link_config_path)
-                .map_err(|err| {
-                    anyhow!(
-                        "failed to read linked workspace config {}: {}",
-                        link.config,
-                        err
-                    )
-                })?
-                .ok_or_else(|| {
-                    anyhow!(
+            let ResolvedLink {
+                root_abs: link_root_abs,
+                root_display: link_root_display,
+                config: link_config,
+            } = resolve_link(&repo_root, &current_root_abs, link).map_err(
+                |problem| match problem {
+                    LinkProblem::MissingRoot => {
+                        anyhow!("linked workspace root {} does not exist", link.root)
+                    }
+                    LinkProblem::MissingConfig => anyhow!(
                         "linked workspace config {} not found (expected for {})",
                         link.config,
                         link.root
-                    )
-                })?;
+                    ),
+                    LinkProblem::UnreadableConfig(err) => anyhow!(
+                        "failed to read linked workspace config {}: {}",
+                        link.config,
+                        err
+                    ),
+                },
+            )?;
 
             let alias_prefix = resolve_alias_prefix(&link_config, None);
             let component_import_alias = resolve_component_import_alias(&link_config);
+            let utils_alias_prefix = resolve_utils_alias_prefix(&link_config, None);
+            let utils_import_alias = resolve_utils_import_alias(&link_config);
             let mut pm_context = PackageManagerContext::new(repo_root.clone());
             pm_context.package_manager = Some(package_manager);
             pm_context.workspace_root = Some(link_root_abs.clone());
@@ -634,10 +2149,13 @@ fn build_workspace_context(
                     .unwrap_or_else(|| link.root.clone()),
                 kind: link.kind,
                 root_abs: link_root_abs,
-                root_rel: link.root.clone(),
+                root_display: link_root_display,
+                root_rel: normalize_workspace_root(&link.root),
                 config: link_config,
                 alias_prefix,
                 component_import_alias,
+                utils_alias_prefix,
+                utils_import_alias,
                 package_name: link.package_name.clone(),
                 package_manager_context: pm_context,
             });
@@ -645,6 +2163,8 @@ fn build_workspace_context(
     } else {
         let alias_prefix = resolve_alias_prefix(config, Some(detection));
         let component_import_alias = resolve_component_import_alias(config);
+        let utils_alias_prefix = resolve_utils_alias_prefix(config, Some(detection));
+        let utils_import_alias = resolve_utils_import_alias(config);
         let mut pm_context = PackageManagerContext::new(repo_root.clone());
         pm_context.package_manager = Some(package_manager);
         pm_context.workspace_root = Some(current_dir.clone());
@@ -658,10 +2178,13 @@ fn build_workspace_context(
                 .unwrap_or_else(|| ".".into()),
             kind: WorkspaceKind::App,
             root_abs: current_dir.clone(),
+            root_display: current_dir.clone(),
             root_rel: normalize_relative_path(Path::new(".")),
             config: config.clone(),
             alias_prefix,
             component_import_alias,
+            utils_alias_prefix,
+            utils_import_alias,
             package_name: config
                 .workspace
                 .as_ref()
@@ -673,15 +2196,50 @@ fn build_workspace_context(
     Ok(WorkspaceContext {
         current_dir,
         handles,
+        framework: detection.framework,
     })
 }
 
+/// Assigns each path to the workspace with the deepest root containing it.
+fn group_files_by_workspace(
+    context: &WorkspaceContext,
+    paths: Vec<PathBuf>,
+) -> Vec<(&WorkspaceHandle, Vec<PathBuf>)> {
+    let mut groups: Vec<(&WorkspaceHandle, Vec<PathBuf>)> = Vec::new();
+    for path in paths {
+        let owner = context
+            .handles()
+            .filter(|handle| path.starts_with(&handle.root_abs))
+            .max_by_key(|handle| handle.root_abs.components().count());
+        let Some(owner) = owner else {
+            continue;
+        };
+
+        match groups.iter_mut().find(|(handle, _)| handle.id == owner.id) {
+            Some((_, files)) => files.push(path),
+            None => groups.push((owner, vec![path])),
+        }
+    }
+    groups
+}
+
+fn normalize_workspace_root(root: &str) -> String {
+    let normalized = normalize_relative_path(&normalize_path_lexically(Path::new(root)));
+    let trimmed = normalized.trim_end_matches('/');
+    if trimmed.is_empty() {
+        ".".into()
+    } else {
+        trimmed.to_string()
+    }
+}
+
 fn select_workspace_handle<'a>(
     context: &'a WorkspaceContext,
     target: Option<&str>,
 ) -> Result<&'a WorkspaceHandle> {
     if let Some(target) = target {
         let normalized = target.to_ascii_lowercase();
+        let normalized_root = normalize_workspace_root(&normalized);
 
         if let Some(handle) = context.handles().find(|handle| {
             handle
@@ -695,7 +2253,7 @@ fn select_workspace_handle<'a>(
 
         if let Some(handle) = context
             .handles()
-            .find(|handle| handle.root_rel.to_ascii_lowercase() == normalized)
+            .find(|handle| handle.root_rel.to_ascii_lowercase() == normalized_root)
         {
             return Ok(handle);
         }
@@ -717,10 +2275,10 @@ fn select_workspace_handle<'a>(
         );
     }
 
-    if context.primary().kind == WorkspaceKind::App {
-        if let Some(ui_handle) = context.first_by_kind(WorkspaceKind::Ui) {
-            return Ok(ui_handle);
-        }
+    if context.primary().kind == WorkspaceKind::App
+        && let Some(ui_handle) = context.first_by_kind(WorkspaceKind::Ui)
+    {
+        return Ok(ui_handle);
     }
 
     Ok(context.primary())
@@ -735,18 +2293,42 @@ fn build_component_lookup(components: &HashMap<String, Component>) -> HashMap<St
     lookup
 }
 
+/// Resolves `requested` and their dependencies, keeping only the files meant for
+/// `framework`. With `keep_going`, a request that fails to resolve is returned as a failure
+/// instead of aborting the rest.
 async fn collect_components(
-    client: &RegistryClient,
-    requested_slugs: &[String],
-) -> Result<Vec<ComponentEntry>> {
+    registries: &RegistrySet<'_>,
+    requested: &[RequestedComponent],
+    framework: FrameworkKind,
+    keep_going: bool,
+) -> Result<(Vec<ComponentEntry>, Vec<ComponentFailure>)> {
     let mut seen = HashSet::new();
     let mut entries = Vec::new();
+    let mut failures = Vec::new();
 
-    for slug in requested_slugs {
-        let components = client.fetch_component_with_dependencies(slug).await?;
-        for component in components {
-            if seen.insert(component.slug.clone()) {
+    for request in requested {
+        let components = match registries
+            .fetch_component_with_dependencies(request.registry.as_deref(), &request.slug)
+            .await
+        {
+            Ok(components) => components,
+            Err(err) if keep_going => {
+                failures.push(ComponentFailure {
+                    registry: request.registry.clone(),
+                    slug: request.slug.clone(),
+                    name: request.slug.clone(),
+                    error: err.to_string(),
+                });
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        for mut component in components {
+            if seen.insert((component.registry.clone(), component.slug.clone())) {
+                component.component.files =
+                    select_framework_files(component.component.files, framework);
                 entries.push(ComponentEntry {
+                    registry: component.registry,
                     slug: component.slug,
                     component: component.component,
                 });
@@ -754,49 +2336,74 @@ async fn collect_components(
         }
     }
 
-    Ok(entries)
+    Ok((entries, failures))
 }
 
 const FILE_FETCH_CONCURRENCY: usize = 6;
 
 async fn gather_component_files(
-    client: &RegistryClient,
+    registries: &RegistrySet<'_>,
     components: &[ComponentEntry],
     context: &WorkspaceContext,
+    progress: Option<&Spinner>,
+    keep_going: bool,
 ) -> Result<(
     Vec<ComponentFileWithContent>,
     HashMap<String, WorkspaceDependencySet>,
+    Vec<ComponentFailure>,
 )> {
     let mut files = Vec::new();
     let mut deps_per_workspace: HashMap<String, WorkspaceDependencySet> = HashMap::new();
+    let mut dependency_targets = Vec::new();
     let mut pending_files = Vec::new();
 
     for entry in components {
         let mut workspace_ids_for_component = HashSet::new();
 
         for file in &entry.component.files {
-            let handle = select_workspace_handle(context, file.target.as_deref())?.clone();
-            let mut relative_path = resolve_component_path(&file.path, &handle.config);
+            let LocatedComponentFile {
+                handle,
+                absolute_path,
+                display_path,
+            } = locate_component_file(context, &entry.slug, file)?;
+
+            // Registry paths are remote input; never let `..` segments or symlinks carry a
+            // write outside the workspace that owns the file.
+            if !canonicalize_path(&absolute_path).starts_with(canonicalize_path(&handle.root_abs)) {
+                anyhow::bail!(
+                    "refusing to write {} for {}: registry path `{}` resolves outside the workspace root {}",
+                    absolute_path.display(),
+                    entry.display_name(),
+                    file.path,
+                    handle.root_abs.display()
+                );
+            }
 
-            if let Some(flattened) =
-                flatten_relative_path_for_slug(&relative_path, &handle.config, &entry.slug)
-            {
-                relative_path = flattened;
+            if let Some(blocker) = non_directory_ancestor(&absolute_path) {
+                let blocker_display = blocker
+                    .strip_prefix(&handle.root_abs)
+                    .map(|relative| context.display_path(handle, relative))
+                    .unwrap_or(blocker);
+                anyhow::bail!(
+                    "cannot write {}: {} exists but is not a directory. Rename or remove it, or point the alias in nocta.config.json elsewhere.",
+                    display_path.display(),
+                    blocker_display.display()
+                );
             }
 
-            let absolute_path = handle.root_abs.join(&relative_path);
-            let display_path = diff_paths(&absolute_path, &context.current_dir)
-                .unwrap_or_else(|| absolute_path.clone());
+            let handle = handle.clone();
 
             pending_files.push(PendingComponentFile {
                 workspace_handle: handle.clone(),
                 workspace_id: handle.id.clone(),
+                registry: entry.registry.clone(),
                 absolute_path,
                 display_path,
                 component_name: entry.component.name.clone(),
                 component_slug: entry.slug.clone(),
                 file_type: file.file_type.clone(),
                 registry_path: file.path.clone(),
+                binary: file.is_binary(),
             });
 
             workspace_ids_for_component.insert(handle.id.clone());
@@ -805,74 +2412,199 @@ async fn gather_component_files(
         let preferred_target = select_dependency_target(&workspace_ids_for_component, context)?;
 
         if let Some(target_id) = preferred_target {
-            let deps_entry = deps_per_workspace
-                .entry(target_id.clone())
-                .or_insert_with(WorkspaceDependencySet::default);
-            for (name, version) in &entry.component.dependencies {
-                deps_entry
-                    .regular
-                    .entry(name.clone())
-                    .or_insert(version.clone());
-            }
-            for (name, version) in &entry.component.dev_dependencies {
-                deps_entry
-                    .dev
-                    .entry(name.clone())
-                    .or_insert(version.clone());
-            }
+            dependency_targets.push((target_id, entry));
         }
     }
 
-    let client_ref = client;
+    if let Some(spinner) = progress {
+        spinner.set_total(Some(pending_files.len() as u64));
+    }
     let mut fetch_results = stream::iter(pending_files.into_iter().map(|pending| async move {
-        let contents = client_ref
-            .fetch_component_file(&pending.registry_path)
-            .await;
+        let contents = match registries.client(pending.registry.as_deref()) {
+            Ok(client) if pending.binary => client
+                .fetch_component_file_bytes(&pending.registry_path)
+                .await
+                .map(FetchedFile::Binary),
+            Ok(client) => client
+                .fetch_component_file(&pending.registry_path)
+                .await
+                .map(FetchedFile::Text),
+            Err(err) => Err(err),
+        };
         (pending, contents)
     }))
     .buffer_unordered(FILE_FETCH_CONCURRENCY)
+    .inspect(|_| {
+        if let Some(spinner) = progress {
+            spinner.inc();
+        }
+    })
     .collect::<Vec<_>>()
     .await;
+    if let Some(spinner) = progress {
+        spinner.set_total(None);
+    }
 
+    let mut failures: Vec<ComponentFailure> = Vec::new();
     for (pending, contents_result) in fetch_results.drain(..) {
-        let contents = contents_result.with_context(|| {
-            format!("failed to fetch component asset {}", pending.registry_path)
-        })?;
-        let normalized = normalize_component_content(&contents, &pending.workspace_handle);
+        let fetched = match contents_result
+            .with_context(|| format!("failed to fetch component asset {}", pending.registry_path))
+        {
+            Ok(fetched) => fetched,
+            Err(err) if keep_going => {
+                if !failures.iter().any(|failure| {
+                    failure.registry == pending.registry && failure.slug == pending.component_slug
+                }) {
+                    failures.push(ComponentFailure {
+                        registry: pending.registry.clone(),
+                        slug: pending.component_slug.clone(),
+                        name: pending.component_name.clone(),
+                        error: format!("{:#}", err),
+                    });
+                }
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        let (content, bytes, registry_imports) = match fetched {
+            FetchedFile::Text(contents) => (
+                normalize_component_content(
+                    &contents,
+                    &pending.workspace_handle,
+                    &pending.registry_path,
+                ),
+                None,
+                IMPORT_SPECIFIER_RE
+                    .captures_iter(&contents)
+                    .map(|caps| caps[1].to_string())
+                    .collect(),
+            ),
+            FetchedFile::Binary(bytes) => (String::new(), Some(bytes), Vec::new()),
+        };
         files.push(ComponentFileWithContent {
             workspace_id: pending.workspace_id,
             absolute_path: pending.absolute_path,
             display_path: pending.display_path,
-            content: normalized,
+            content,
+            bytes,
             component_name: pending.component_name,
             component_slug: pending.component_slug,
+            registry: pending.registry,
             file_type: pending.file_type,
+            registry_path: pending.registry_path,
+            registry_imports,
         });
     }
 
-    Ok((files, deps_per_workspace))
+    fail_dependents(components, &mut failures);
+    let failed = |registry: &Option<String>, slug: &str| {
+        failures
+            .iter()
+            .any(|failure| &failure.registry == registry && failure.slug == slug)
+    };
+    files.retain(|file| !failed(&file.registry, &file.component_slug));
+
+    for (target_id, entry) in dependency_targets {
+        if failed(&entry.registry, &entry.slug) {
+            continue;
+        }
+        let deps_entry = deps_per_workspace.entry(target_id).or_default();
+        let component_name = entry.display_name();
+        for (name, version) in &entry.component.dependencies {
+            deps_entry.require(DependencyScope::Regular, name, version, &component_name);
+        }
+        for (name, version) in &entry.component.dev_dependencies {
+            deps_entry.require(DependencyScope::Dev, name, version, &component_name);
+        }
+    }
+
+    Ok((files, deps_per_workspace, failures))
 }
 
-fn flatten_relative_path_for_slug(
-    relative_path: &Path,
-    config: &Config,
-    slug: &str,
-) -> Option<PathBuf> {
-    let base = Path::new(config.aliases.components.filesystem_path());
-    let stripped = relative_path.strip_prefix(base).ok()?;
-    let mut components = stripped.components();
-    let first = components.next()?;
+/// A component `--keep-going` skipped, with the reason.
+struct ComponentFailure {
+    registry: Option<String>,
+    slug: String,
+    name: String,
+    error: String,
+}
 
-    if first.as_os_str() != OsStr::new(slug) {
-        return None;
+/// Marks components whose internal or registry dependencies failed as failed too, so
+/// nothing is installed with a missing dependency.
+fn fail_dependents(components: &[ComponentEntry], failures: &mut Vec<ComponentFailure>) {
+    loop {
+        let dependent = components.iter().find_map(|entry| {
+            if failures
+                .iter()
+                .any(|failure| failure.registry == entry.registry && failure.slug == entry.slug)
+            {
+                return None;
+            }
+            let internal = entry
+                .component
+                .internal_dependencies
+                .iter()
+                .map(|slug| (entry.registry.clone(), slug.as_str()));
+            let cross = entry
+                .component
+                .registry_dependencies
+                .iter()
+                .map(|dependency| {
+                    let (registry, slug) = split_scoped_slug(dependency);
+                    (registry.map(str::to_string), slug)
+                });
+            internal.chain(cross).find_map(|(registry, slug)| {
+                failures
+                    .iter()
+                    .find(|failure| failure.registry == registry && failure.slug == slug)
+                    .map(|failure| ComponentFailure {
+                        registry: entry.registry.clone(),
+                        slug: entry.slug.clone(),
+                        name: entry.component.name.clone(),
+                        error: format!("depends on {}, which failed", failure.name),
+                    })
+            })
+        });
+        match dependent {
+            Some(failure) => failures.push(failure),
+            None => break,
+        }
     }
+}
 
-    let remainder: PathBuf = components.collect();
-    if remainder.as_os_str().is_empty() {
-        return None;
-    }
+pub(crate) struct LocatedComponentFile<'a> {
+    pub(crate) handle: &'a WorkspaceHandle,
+    pub(crate) absolute_path: PathBuf,
+    pub(crate) display_path: PathBuf,
+}
+
+/// Resolves where a registry file lands on disk for the given workspace layout.
+pub(crate) fn locate_component_file<'a>(
+    context: &'a WorkspaceContext,
+    slug: &str,
+    file: &ComponentFile,
+) -> Result<LocatedComponentFile<'a>> {
+    let handle = select_workspace_handle(context, file.target.as_deref())?;
+    let relative_path = component_install_path(&file.path, &handle.config, slug);
+
+    let absolute_path = handle.root_abs.join(&relative_path);
+    let display_path = context.display_path(handle, &relative_path);
+
+    Ok(LocatedComponentFile {
+        handle,
+        absolute_path,
+        display_path,
+    })
+}
 
-    Some(base.join(remainder))
+/// Returns the nearest existing ancestor of `path` when it is not a directory, which
+/// would make creating the parent directories fail mid-install.
+fn non_directory_ancestor(path: &Path) -> Option<PathBuf> {
+    let existing = path
+        .ancestors()
+        .skip(1)
+        .find(|ancestor| fs::symlink_metadata(ancestor).is_ok())?;
+    (!existing.is_dir()).then(|| existing.to_path_buf())
 }
 
 fn select_dependency_target(
@@ -918,6 +2650,7 @@ const EXPORT_BLOCK_COMMENT: &str =
 
 fn sync_component_exports(
     dry_run: bool,
+    strategy_override: Option<ExportStrategy>,
     context: &WorkspaceContext,
     component_entries: &[ComponentEntry],
     files: &[ComponentFileWithContent],
@@ -934,140 +2667,219 @@ fn sync_component_exports(
         .collect();
 
     for handle in context.handles() {
-        let Some(exports_cfg) = handle
-            .config
-            .exports
-            .as_ref()
-            .and_then(|cfg| cfg.components())
-        else {
+        let Some(exports_cfg) = handle.config.exports.as_ref() else {
             continue;
         };
 
-        if exports_cfg.strategy != ExportStrategy::Named {
-            continue;
-        }
-
-        let workspace_files: Vec<&ComponentFileWithContent> = files
-            .iter()
-            .filter(|file| file.workspace_id == handle.id && file.file_type == "component")
-            .collect();
-
-        if workspace_files.is_empty() {
-            continue;
-        }
-
-        let barrel_rel = Path::new(exports_cfg.barrel_path());
-        let barrel_abs = handle.root_abs.join(barrel_rel);
-        let barrel_dir = barrel_abs
-            .parent()
-            .unwrap_or_else(|| handle.root_abs.as_path());
+        // Keyed by barrel so `components` and `utils` sharing a file share one export block.
+        let mut entries_by_barrel: BTreeMap<PathBuf, BTreeMap<String, BTreeSet<String>>> =
+            BTreeMap::new();
+        for file in files.iter().filter(|file| file.workspace_id == handle.id) {
+            let Some(target) = exports_cfg.target_for_file_type(&file.file_type) else {
+                continue;
+            };
+            let strategy = strategy_override.unwrap_or(target.strategy);
 
-        let mut new_entries: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
-        for file in workspace_files {
             let Some(entry) = component_lookup.get(file.component_slug.as_str()) else {
                 continue;
             };
 
-            if entry.component.exports.is_empty() {
+            let names = match strategy {
+                ExportStrategy::Named if file.file_type == "component" => {
+                    entry.component.exports.clone()
+                }
+                ExportStrategy::Named => exported_declarations(&file.content),
+                ExportStrategy::Default => entry
+                    .component
+                    .exports
+                    .first()
+                    .filter(|_| file.file_type == "component")
+                    .map(|name| vec![format!("default as {}", name)])
+                    .unwrap_or_default(),
+            };
+            if names.is_empty() {
                 continue;
             }
 
+            let barrel_rel = PathBuf::from(target.barrel_path());
+            let barrel_abs = handle.root_abs.join(&barrel_rel);
+            let barrel_dir = barrel_abs.parent().unwrap_or(handle.root_abs.as_path());
             let module_path = module_path_from_barrel(barrel_dir, &file.absolute_path);
-            let export_entry = new_entries.entry(module_path).or_insert_with(BTreeSet::new);
-            for name in &entry.component.exports {
-                export_entry.insert(name.clone());
-            }
+            entries_by_barrel
+                .entry(barrel_rel)
+                .or_default()
+                .entry(module_path)
+                .or_default()
+                .extend(names);
         }
 
-        if new_entries.is_empty() {
-            continue;
+        for (barrel_rel, new_entries) in entries_by_barrel {
+            if let Some(update) = sync_export_barrel(
+                dry_run,
+                context,
+                handle,
+                &barrel_rel,
+                new_entries,
+                file_changes,
+            )? {
+                updates.push(update);
+            }
         }
+    }
 
-        let touched_modules: Vec<String> = new_entries.keys().cloned().collect();
+    Ok(updates)
+}
 
-        let existing_content = match read_file(&barrel_abs) {
-            Ok(content) => Some(content),
-            Err(err) => {
-                if err.kind() == io::ErrorKind::NotFound {
-                    None
-                } else {
-                    return Err(anyhow!(
-                        "failed to read export barrel {}: {}",
-                        barrel_abs.display(),
-                        err
-                    ));
-                }
-            }
-        };
+/// Merges `new_entries` into the marked export block of one barrel file.
+fn sync_export_barrel(
+    dry_run: bool,
+    context: &WorkspaceContext,
+    handle: &WorkspaceHandle,
+    barrel_rel: &Path,
+    new_entries: BTreeMap<String, BTreeSet<String>>,
+    file_changes: &mut Vec<FileChange>,
+) -> Result<Option<ExportUpdate>> {
+    let barrel_abs = handle.root_abs.join(barrel_rel);
+
+    let touched_modules: Vec<String> = new_entries.keys().cloned().collect();
+    let configured_style = handle
+        .config
+        .exports
+        .as_ref()
+        .and_then(|exports| exports.style);
+
+    let Some(BarrelMerge {
+        existing_content,
+        new_content,
+        merged_map,
+        style,
+    }) = merge_export_barrel(
+        dry_run,
+        &barrel_abs,
+        new_entries,
+        configured_style,
+        file_changes,
+    )?
+    else {
+        return Ok(None);
+    };
 
-        let partition = existing_content
-            .as_deref()
-            .map(parse_existing_export_block)
-            .unwrap_or_else(|| parse_existing_export_block(""));
+    let display_path = context.display_path(handle, barrel_rel);
 
-        let mut merged_map = partition.existing_map.clone();
-        for (module, names) in new_entries.into_iter() {
-            merged_map
-                .entry(module)
-                .or_insert_with(BTreeSet::new)
-                .extend(names.into_iter());
-        }
+    let touched_set: HashSet<String> = touched_modules.into_iter().collect();
+    let statements = merged_map
+        .iter()
+        .filter(|(module, _)| touched_set.contains(module.as_str()))
+        .map(|(module, names)| format_export_line(module, names, style))
+        .collect::<Vec<_>>();
 
-        if merged_map == partition.existing_map {
-            continue;
-        }
+    let change = if existing_content.is_some() {
+        ExportChangeKind::Updated
+    } else {
+        ExportChangeKind::Created
+    };
+
+    Ok(Some(ExportUpdate {
+        workspace_label: handle.label.clone(),
+        display_path,
+        statements,
+        change,
+        previous_content: existing_content,
+        new_content,
+    }))
+}
 
-        let export_lines = export_lines_from_map(&merged_map);
-        let block = build_export_block(&export_lines);
+/// A barrel rewritten by [`merge_export_barrel`].
+struct BarrelMerge {
+    existing_content: Option<String>,
+    new_content: String,
+    merged_map: BTreeMap<String, BTreeSet<String>>,
+    style: ExportLineStyle,
+}
 
-        let mut new_content = String::new();
-        new_content.push_str(&partition.before);
-        if !partition.before.is_empty() && !partition.before.ends_with('\n') {
-            new_content.push('\n');
-        }
-        new_content.push_str(&block);
-        if !partition.after.is_empty() {
-            if !block.ends_with('\n') {
-                new_content.push('\n');
-            }
-            if !partition.after.starts_with('\n') && !new_content.ends_with('\n') {
-                new_content.push('\n');
+/// Merges `new_entries` into the export block of the barrel at `barrel_abs` and writes it
+/// back unless `dry_run`. Returns `None` when every entry is already exported.
+fn merge_export_barrel(
+    dry_run: bool,
+    barrel_abs: &Path,
+    new_entries: BTreeMap<String, BTreeSet<String>>,
+    configured_style: Option<BarrelStyle>,
+    file_changes: &mut Vec<FileChange>,
+) -> Result<Option<BarrelMerge>> {
+    // Held until the barrel is written so concurrent runs merge instead of clobbering.
+    let _barrel_lock =
+        if dry_run {
+            None
+        } else {
+            Some(cache::lock_project_file(barrel_abs).with_context(|| {
+                format!("failed to lock export barrel {}", barrel_abs.display())
+            })?)
+        };
+
+    let existing_content = match read_file(barrel_abs) {
+        Ok(content) => Some(content),
+        Err(err) => {
+            if err.kind() == io::ErrorKind::NotFound {
+                None
+            } else {
+                return Err(anyhow!(
+                    "failed to read export barrel {}: {}",
+                    barrel_abs.display(),
+                    err
+                ));
             }
-            new_content.push_str(&partition.after);
         }
+    };
 
-        let display_path =
-            diff_paths(&barrel_abs, &context.current_dir).unwrap_or_else(|| barrel_abs.clone());
+    let partition = existing_content
+        .as_deref()
+        .map(parse_existing_export_block)
+        .unwrap_or_else(|| parse_existing_export_block(""));
+
+    let mut merged_map = partition.existing_map.clone();
+    for (module, names) in new_entries.into_iter() {
+        merged_map
+            .entry(module)
+            .or_insert_with(BTreeSet::new)
+            .extend(names);
+    }
 
-        if !dry_run {
-            ensure_change_record(&barrel_abs, file_changes)?;
-            write_file(&barrel_abs, &new_content).with_context(|| {
-                format!("failed to write export barrel {}", barrel_abs.display())
-            })?;
-        }
+    if merged_map == partition.existing_map {
+        return Ok(None);
+    }
 
-        let touched_set: HashSet<String> = touched_modules.into_iter().collect();
-        let statements = merged_map
-            .iter()
-            .filter(|(module, _)| touched_set.contains(module.as_str()))
-            .map(|(module, names)| format_export_line(module, names))
-            .collect::<Vec<_>>();
+    let style = resolve_barrel_style(configured_style, existing_content.as_deref());
+    let export_lines = export_lines_from_map(&merged_map, style);
+    let block = build_export_block(&export_lines);
 
-        let change = if existing_content.is_some() {
-            ExportChangeKind::Updated
-        } else {
-            ExportChangeKind::Created
-        };
+    let mut new_content = String::new();
+    new_content.push_str(&partition.before);
+    if !partition.before.is_empty() && !partition.before.ends_with('\n') {
+        new_content.push('\n');
+    }
+    new_content.push_str(&block);
+    if !partition.after.is_empty() {
+        if !block.ends_with('\n') {
+            new_content.push('\n');
+        }
+        if !partition.after.starts_with('\n') && !new_content.ends_with('\n') {
+            new_content.push('\n');
+        }
+        new_content.push_str(&partition.after);
+    }
 
-        updates.push(ExportUpdate {
-            workspace_label: handle.label.clone(),
-            display_path,
-            statements,
-            change,
-        });
+    if !dry_run {
+        ensure_change_record(barrel_abs, file_changes)?;
+        write_file(barrel_abs, &new_content)
+            .with_context(|| format!("failed to write export barrel {}", barrel_abs.display()))?;
     }
 
-    Ok(updates)
+    Ok(Some(BarrelMerge {
+        existing_content,
+        new_content,
+        merged_map,
+        style,
+    }))
 }
 
 #[derive(Default)]
@@ -1082,25 +2894,25 @@ fn parse_existing_export_block(content: &str) -> ExportPartition {
         return ExportPartition::default();
     }
 
-    if let Some(start_idx) = content.find(EXPORT_BLOCK_START) {
-        if let Some(end_rel_idx) = content[start_idx..].find(EXPORT_BLOCK_END) {
-            let end_idx = start_idx + end_rel_idx;
-            let block_body_start = start_idx + EXPORT_BLOCK_START.len();
-            let block_body = &content[block_body_start..end_idx];
-            let after_start = end_idx + EXPORT_BLOCK_END.len();
-            let before = content[..start_idx].to_string();
-            let after = if after_start < content.len() {
-                content[after_start..].to_string()
-            } else {
-                String::new()
-            };
-            let existing_map = parse_export_lines(block_body);
-            return ExportPartition {
-                before,
-                after,
-                existing_map,
-            };
-        }
+    if let Some(start_idx) = content.find(EXPORT_BLOCK_START)
+        && let Some(end_rel_idx) = content[start_idx..].find(EXPORT_BLOCK_END)
+    {
+        let end_idx = start_idx + end_rel_idx;
+        let block_body_start = start_idx + EXPORT_BLOCK_START.len();
+        let block_body = &content[block_body_start..end_idx];
+        let after_start = end_idx + EXPORT_BLOCK_END.len();
+        let before = content[..start_idx].to_string();
+        let after = if after_start < content.len() {
+            content[after_start..].to_string()
+        } else {
+            String::new()
+        };
+        let existing_map = parse_export_lines(block_body);
+        return ExportPartition {
+            before,
+            after,
+            existing_map,
+        };
     }
 
     ExportPartition {
@@ -1157,15 +2969,256 @@ fn parse_export_line(line: &str) -> Option<(String, Vec<String>)> {
     Some((module, names))
 }
 
-fn export_lines_from_map(map: &BTreeMap<String, BTreeSet<String>>) -> Vec<String> {
+/// Outcome of [`dedupe_barrel`] for one barrel file.
+pub(crate) struct BarrelDedupe {
+    pub(crate) content: String,
+    /// Export lines dropped as exact repeats, or outside the managed block and already
+    /// covered by it.
+    pub(crate) removed: Vec<String>,
+    /// Export names re-exported from more than one module, with those modules.
+    pub(crate) conflicts: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// Drops duplicate re-exports from a barrel. The managed export block is canonical: lines
+/// inside it are only deduplicated against each other, while lines outside it are also
+/// dropped when the block already exports the same names from the same module.
+/// Names re-exported from different modules are reported but left alone.
+pub(crate) fn dedupe_barrel(content: &str) -> BarrelDedupe {
+    let managed = parse_existing_export_block(content).existing_map;
+    let mut seen_inside = HashSet::new();
+    let mut seen_outside = HashSet::new();
+    let mut in_block = false;
+    let mut kept = String::with_capacity(content.len());
+    let mut removed = Vec::new();
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed == EXPORT_BLOCK_START {
+            in_block = true;
+        } else if trimmed == EXPORT_BLOCK_END {
+            in_block = false;
+        } else if trimmed.starts_with("export") {
+            let duplicate = if in_block {
+                !seen_inside.insert(trimmed.to_string())
+            } else {
+                !seen_outside.insert(trimmed.to_string())
+                    || parse_export_line(trimmed).is_some_and(|(module, names)| {
+                        managed.get(&module).is_some_and(|exported| {
+                            names.iter().all(|name| exported.contains(name))
+                        })
+                    })
+            };
+            if duplicate {
+                removed.push(trimmed.to_string());
+                continue;
+            }
+        }
+        kept.push_str(line);
+    }
+
+    let mut modules_by_name: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for (module, names) in parse_export_lines(&kept) {
+        for name in names {
+            let exported = name.rsplit(" as ").next().unwrap_or(&name);
+            let exported = exported.strip_prefix("type ").unwrap_or(exported).trim();
+            modules_by_name
+                .entry(exported.to_string())
+                .or_default()
+                .insert(module.clone());
+        }
+    }
+    modules_by_name.retain(|_, modules| modules.len() > 1);
+
+    BarrelDedupe {
+        content: kept,
+        removed,
+        conflicts: modules_by_name,
+    }
+}
+
+/// `exports dedupe`: cleans every export barrel configured for the current workspace and
+/// its linked workspaces.
+pub(crate) fn dedupe_export_barrels(reporter: &ConsoleReporter, dry_run: bool) -> CommandResult {
+    let config = read_config()?.ok_or_else(|| {
+        anyhow!(
+            "{} not found. Run \"npx nocta-ui init\" first",
+            config_path().display()
+        )
+    })?;
+    let context = build_workspace_context(&config, &detect_framework())?;
+    let prefix = if dry_run { "[dry-run] " } else { "" };
+    let mut changed = false;
+    let mut found_barrel = false;
+
+    for handle in context.handles() {
+        let Some(exports) = handle.config.exports.as_ref() else {
+            continue;
+        };
+        let barrels: BTreeSet<PathBuf> = [exports.components(), exports.utils()]
+            .into_iter()
+            .flatten()
+            .map(|target| PathBuf::from(target.barrel_path()))
+            .collect();
+
+        for barrel_rel in barrels {
+            let barrel_abs = handle.root_abs.join(&barrel_rel);
+            let _barrel_lock = if dry_run {
+                None
+            } else {
+                Some(cache::lock_project_file(&barrel_abs).with_context(|| {
+                    format!("failed to lock export barrel {}", barrel_abs.display())
+                })?)
+            };
+            let content = match read_file(&barrel_abs) {
+                Ok(content) => content,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    return Err(anyhow!(
+                        "failed to read export barrel {}: {}",
+                        barrel_abs.display(),
+                        err
+                    ));
+                }
+            };
+            found_barrel = true;
+
+            let display_path = context.display_path(handle, &barrel_rel);
+            let dedupe = dedupe_barrel(&content);
+            if !dedupe.removed.is_empty() {
+                changed = true;
+                reporter.info(format!(
+                    "{}",
+                    format!(
+                        "{}{} {} duplicate export line(s) from {}:",
+                        prefix,
+                        if dry_run { "Would remove" } else { "Removed" },
+                        dedupe.removed.len(),
+                        display_path.display()
+                    )
+                    .if_supports_color(Stream::Stdout, |text| text.green())
+                ));
+                for line in &dedupe.removed {
+                    reporter.info(format!(
+                        "   {}",
+                        line.if_supports_color(Stream::Stdout, |text| text.dimmed())
+                    ));
+                }
+                if !dry_run {
+                    write_file(&barrel_abs, &dedupe.content).with_context(|| {
+                        format!("failed to write export barrel {}", barrel_abs.display())
+                    })?;
+                }
+            }
+
+            if !dedupe.conflicts.is_empty() {
+                reporter.warn(format!(
+                    "{}",
+                    format!(
+                        "Export names re-exported from more than one module in {} (resolve these by hand):",
+                        display_path.display()
+                    )
+                    .if_supports_color(Stream::Stdout, |text| text.yellow())
+                ));
+                for (name, modules) in &dedupe.conflicts {
+                    let modules: Vec<&str> = modules.iter().map(String::as_str).collect();
+                    reporter.info(format!(
+                        "   {}",
+                        format!("{}: {}", name, modules.join(", "))
+                            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+                    ));
+                }
+            }
+        }
+    }
+
+    if !found_barrel {
+        reporter.info(format!(
+            "{}",
+            "No export barrels found; configure `exports` in nocta.config.json first."
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+        return Ok(CommandOutcome::NoOp);
+    }
+    if !changed {
+        reporter.info(format!(
+            "{}",
+            "No duplicate export lines found."
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+        return Ok(CommandOutcome::NoOp);
+    }
+    Ok(CommandOutcome::Completed)
+}
+
+/// Quote and semicolon style for generated export lines, resolved per field from the
+/// `exports.style` config, then the first module import/re-export of the existing
+/// barrel, then double quotes with semicolons. `parse_export_line` accepts every style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ExportLineStyle {
+    quote: char,
+    semicolons: bool,
+}
+
+fn resolve_barrel_style(
+    configured: Option<BarrelStyle>,
+    existing: Option<&str>,
+) -> ExportLineStyle {
+    let configured = configured.unwrap_or_default();
+    let detected = existing.and_then(detect_barrel_style);
+    ExportLineStyle {
+        quote: configured
+            .quotes
+            .map(|quotes| quotes.as_char())
+            .or(detected.map(|style| style.quote))
+            .unwrap_or('"'),
+        semicolons: configured
+            .semicolons
+            .or(detected.map(|style| style.semicolons))
+            .unwrap_or(true),
+    }
+}
+
+fn detect_barrel_style(content: &str) -> Option<ExportLineStyle> {
+    content.lines().find_map(|line| {
+        let line = line.trim_end();
+        if !line.starts_with("export ") && !line.starts_with("import ") {
+            return None;
+        }
+        // `... from "<module>"`, or a side-effect `import "<module>"`.
+        let module = line
+            .rsplit_once(" from ")
+            .map(|(_, module)| module)
+            .or_else(|| line.strip_prefix("import "))?;
+        let quote = module
+            .trim_start()
+            .chars()
+            .next()
+            .filter(|quote| *quote == '"' || *quote == '\'')?;
+        Some(ExportLineStyle {
+            quote,
+            semicolons: line.ends_with(';'),
+        })
+    })
+}
+
+fn export_lines_from_map(
+    map: &BTreeMap<String, BTreeSet<String>>,
+    style: ExportLineStyle,
+) -> Vec<String> {
     map.iter()
-        .map(|(module, names)| format_export_line(module, names))
+        .map(|(module, names)| format_export_line(module, names, style))
         .collect()
 }
 
-fn format_export_line(module: &str, names: &BTreeSet<String>) -> String {
+fn format_export_line(module: &str, names: &BTreeSet<String>, style: ExportLineStyle) -> String {
     let joined = names.iter().cloned().collect::<Vec<_>>().join(", ");
-    format!("export {{ {} }} from \"{}\";", joined, module)
+    format!(
+        "export {{ {} }} from {quote}{}{quote}{}",
+        joined,
+        module,
+        if style.semicolons { ";" } else { "" },
+        quote = style.quote
+    )
 }
 
 fn build_export_block(lines: &[String]) -> String {
@@ -1183,6 +3236,14 @@ fn build_export_block(lines: &[String]) -> String {
     block
 }
 
+/// Names of the values a utility module declares with `export function`/`const`/`class`.
+fn exported_declarations(content: &str) -> Vec<String> {
+    EXPORTED_DECLARATION_RE
+        .captures_iter(content)
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
 fn module_path_from_barrel(barrel_dir: &Path, target_path: &Path) -> String {
     let relative = diff_paths(target_path, barrel_dir).unwrap_or_else(|| target_path.to_path_buf());
     let mut without_extension = relative.clone();
@@ -1198,28 +3259,58 @@ fn module_path_from_barrel(barrel_dir: &Path, target_path: &Path) -> String {
     module
 }
 
-fn normalize_component_content(content: &str, handle: &WorkspaceHandle) -> String {
+/// Rewrites a registry file's imports for `handle`: `@/` imports get the workspace's
+/// aliases, and with `components.filenameCase` set, imports of component files (`@/` or
+/// relative to `registry_path`) are renamed to match the files on disk.
+pub(crate) fn normalize_component_content(
+    content: &str,
+    handle: &WorkspaceHandle,
+    registry_path: &str,
+) -> String {
     let alias_prefix = handle.alias_prefix.trim_end_matches('/');
     let component_alias = handle
         .component_import_alias
         .as_deref()
         .map(|alias| alias.trim_end_matches('/').to_string());
 
+    let content = match handle.config.filename_case() {
+        Some(case) => rename_relative_component_imports(content, handle, registry_path, case),
+        None => content.to_string(),
+    };
+
     IMPORT_NORMALIZE_RE
-        .replace_all(content, |caps: &regex::Captures| {
+        .replace_all(&content, |caps: &regex::Captures| {
             let open = &caps[1];
-            let path = normalize_import_path(&caps[2]);
+            let mut path = normalize_import_path(&caps[2], source_root(handle));
             let close = &caps[3];
 
-            if let Some(custom_alias) = component_alias.as_deref() {
-                if let Some(relative) = component_relative_path(handle, &path) {
-                    let joined = if relative.is_empty() {
-                        custom_alias.to_string()
-                    } else {
-                        join_import_path(custom_alias, &relative)
-                    };
-                    return format!("{}{}{}", open, joined, close);
-                }
+            if let Some(case) = handle.config.filename_case()
+                && component_relative_path(handle, &path).is_some_and(|rel| !rel.is_empty())
+            {
+                path = match path.rsplit_once('/') {
+                    Some((dir, name)) => format!("{}/{}", dir, case.apply(name)),
+                    None => case.apply(&path),
+                };
+            }
+
+            if let Some(relative) = utils_relative_path(handle, &path) {
+                return format!(
+                    "{}{}{}",
+                    open,
+                    join_import_path(&utils_import_base(handle), &relative),
+                    close
+                );
+            }
+
+            if let Some(custom_alias) = component_alias.as_deref()
+                && let Some(relative) = component_relative_path(handle, &path)
+            {
+                let joined = if relative.is_empty() {
+                    custom_alias.to_string()
+                } else {
+                    join_import_path(custom_alias, &relative)
+                };
+                return format!("{}{}{}", open, joined, close);
             }
 
             format!("{}{}{}", open, join_import_path(alias_prefix, &path), close)
@@ -1227,17 +3318,60 @@ fn normalize_component_content(content: &str, handle: &WorkspaceHandle) -> Strin
         .into_owned()
 }
 
-fn normalize_import_path(import_path: &str) -> String {
-    let mut path = import_path
-        .trim_start_matches("./")
-        .trim_start_matches("/")
-        .to_string();
-    if let Some(stripped) = path.strip_prefix("app/") {
-        path = stripped.to_string();
-    } else if let Some(stripped) = path.strip_prefix("src/") {
-        path = stripped.to_string();
-    }
-    path
+/// Applies `case` to the last segment of relative imports in `content` that resolve, from
+/// `registry_path`, into the components directory.
+fn rename_relative_component_imports(
+    content: &str,
+    handle: &WorkspaceHandle,
+    registry_path: &str,
+    case: FilenameCase,
+) -> String {
+    RELATIVE_IMPORT_RE
+        .replace_all(content, |caps: &regex::Captures| {
+            let specifier = &caps[3];
+            let in_components = registry_import_target(registry_path, specifier)
+                .and_then(|target| component_relative_path(handle, &target))
+                .is_some_and(|relative| !relative.is_empty());
+            let specifier = match specifier.rsplit_once('/') {
+                Some((dir, name)) if in_components && name != ".." => {
+                    format!("{}/{}", dir, case.apply(name))
+                }
+                _ => specifier.to_string(),
+            };
+            format!("{}{}{}{}", &caps[1], &caps[2], specifier, &caps[4])
+        })
+        .into_owned()
+}
+
+/// Trims `./`, `/` and the workspace's source root (see [`source_root`]) from a registry
+/// `@/` import. Other `app/`/`src/` segments are part of the module path and kept.
+fn normalize_import_path(import_path: &str, source_root: Option<&str>) -> String {
+    let path = import_path.trim_start_matches("./").trim_start_matches('/');
+    source_root
+        .and_then(|root| path.strip_prefix(root))
+        .and_then(|rest| rest.strip_prefix('/'))
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// The directory `@/` resolves to inside the workspace (`src` or `app`), taken from the
+/// leading segment of the components alias path, falling back to the utils alias path.
+fn source_root(handle: &WorkspaceHandle) -> Option<&'static str> {
+    [
+        &handle.config.aliases.components,
+        &handle.config.aliases.utils,
+    ]
+    .into_iter()
+    .find_map(|alias| {
+        let path = alias
+            .filesystem_path()
+            .trim_start_matches("./")
+            .trim_start_matches('/');
+        SOURCE_ROOTS.into_iter().find(|root| {
+            path.strip_prefix(root)
+                .is_some_and(|rest| rest.starts_with('/'))
+        })
+    })
 }
 
 fn join_import_path(prefix: &str, import_path: &str) -> String {
@@ -1253,80 +3387,329 @@ fn join_import_path(prefix: &str, import_path: &str) -> String {
     }
 }
 
+/// Existing files the write would change; files already holding the same content are
+/// not conflicts.
 fn find_existing_files(files: &[ComponentFileWithContent]) -> Vec<PathBuf> {
     files
         .iter()
-        .filter(|file| file_exists(&file.absolute_path))
+        .filter(|file| {
+            file_exists(&file.absolute_path)
+                && !content_matches(
+                    &file.absolute_path,
+                    file.bytes.as_deref().unwrap_or(file.content.as_bytes()),
+                )
+        })
         .map(|file| file.display_path.clone())
         .collect()
 }
 
 fn write_component_files(
+    reporter: &ConsoleReporter,
     files: &[ComponentFileWithContent],
     dry_run: bool,
     file_changes: &mut Vec<FileChange>,
-) -> Result<()> {
+) -> Result<Vec<PathBuf>> {
+    let mut unchanged = Vec::new();
     for file in files {
-        if dry_run {
+        let contents = file.bytes.as_deref().unwrap_or(file.content.as_bytes());
+        if content_matches(&file.absolute_path, contents) {
+            unchanged.push(file.display_path.clone());
             continue;
         }
-        ensure_change_record(&file.absolute_path, file_changes)?;
-        write_file(&file.absolute_path, &file.content)
-            .with_context(|| format!("failed to write {}", file.display_path.display()))?;
+        if !dry_run {
+            ensure_change_record(&file.absolute_path, file_changes)?;
+            write_file(&file.absolute_path, contents)
+                .with_context(|| format!("failed to write {}", file.display_path.display()))?;
+        }
+        reporter.event(
+            "fileWritten",
+            json!({
+                "dryRun": dry_run,
+                "path": normalize_relative_path(&file.display_path),
+                "component": file.component_slug,
+                "registry": file.registry,
+            }),
+        );
     }
-    Ok(())
+    Ok(unchanged)
 }
 
-fn ensure_change_record(path: &Path, changes: &mut Vec<FileChange>) -> Result<()> {
-    if changes.iter().any(|change| change.path == path) {
-        return Ok(());
+/// Whether `path` already holds exactly `contents`; missing or unreadable files never match.
+fn content_matches(path: &Path, contents: &[u8]) -> bool {
+    fs::read(path).is_ok_and(|existing| existing == contents)
+}
+
+fn report_dependency_conflicts(
+    reporter: &ConsoleReporter,
+    context: &WorkspaceContext,
+    deps_by_workspace: &HashMap<String, WorkspaceDependencySet>,
+) {
+    for handle in context.handles() {
+        let conflicts = match deps_by_workspace.get(&handle.id) {
+            Some(spec) if !spec.conflicts.is_empty() => &spec.conflicts,
+            _ => continue,
+        };
+
+        reporter.warn(format!(
+            "{}",
+            format!(
+                "Conflicting dependency versions requested in {}:",
+                handle.label
+            )
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        for conflict in conflicts {
+            reporter.info(format!(
+                "   {}",
+                format!(
+                    "{}: using {} (required by {}) over {} (required by {})",
+                    conflict.name,
+                    conflict.kept,
+                    conflict.kept_by,
+                    conflict.dropped,
+                    conflict.dropped_by
+                )
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
     }
+}
 
-    let previous_contents = if path.exists() {
-        Some(fs::read(path).with_context(|| format!("failed to snapshot {}", path.display()))?)
-    } else {
-        None
-    };
+fn report_ignored_dependencies(
+    reporter: &ConsoleReporter,
+    context: &WorkspaceContext,
+    deps_by_workspace: &HashMap<String, WorkspaceDependencySet>,
+) {
+    for handle in context.handles() {
+        let ignored = match deps_by_workspace.get(&handle.id) {
+            Some(spec) if !spec.ignored.is_empty() => &spec.ignored,
+            _ => continue,
+        };
+
+        reporter.warn(format!(
+            "{}",
+            format!(
+                "Not installing ignored dependencies in {} (provide them yourself):",
+                handle.label
+            )
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        for dependency in ignored {
+            reporter.info(format!(
+                "   {}",
+                format!(
+                    "{}@{} (required by {})",
+                    dependency.name, dependency.version, dependency.required_by
+                )
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+    }
+}
 
-    changes.push(FileChange {
-        path: path.to_path_buf(),
-        previous_contents,
-    });
+/// Packages whose peer ranges are checked against the project's installed versions.
+const CHECKED_PEER_DEPENDENCIES: [&str; 2] = ["react", "react-dom"];
 
-    Ok(())
+/// A component peer range the project's installed version does not satisfy.
+struct PeerMismatch {
+    component: String,
+    name: String,
+    required: String,
+    installed: String,
 }
 
-fn rollback_file_changes(changes: &[FileChange]) -> Result<()> {
-    for change in changes.iter().rev() {
-        match &change.previous_contents {
-            Some(contents) => {
-                if let Some(parent) = change.path.parent() {
-                    if !parent.as_os_str().is_empty() {
-                        fs::create_dir_all(parent)
-                            .with_context(|| format!("failed to recreate {}", parent.display()))?;
-                    }
-                }
-                fs::write(&change.path, contents)
-                    .with_context(|| format!("failed to restore {}", change.path.display()))?;
+fn find_peer_mismatches(
+    context: &WorkspaceContext,
+    entries: &[ComponentEntry],
+) -> Vec<PeerMismatch> {
+    let base = &context.primary().root_abs;
+    let mut mismatches = Vec::new();
+    for entry in entries {
+        for name in CHECKED_PEER_DEPENDENCIES {
+            let Some(required) = entry.component.peer_dependencies.get(name) else {
+                continue;
+            };
+            if let Some(installed) = installed_version_outside_range(base, name, required) {
+                mismatches.push(PeerMismatch {
+                    component: entry.display_name(),
+                    name: name.to_string(),
+                    required: required.clone(),
+                    installed,
+                });
             }
-            None => {
-                if change.path.exists() {
-                    fs::remove_file(&change.path)
-                        .with_context(|| format!("failed to remove {}", change.path.display()))?;
+        }
+    }
+    mismatches
+}
+
+fn report_peer_mismatches(
+    reporter: &ConsoleReporter,
+    context: &WorkspaceContext,
+    mismatches: &[PeerMismatch],
+) {
+    reporter.warn(format!(
+        "{}",
+        format!(
+            "Installed versions in {} fall outside component peer ranges:",
+            context.primary().label
+        )
+        .if_supports_color(Stream::Stdout, |text| text.yellow())
+    ));
+    for mismatch in mismatches {
+        reporter.info(format!(
+            "   {}",
+            format!(
+                "{}: {} installed, {} expects {}",
+                mismatch.name, mismatch.installed, mismatch.component, mismatch.required
+            )
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+    }
+    reporter.info(format!(
+        "   {}",
+        "Components are still installed; they may misbehave at runtime until versions line up."
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+}
+
+/// An import between two files of one component that were placed in different workspaces,
+/// where the importing workspace's aliases cannot reach the imported file.
+struct CrossWorkspaceImport<'a> {
+    from: &'a ComponentFileWithContent,
+    to: &'a ComponentFileWithContent,
+    specifier: &'a str,
+}
+
+fn find_cross_workspace_imports(
+    files: &[ComponentFileWithContent],
+) -> Vec<CrossWorkspaceImport<'_>> {
+    let mut by_component: BTreeMap<(Option<&str>, &str), Vec<&ComponentFileWithContent>> =
+        BTreeMap::new();
+    for file in files {
+        by_component
+            .entry((file.registry.as_deref(), file.component_slug.as_str()))
+            .or_default()
+            .push(file);
+    }
+
+    let mut split = Vec::new();
+    for component_files in by_component.values() {
+        let spans_workspaces = component_files
+            .iter()
+            .any(|file| file.workspace_id != component_files[0].workspace_id);
+        if !spans_workspaces {
+            continue;
+        }
+
+        for from in component_files {
+            for specifier in &from.registry_imports {
+                let Some(target) = registry_import_target(&from.registry_path, specifier) else {
+                    continue;
+                };
+                let Some(to) = component_files.iter().find(|candidate| {
+                    let module = registry_module_key(&candidate.registry_path);
+                    module == target || module.strip_suffix("/index") == Some(target.as_str())
+                }) else {
+                    continue;
+                };
+                if to.workspace_id != from.workspace_id {
+                    split.push(CrossWorkspaceImport {
+                        from,
+                        to,
+                        specifier,
+                    });
                 }
             }
         }
     }
 
-    Ok(())
+    split
+}
+
+/// Registry path of the module an import in `importer` points to, for `@/` aliased and
+/// relative specifiers. Package imports return `None`.
+fn registry_import_target(importer: &str, specifier: &str) -> Option<String> {
+    if let Some(aliased) = specifier.strip_prefix("@/") {
+        return Some(registry_module_key(aliased));
+    }
+    if !specifier.starts_with("./") && !specifier.starts_with("../") {
+        return None;
+    }
+
+    let importer_dir = Path::new(importer.trim_start_matches("./")).parent()?;
+    let joined = normalize_path_lexically(&importer_dir.join(specifier));
+    Some(registry_module_key(&joined.to_string_lossy()))
+}
+
+fn registry_module_key(path: &str) -> String {
+    // Registry paths and `@/` specifiers share the registry's layout, whose source root is
+    // unknown here, so either conventional root is dropped before comparing them.
+    let path = path.replace('\\', "/");
+    let path = path.trim_start_matches("./").trim_start_matches('/');
+    let normalized = SOURCE_ROOTS
+        .into_iter()
+        .find_map(|root| path.strip_prefix(root)?.strip_prefix('/'))
+        .unwrap_or(path);
+    let without_extension = Path::new(&normalized).with_extension("");
+    without_extension.to_string_lossy().replace('\\', "/")
+}
+
+fn report_cross_workspace_imports(
+    reporter: &ConsoleReporter,
+    context: &WorkspaceContext,
+    imports: &[CrossWorkspaceImport<'_>],
+) {
+    let label = |file: &ComponentFileWithContent| {
+        context
+            .handle_by_id(&file.workspace_id)
+            .map(|handle| handle.label.clone())
+            .unwrap_or_else(|| file.workspace_id.clone())
+    };
+
+    reporter.warn(format!(
+        "{}",
+        "Some components are split across workspaces and import each other's files; these imports will not resolve:"
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+    ));
+    for import in imports {
+        reporter.info(format!(
+            "   {}",
+            format!(
+                "{}: {} ({}) imports \"{}\" from {} ({})",
+                import.from.component_name,
+                import.from.display_path.display(),
+                label(import.from),
+                import.specifier,
+                import.to.display_path.display(),
+                label(import.to)
+            )
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+    }
+    reporter.info(format!(
+        "   {}",
+        "Check the `target` of these files in the registry or the workspace links in nocta.config.json."
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+}
+
+fn kept_dependency_detail(issue: &RequirementIssue, required: &str) -> String {
+    format!(
+        "{}: installed {}, required {}",
+        issue.name,
+        issue.installed.as_deref().unwrap_or("unknown"),
+        required
+    )
 }
 
 fn handle_workspace_dependencies(
     dry_run: bool,
+    missing_only: bool,
     context: &WorkspaceContext,
     deps_by_workspace: &HashMap<String, WorkspaceDependencySet>,
     reporter: &ConsoleReporter,
-) -> Result<()> {
+) -> Result<Vec<DependencyRecord>> {
+    let mut records = Vec::new();
     for handle in context.handles() {
         let spec = match deps_by_workspace.get(&handle.id) {
             Some(spec) if !spec.is_empty() => spec,
@@ -1336,9 +3719,8 @@ fn handle_workspace_dependencies(
         let base_path = handle
             .package_manager_context
             .workspace_root
-            .as_ref()
-            .map(|path| path.as_path())
-            .unwrap_or_else(|| handle.root_abs.as_path());
+            .as_deref()
+            .unwrap_or(handle.root_abs.as_path());
 
         let installed = get_installed_dependencies_at(base_path)?;
         let mut required_map: HashMap<String, String> = HashMap::new();
@@ -1352,9 +3734,14 @@ fn handle_workspace_dependencies(
         let mut incompatible_regular = Vec::new();
         let mut incompatible_dev = Vec::new();
         let mut satisfied = Vec::new();
+        let mut kept = Vec::new();
 
         for (dep, version) in &spec.regular {
             if let Some(issue) = issues.iter().find(|issue| issue.name == *dep) {
+                if missing_only && issue.reason != RequirementIssueReason::Missing {
+                    kept.push(kept_dependency_detail(issue, version));
+                    continue;
+                }
                 deps_to_install.insert(dep.clone(), version.clone());
                 let detail = match issue.reason {
                     RequirementIssueReason::Missing => {
@@ -1368,6 +3755,12 @@ fn handle_workspace_dependencies(
                             version
                         )
                     }
+                    RequirementIssueReason::InvalidRange => {
+                        format!(
+                            "{}: registry range `{}` is not a valid semver range",
+                            dep, version
+                        )
+                    }
                 };
                 incompatible_regular.push(detail);
             } else if let Some(installed_version) = installed.get(dep) {
@@ -1380,6 +3773,10 @@ fn handle_workspace_dependencies(
 
         for (dep, version) in &spec.dev {
             if let Some(issue) = issues.iter().find(|issue| issue.name == *dep) {
+                if missing_only && issue.reason != RequirementIssueReason::Missing {
+                    kept.push(kept_dependency_detail(issue, version));
+                    continue;
+                }
                 dev_deps_to_install.insert(dep.clone(), version.clone());
                 let detail = match issue.reason {
                     RequirementIssueReason::Missing => {
@@ -1393,6 +3790,12 @@ fn handle_workspace_dependencies(
                             version
                         )
                     }
+                    RequirementIssueReason::InvalidRange => {
+                        format!(
+                            "{}: registry range `{}` is not a valid semver range",
+                            dep, version
+                        )
+                    }
                 };
                 incompatible_dev.push(detail);
             } else if let Some(installed_version) = installed.get(dep) {
@@ -1405,9 +3808,32 @@ fn handle_workspace_dependencies(
 
         if !satisfied.is_empty() {
             let satisfied_heading = format!("Dependencies already satisfied in {}:", handle.label);
-            reporter.info(format!("\n{}", satisfied_heading.green()));
+            reporter.info(format!(
+                "\n{}",
+                satisfied_heading.if_supports_color(Stream::Stdout, |text| text.green())
+            ));
             for entry in satisfied {
-                reporter.info(format!("   {}", entry.dimmed()));
+                reporter.info(format!(
+                    "   {}",
+                    entry.if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
+            }
+        }
+
+        if !kept.is_empty() {
+            let kept_heading = format!(
+                "Keeping installed versions in {} (--limit-deps); these may not satisfy the registry:",
+                handle.label
+            );
+            reporter.warn(format!(
+                "\n{}",
+                kept_heading.if_supports_color(Stream::Stdout, |text| text.yellow())
+            ));
+            for entry in &kept {
+                reporter.info(format!(
+                    "   {}",
+                    entry.if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
             }
         }
 
@@ -1420,9 +3846,15 @@ fn handle_workspace_dependencies(
             } else {
                 format!("Incompatible dependencies updated in {}:", handle.label)
             };
-            reporter.warn(format!("\n{}", incompatible_heading.yellow()));
+            reporter.warn(format!(
+                "\n{}",
+                incompatible_heading.if_supports_color(Stream::Stdout, |text| text.yellow())
+            ));
             for entry in &incompatible_regular {
-                reporter.info(format!("   {}", entry.dimmed()));
+                reporter.info(format!(
+                    "   {}",
+                    entry.if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
             }
         }
 
@@ -1435,9 +3867,15 @@ fn handle_workspace_dependencies(
             } else {
                 format!("Incompatible dev dependencies updated in {}:", handle.label)
             };
-            reporter.warn(format!("\n{}", incompatible_heading.yellow()));
+            reporter.warn(format!(
+                "\n{}",
+                incompatible_heading.if_supports_color(Stream::Stdout, |text| text.yellow())
+            ));
             for entry in &incompatible_dev {
-                reporter.info(format!("   {}", entry.dimmed()));
+                reporter.info(format!(
+                    "   {}",
+                    entry.if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
             }
         }
 
@@ -1447,9 +3885,16 @@ fn handle_workspace_dependencies(
             } else {
                 format!("Installing missing dependencies in {}...", handle.label)
             };
-            reporter.info(format!("\n{}", install_heading.blue()));
+            reporter.info(format!(
+                "\n{}",
+                install_heading.if_supports_color(Stream::Stdout, |text| text.blue())
+            ));
             for (dep, version) in &deps_to_install {
-                reporter.info(format!("   {}", format!("{}@{}", dep, version).dimmed()));
+                reporter.info(format!(
+                    "   {}",
+                    format!("{}@{}", dep, version)
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
             }
 
             let install_map: HashMap<String, String> = deps_to_install
@@ -1463,21 +3908,22 @@ fn handle_workspace_dependencies(
                     &handle.package_manager_context,
                     DependencyScope::Regular,
                 )? {
-                    reporter.info(format!(
-                        "{}",
-                        format!("   Command: {}", plan.command_line().join(" ")).dimmed()
-                    ));
+                    print_planned_install(reporter, &plan);
                 }
             } else if let Some(plan) = plan_dependency_install(
                 &install_map,
                 &handle.package_manager_context,
                 DependencyScope::Regular,
             )? {
-                plan.execute()?;
-                reporter.info(format!(
-                    "{}",
-                    format!("Dependencies installed for {}.", handle.label).green()
-                ));
+                if execute_install(&plan, reporter, "Dependencies installation failed") {
+                    reporter.info(format!(
+                        "{}",
+                        format!("Dependencies installed for {}.", handle.label)
+                            .if_supports_color(Stream::Stdout, |text| text.green())
+                    ));
+                } else {
+                    deps_to_install.clear();
+                }
             }
         }
 
@@ -1490,9 +3936,16 @@ fn handle_workspace_dependencies(
             } else {
                 format!("Installing missing dev dependencies in {}...", handle.label)
             };
-            reporter.info(format!("\n{}", install_heading.blue()));
+            reporter.info(format!(
+                "\n{}",
+                install_heading.if_supports_color(Stream::Stdout, |text| text.blue())
+            ));
             for (dep, version) in &dev_deps_to_install {
-                reporter.info(format!("   {}", format!("{}@{}", dep, version).dimmed()));
+                reporter.info(format!(
+                    "   {}",
+                    format!("{}@{}", dep, version)
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
             }
 
             let install_map: HashMap<String, String> = dev_deps_to_install
@@ -1506,40 +3959,204 @@ fn handle_workspace_dependencies(
                     &handle.package_manager_context,
                     DependencyScope::Dev,
                 )? {
-                    reporter.info(format!(
-                        "{}",
-                        format!("   Command: {}", plan.command_line().join(" ")).dimmed()
-                    ));
+                    print_planned_install(reporter, &plan);
                 }
             } else if let Some(plan) = plan_dependency_install(
                 &install_map,
                 &handle.package_manager_context,
                 DependencyScope::Dev,
             )? {
-                plan.execute()?;
-                reporter.info(format!(
-                    "{}",
-                    format!("Dev dependencies installed for {}.", handle.label).green()
-                ));
+                if execute_install(&plan, reporter, "Dev dependencies installation failed") {
+                    reporter.info(format!(
+                        "{}",
+                        format!("Dev dependencies installed for {}.", handle.label)
+                            .if_supports_color(Stream::Stdout, |text| text.green())
+                    ));
+                } else {
+                    dev_deps_to_install.clear();
+                }
             }
         }
+
+        let resolved = if dry_run {
+            HashMap::new()
+        } else {
+            get_installed_dependencies_at(base_path)?
+        };
+        let scoped = deps_to_install
+            .iter()
+            .map(|dep| (DependencyScope::Regular, dep))
+            .chain(
+                dev_deps_to_install
+                    .iter()
+                    .map(|dep| (DependencyScope::Dev, dep)),
+            );
+        for (scope, (name, requested)) in scoped {
+            let record = DependencyRecord {
+                workspace: handle.label.clone(),
+                name: name.clone(),
+                requested: requested.clone(),
+                resolved: resolved.get(name).cloned(),
+                scope,
+            };
+            let mut fields = record.to_json();
+            fields["dryRun"] = json!(dry_run);
+            reporter.event("dependencyInstalled", fields);
+            records.push(record);
+        }
+    }
+
+    Ok(records)
+}
+
+/// Runs `plan`, retrying once. A second failure only warns with the command to run by hand,
+/// so the component files already written are kept; returns whether the install succeeded.
+fn execute_install(
+    plan: &DependencyInstallPlan,
+    reporter: &ConsoleReporter,
+    failure: &str,
+) -> bool {
+    let err = match plan.execute() {
+        Ok(()) => return true,
+        Err(err) => err,
+    };
+    reporter.warn(format!(
+        "{}",
+        format!("{}; retrying once ({})", failure, err)
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+    ));
+    let Err(err) = plan.execute() else {
+        return true;
+    };
+
+    reporter.warn(format!(
+        "{}",
+        format!("{}; you can install them manually", failure)
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+    ));
+    reporter.info(format!(
+        "{}",
+        format!("Run: {}", plan.command_line().join(" "))
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.error(format!(
+        "{}",
+        format!("Error: {}", err).if_supports_color(Stream::Stderr, |text| text.red())
+    ));
+    false
+}
+
+/// Content hash of a component file as it ended up on disk (after any formatting),
+/// falling back to the registry content when the file was not written.
+/// Lockfile entries for a component's files. Files the user kept in the `--interactive`
+/// picker (flagged `true`) were not written, so they keep their `previous` entry, if any.
+fn merge_locked_files(
+    fresh: Vec<(LockedFile, bool)>,
+    previous: Option<&LockedComponent>,
+) -> Vec<LockedFile> {
+    fresh
+        .into_iter()
+        .filter_map(|(locked, skipped)| {
+            if !skipped {
+                return Some(locked);
+            }
+            previous?
+                .files
+                .iter()
+                .find(|file| file.path == locked.path)
+                .cloned()
+        })
+        .collect()
+}
+
+/// Whether the file at `path` still holds what the lockfile recorded for it (`recorded`),
+/// hashed as a component file or, for binary assets, as raw bytes.
+fn matches_locked_hash(path: &Path, recorded: &str) -> Result<bool> {
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(content_hash(&bytes) == recorded
+        || std::str::from_utf8(&bytes).is_ok_and(|text| component_hash_matches(recorded, text)))
+}
+
+/// Hash of `file` as it ends up on disk, which a formatter may have rewritten. Dry runs
+/// write nothing, so they hash the content that would have been written.
+fn written_content_hash(file: &ComponentFileWithContent, dry_run: bool) -> String {
+    if dry_run {
+        return match file.bytes.as_deref() {
+            Some(bytes) => content_hash(bytes),
+            None => component_content_hash(&file.content),
+        };
+    }
+    if let Some(bytes) = file.bytes.as_deref() {
+        return content_hash(fs::read(&file.absolute_path).as_deref().unwrap_or(bytes));
     }
+    component_content_hash(
+        &fs::read_to_string(&file.absolute_path).unwrap_or_else(|_| file.content.clone()),
+    )
+}
+
+/// Example `import` statements for each requested component, paired with its display name.
+fn component_import_lines<'a>(
+    context: &WorkspaceContext,
+    requested_components: &'a [ComponentEntry],
+) -> Vec<(String, &'a str)> {
+    let primary_handle =
+        select_workspace_handle(context, None).unwrap_or_else(|_| context.primary());
+    let alias_base = component_import_base(primary_handle);
+
+    requested_components
+        .iter()
+        .filter_map(|component| {
+            let first_file = component.component.files.first()?;
+            let mut raw_path = first_file
+                .path
+                .trim_start_matches("./")
+                .trim_start_matches('/')
+                .to_string();
+            if let Some(stripped) = raw_path.strip_suffix(".tsx") {
+                raw_path = stripped.to_string();
+            }
+            let relative_path = component_relative_path(primary_handle, &raw_path)
+                .unwrap_or_else(|| raw_path.clone());
+            let module = if relative_path.is_empty() {
+                alias_base.clone()
+            } else {
+                join_import_path(&alias_base, &relative_path)
+            };
 
-    Ok(())
+            Some((
+                format!(
+                    "import {{ {} }} from \"{}\";",
+                    component.component.exports.join(", "),
+                    module
+                ),
+                component.component.name.as_str(),
+            ))
+        })
+        .collect()
 }
 
+#[allow(clippy::too_many_arguments)]
 fn print_add_summary(
     reporter: &ConsoleReporter,
     dry_run: bool,
     context: &WorkspaceContext,
     requested_components: &[ComponentEntry],
+    components: &[ComponentEntry],
     files: &[ComponentFileWithContent],
+    skipped: &[PathBuf],
+    unchanged: &[PathBuf],
 ) {
     reporter.blank();
-    reporter.info(format!("{}", "Components installed:".green()));
+    reporter.info(format!(
+        "{}",
+        "Components installed:".if_supports_color(Stream::Stdout, |text| text.green())
+    ));
 
     let mut files_by_workspace: BTreeMap<String, Vec<&ComponentFileWithContent>> = BTreeMap::new();
-    for file in files {
+    for file in files
+        .iter()
+        .filter(|file| !skipped.contains(&file.display_path))
+    {
         files_by_workspace
             .entry(file.workspace_id.clone())
             .or_default()
@@ -1550,64 +4167,78 @@ fn print_add_summary(
         if let Some(handle) = context.handle_by_id(workspace_id) {
             reporter.info(format!(
                 "{}",
-                format!("  Workspace {}:", handle.label).blue()
+                format!("  Workspace {}:", handle.label)
+                    .if_supports_color(Stream::Stdout, |text| text.blue())
             ));
             for file in entries {
+                let status = if unchanged.contains(&file.display_path) {
+                    " - unchanged"
+                } else {
+                    ""
+                };
+                let category = components
+                    .iter()
+                    .find(|entry| {
+                        entry.slug == file.component_slug && entry.registry == file.registry
+                    })
+                    .map(|entry| entry.component.category.trim())
+                    .filter(|category| !category.is_empty());
+                let label = match category {
+                    Some(category) => format!("{}, {}", file.component_name, category),
+                    None => file.component_name.clone(),
+                };
                 reporter.info(format!(
                     "     {}",
-                    format!("{} ({})", file.display_path.display(), file.component_name).dimmed()
+                    format!("{} ({}){}", file.display_path.display(), label, status)
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
                 ));
             }
         }
     }
 
-    let heading = if dry_run {
-        "[dry-run] Example imports:".blue()
-    } else {
-        "Import and use:".blue()
-    };
-    reporter.info(format!("\n{}", heading));
-
-    let primary_handle =
-        select_workspace_handle(context, None).unwrap_or_else(|_| context.primary());
-    let alias_base = component_import_base(primary_handle);
-
-    for component in requested_components {
-        if let Some(first_file) = component.component.files.first() {
-            let mut raw_path = first_file
-                .path
-                .trim_start_matches("./")
-                .trim_start_matches('/')
-                .to_string();
-            if let Some(stripped) = raw_path.strip_suffix(".tsx") {
-                raw_path = stripped.to_string();
-            }
-            let relative_path = component_relative_path(primary_handle, &raw_path)
-                .unwrap_or_else(|| raw_path.clone());
-
+    if !skipped.is_empty() {
+        reporter.warn(format!(
+            "\n{}",
+            "Kept existing files (not overwritten):"
+                .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        for path in skipped {
             reporter.info(format!(
                 "   {}",
-                format!(
-                    "import {{ {} }} from \"{}\"; // {}",
-                    component.component.exports.join(", "),
-                    if relative_path.is_empty() {
-                        alias_base.clone()
-                    } else {
-                        join_import_path(&alias_base, &relative_path)
-                    },
-                    component.component.name
-                )
-                .dimmed()
+                path.display()
+                    .to_string()
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
             ));
         }
     }
 
+    let heading = if dry_run {
+        "[dry-run] Example imports:"
+    } else {
+        "Import and use:"
+    };
+    reporter.info(format!(
+        "\n{}",
+        heading.if_supports_color(Stream::Stdout, |text| text.blue())
+    ));
+
+    for (statement, name) in component_import_lines(context, requested_components) {
+        reporter.info(format!(
+            "   {}",
+            format!("{} // {}", statement, name)
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+    }
+
     let variants: Vec<_> = requested_components
         .iter()
         .filter(|entry| !entry.component.variants.is_empty())
         .collect();
     if !variants.is_empty() {
-        reporter.info(format!("\n{}", "Available variants:".blue()));
+        reporter.info(format!(
+            "\n{}",
+            "Available variants:".if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
         for entry in variants {
             reporter.info(format!(
                 "   {}",
@@ -1616,7 +4247,7 @@ fn print_add_summary(
                     entry.component.name,
                     entry.component.variants.join(", ")
                 )
-                .dimmed()
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
             ));
         }
     }
@@ -1626,7 +4257,10 @@ fn print_add_summary(
         .filter(|entry| !entry.component.sizes.is_empty())
         .collect();
     if !sizes.is_empty() {
-        reporter.info(format!("\n{}", "Available sizes:".blue()));
+        reporter.info(format!(
+            "\n{}",
+            "Available sizes:".if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
         for entry in sizes {
             reporter.info(format!(
                 "   {}",
@@ -1635,12 +4269,63 @@ fn print_add_summary(
                     entry.component.name,
                     entry.component.sizes.join(", ")
                 )
-                .dimmed()
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
             ));
         }
     }
 }
 
+/// Condensed form of `print_add_summary` for `--summary-only`: component names and totals.
+fn print_add_summary_counts(
+    reporter: &ConsoleReporter,
+    dry_run: bool,
+    requested_components: &[ComponentEntry],
+    files_written: usize,
+    dependencies: usize,
+    barrels: usize,
+) {
+    let count = |amount: usize, singular: &str, plural: &str| {
+        format!("{} {}", amount, if amount == 1 { singular } else { plural })
+    };
+    let names = requested_components
+        .iter()
+        .map(ComponentEntry::display_name)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    reporter.blank();
+    if dry_run {
+        reporter.info(format!(
+            "{} {}",
+            "[dry-run] Components:".if_supports_color(Stream::Stdout, |text| text.blue()),
+            names
+        ));
+    } else {
+        reporter.info(format!(
+            "{} {}",
+            "Components installed:".if_supports_color(Stream::Stdout, |text| text.green()),
+            names
+        ));
+    }
+
+    let verb = if dry_run { "to write" } else { "written" };
+    let dependency_verb = if dry_run { "to install" } else { "installed" };
+    let barrel_verb = if dry_run { "to update" } else { "updated" };
+    reporter.info(format!(
+        "   {}",
+        format!(
+            "{} {}, {} {}, {} {}",
+            count(files_written, "file", "files"),
+            verb,
+            count(dependencies, "dependency", "dependencies"),
+            dependency_verb,
+            count(barrels, "export barrel", "export barrels"),
+            barrel_verb
+        )
+        .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+}
+
 fn normalize_alias_path(path: &str) -> String {
     path.trim_start_matches("./")
         .trim_start_matches('/')
@@ -1664,6 +4349,46 @@ fn component_import_base(handle: &WorkspaceHandle) -> String {
     }
 }
 
+/// Registry sources import the shared utils module as `@/lib/utils`.
+const REGISTRY_UTILS_PATH: &str = "lib/utils";
+
+/// Remainder of `path` below the utils module, if `path` points at it either through the
+/// registry's `lib/utils` convention or the workspace's configured utils location.
+fn utils_relative_path(handle: &WorkspaceHandle, path: &str) -> Option<String> {
+    let normalized = path.trim_start_matches("./").trim_start_matches('/');
+    let configured = normalize_alias_path(handle.config.aliases.utils.filesystem_path());
+    let configured = configured.trim_end_matches('/');
+
+    [REGISTRY_UTILS_PATH, configured]
+        .into_iter()
+        .filter(|base| !base.is_empty())
+        .find_map(|base| {
+            if normalized == base {
+                Some(String::new())
+            } else {
+                normalized
+                    .strip_prefix(base)
+                    .and_then(|rest| rest.strip_prefix('/'))
+                    .map(str::to_string)
+            }
+        })
+}
+
+fn utils_import_base(handle: &WorkspaceHandle) -> String {
+    if let Some(custom_alias) = handle.utils_import_alias.as_deref() {
+        return custom_alias.to_string();
+    }
+
+    let configured = normalize_alias_path(handle.config.aliases.utils.filesystem_path());
+    let configured = configured.trim_end_matches('/');
+    let module = if configured.is_empty() {
+        REGISTRY_UTILS_PATH
+    } else {
+        configured
+    };
+    join_import_path(&handle.utils_alias_prefix, module)
+}
+
 fn component_relative_path(handle: &WorkspaceHandle, path: &str) -> Option<String> {
     let normalized = path.trim_start_matches("./").trim_start_matches('/');
 
@@ -1671,10 +4396,7 @@ fn component_relative_path(handle: &WorkspaceHandle, path: &str) -> Option<Strin
         return Some(String::new());
     }
 
-    let stripped = match normalized.strip_prefix("components/") {
-        Some(value) => value,
-        None => return None,
-    };
+    let stripped = normalized.strip_prefix("components/")?;
 
     let alias_suffix = normalize_alias_path(handle.config.aliases.components.filesystem_path());
     let suffix = alias_suffix
@@ -1682,11 +4404,449 @@ fn component_relative_path(handle: &WorkspaceHandle, path: &str) -> Option<Strin
         .trim_start_matches('/');
 
     let mut relative = stripped;
-    if !suffix.is_empty() {
-        if let Some(after_suffix) = relative.strip_prefix(suffix) {
-            relative = after_suffix.trim_start_matches('/');
-        }
+    if !suffix.is_empty()
+        && let Some(after_suffix) = relative.strip_prefix(suffix)
+    {
+        relative = after_suffix.trim_start_matches('/');
     }
 
     Some(relative.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nocta_core::types::QuoteStyle;
+
+    #[test]
+    fn content_matches_identical_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("button.tsx");
+        fs::write(&path, "export const Button = 1;\n").unwrap();
+
+        assert!(content_matches(&path, b"export const Button = 1;\n"));
+    }
+
+    #[test]
+    fn content_matches_rejects_differing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("button.tsx");
+        fs::write(&path, "export const Button = 1;\n").unwrap();
+
+        assert!(!content_matches(&path, b"export const Button = 2;\n"));
+        assert!(!content_matches(&path, b"export const Button = 1;"));
+    }
+
+    #[test]
+    fn content_matches_rejects_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert!(!content_matches(&dir.path().join("button.tsx"), b""));
+        assert!(!content_matches(dir.path(), b""));
+    }
+
+    #[test]
+    fn normalize_import_path_strips_only_the_source_root() {
+        assert_eq!(
+            normalize_import_path("src/app/layout", Some("src")),
+            "app/layout"
+        );
+        assert_eq!(
+            normalize_import_path("app/lib/utils", Some("app")),
+            "lib/utils"
+        );
+        assert_eq!(
+            normalize_import_path("./src/components/ui/button", Some("src")),
+            "components/ui/button"
+        );
+    }
+
+    #[test]
+    fn normalize_import_path_keeps_nested_segments() {
+        assert_eq!(
+            normalize_import_path("app/features/app/widget", Some("src")),
+            "app/features/app/widget"
+        );
+        assert_eq!(
+            normalize_import_path("src/features/src/widget", Some("src")),
+            "features/src/widget"
+        );
+        assert_eq!(
+            normalize_import_path("components/app/card", None),
+            "components/app/card"
+        );
+    }
+
+    #[test]
+    fn normalize_import_path_requires_a_whole_segment() {
+        assert_eq!(normalize_import_path("src", Some("src")), "src");
+        assert_eq!(
+            normalize_import_path("srcs/button", Some("src")),
+            "srcs/button"
+        );
+        assert_eq!(
+            normalize_import_path("application/shell", Some("app")),
+            "application/shell"
+        );
+        assert_eq!(
+            normalize_import_path("src/app/layout", None),
+            "src/app/layout"
+        );
+    }
+
+    #[test]
+    fn registry_module_key_drops_either_conventional_root() {
+        assert_eq!(
+            registry_module_key("src/components/ui/button.tsx"),
+            "components/ui/button"
+        );
+        assert_eq!(registry_module_key("app/lib/utils.ts"), "lib/utils");
+        assert_eq!(
+            registry_module_key("./components/app/card.tsx"),
+            "components/app/card"
+        );
+    }
+
+    #[test]
+    fn export_barrel_merges_exports_written_while_waiting_for_the_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let barrel = dir.path().join("components/index.ts");
+        fs::create_dir_all(barrel.parent().unwrap()).unwrap();
+        fs::write(&barrel, "").unwrap();
+
+        // Another run holds the lock, having read the barrel before this one starts.
+        let other_run = cache::lock_project_file(&barrel).unwrap();
+
+        let waiting = {
+            let barrel = barrel.clone();
+            std::thread::spawn(move || {
+                let entries = BTreeMap::from([(
+                    "./ui/card".to_string(),
+                    BTreeSet::from(["Card".to_string()]),
+                )]);
+                merge_export_barrel(false, &barrel, entries, None, &mut Vec::new())
+                    .unwrap()
+                    .is_some()
+            })
+        };
+
+        // The other run writes its export while this one is blocked on the lock.
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        let button = BTreeMap::from([(
+            "./ui/button".to_string(),
+            BTreeSet::from(["Button".to_string()]),
+        )]);
+        let style = resolve_barrel_style(None, None);
+        fs::write(
+            &barrel,
+            build_export_block(&export_lines_from_map(&button, style)),
+        )
+        .unwrap();
+        drop(other_run);
+
+        assert!(waiting.join().unwrap());
+        let contents = fs::read_to_string(&barrel).unwrap();
+        assert!(contents.contains("Button"), "barrel: {}", contents);
+        assert!(contents.contains("Card"), "barrel: {}", contents);
+    }
+
+    #[test]
+    fn skipped_files_keep_their_previous_lock_entry() {
+        let locked = |path: &str, hash: &str| LockedFile {
+            path: path.into(),
+            hash: hash.into(),
+        };
+        let previous = LockedComponent {
+            name: "Button".into(),
+            registry: None,
+            registry_version: "1.0.0".into(),
+            files: vec![locked("components/ui/button.tsx", "sha256:old")],
+        };
+        let fresh = vec![
+            (
+                locked("components/ui/button.tsx", "sha256:customised"),
+                true,
+            ),
+            (
+                locked("components/ui/button.css", "sha256:customised"),
+                true,
+            ),
+            (locked("components/ui/button.test.tsx", "sha256:new"), false),
+        ];
+
+        assert_eq!(
+            merge_locked_files(fresh, Some(&previous)),
+            [
+                locked("components/ui/button.tsx", "sha256:old"),
+                locked("components/ui/button.test.tsx", "sha256:new"),
+            ]
+        );
+    }
+
+    #[test]
+    fn broken_links_name_the_missing_root_and_the_unreadable_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo_root = canonicalize_path(dir.path());
+        for (package, config) in [
+            ("ui", Some("{")),
+            ("empty", None),
+            (
+                "ok",
+                Some(
+                    r#"{"style":"default","tailwind":{"css":"src/styles.css"},"aliases":{"components":"src/components","utils":"src/lib/utils"}}"#,
+                ),
+            ),
+        ] {
+            let root = repo_root.join("packages").join(package);
+            fs::create_dir_all(&root).unwrap();
+            if let Some(config) = config {
+                fs::write(root.join("nocta.config.json"), config).unwrap();
+            }
+        }
+        fs::create_dir_all(repo_root.join("apps/web")).unwrap();
+        let workspace_cfg: WorkspaceConfig = serde_json::from_value(json!({
+            "kind": "app",
+            "root": "apps/web",
+            "linkedWorkspaces": [
+                { "kind": "ui", "root": "packages/missing", "config": "../../packages/missing/nocta.config.json" },
+                { "kind": "ui", "packageName": "@acme/ui", "root": "packages/ui", "config": "../../packages/ui/nocta.config.json" },
+                { "kind": "ui", "root": "packages/empty", "config": "../../packages/empty/nocta.config.json" },
+                { "kind": "ui", "root": "packages/ok", "config": "../../packages/ok/nocta.config.json" },
+            ],
+        }))
+        .unwrap();
+
+        let broken: Vec<(String, String)> = broken_links(&repo_root, &workspace_cfg)
+            .into_iter()
+            .map(|link| (link.label, link.problem))
+            .collect();
+
+        assert_eq!(broken.len(), 3, "{broken:?}");
+        assert_eq!(
+            broken[0],
+            (
+                "packages/missing".to_string(),
+                "root packages/missing does not exist".to_string()
+            )
+        );
+        assert_eq!(broken[1].0, "@acme/ui");
+        assert!(
+            broken[1]
+                .1
+                .starts_with("config ../../packages/ui/nocta.config.json could not be read: "),
+            "{broken:?}"
+        );
+        assert_eq!(
+            broken[2].1,
+            "config ../../packages/empty/nocta.config.json not found"
+        );
+
+        let ok = resolve_link(
+            &repo_root,
+            &repo_root.join("apps/web"),
+            &workspace_cfg.linked_workspaces[3],
+        )
+        .unwrap_or_else(|_| panic!("packages/ok should resolve"));
+        assert_eq!(ok.root_abs, repo_root.join("packages/ok"));
+        assert_eq!(
+            ok.config.aliases.components.filesystem_path(),
+            "src/components"
+        );
+    }
+
+    fn component_file(dir: &Path, name: &str, content: &str) -> ComponentFileWithContent {
+        ComponentFileWithContent {
+            workspace_id: "primary".into(),
+            absolute_path: dir.join(name),
+            display_path: PathBuf::from(name),
+            content: content.into(),
+            bytes: None,
+            component_name: "Button".into(),
+            component_slug: "button".into(),
+            registry: None,
+            file_type: "component".into(),
+            registry_path: format!("components/{}", name),
+            registry_imports: Vec::new(),
+        }
+    }
+
+    fn identical_differing_and_missing(dir: &Path) -> Vec<ComponentFileWithContent> {
+        fs::write(dir.join("same.tsx"), "export const Same = 1;\n").unwrap();
+        fs::write(dir.join("edited.tsx"), "export const Edited = 0;\n").unwrap();
+        vec![
+            component_file(dir, "same.tsx", "export const Same = 1;\n"),
+            component_file(dir, "edited.tsx", "export const Edited = 1;\n"),
+            component_file(dir, "new.tsx", "export const New = 1;\n"),
+        ]
+    }
+
+    #[test]
+    fn identical_files_are_reported_unchanged_and_not_rewritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = identical_differing_and_missing(dir.path());
+        let mut changes = Vec::new();
+
+        let unchanged =
+            write_component_files(&ConsoleReporter::new(), &files, false, &mut changes).unwrap();
+
+        assert_eq!(unchanged, [PathBuf::from("same.tsx")]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("edited.tsx")).unwrap(),
+            "export const Edited = 1;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("new.tsx")).unwrap(),
+            "export const New = 1;\n"
+        );
+        let recorded: Vec<_> = changes
+            .iter()
+            .map(|change| (change.path.clone(), change.previous_contents.clone()))
+            .collect();
+        assert_eq!(
+            recorded,
+            [
+                (
+                    dir.path().join("edited.tsx"),
+                    Some(b"export const Edited = 0;\n".to_vec())
+                ),
+                (dir.path().join("new.tsx"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn dry_runs_report_unchanged_files_without_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = identical_differing_and_missing(dir.path());
+        let mut changes = Vec::new();
+
+        let unchanged =
+            write_component_files(&ConsoleReporter::new(), &files, true, &mut changes).unwrap();
+
+        assert_eq!(unchanged, [PathBuf::from("same.tsx")]);
+        assert!(changes.is_empty());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("edited.tsx")).unwrap(),
+            "export const Edited = 0;\n"
+        );
+        assert!(!dir.path().join("new.tsx").exists());
+    }
+
+    fn line_style(quote: char, semicolons: bool) -> ExportLineStyle {
+        ExportLineStyle { quote, semicolons }
+    }
+
+    #[test]
+    fn barrel_style_is_detected_from_the_first_module_line() {
+        assert_eq!(
+            detect_barrel_style("// header\nexport { Button } from './button'\n"),
+            Some(line_style('\'', false))
+        );
+        assert_eq!(
+            detect_barrel_style("import './styles.css';\nexport { Card } from \"./card\"\n"),
+            Some(line_style('\'', true))
+        );
+        assert_eq!(
+            detect_barrel_style("export const version = 1;\nexport * from \"./card\";\n"),
+            Some(line_style('"', true))
+        );
+        assert_eq!(detect_barrel_style("export const version = 1;\n"), None);
+        assert_eq!(detect_barrel_style(""), None);
+    }
+
+    #[test]
+    fn configured_barrel_style_wins_field_by_field() {
+        let existing = "export { Button } from './button'\n";
+        assert_eq!(
+            resolve_barrel_style(None, Some(existing)),
+            line_style('\'', false)
+        );
+        assert_eq!(resolve_barrel_style(None, None), line_style('"', true));
+        assert_eq!(
+            resolve_barrel_style(
+                Some(BarrelStyle {
+                    quotes: Some(QuoteStyle::Double),
+                    semicolons: None,
+                }),
+                Some(existing),
+            ),
+            line_style('"', false)
+        );
+        assert_eq!(
+            resolve_barrel_style(
+                Some(BarrelStyle {
+                    quotes: None,
+                    semicolons: Some(true),
+                }),
+                Some(existing),
+            ),
+            line_style('\'', true)
+        );
+    }
+
+    #[test]
+    fn dedupe_barrel_drops_repeats_and_lines_the_block_covers() {
+        let barrel = format!(
+            "export {{ Card }} from \"./card\";\n\
+             export {{ Card }} from \"./card\";\n\
+             export {{ Button }} from \"./button\";\n\
+             export {{ Badge }} from \"./badge\";\n\
+             {EXPORT_BLOCK_START}\n\
+             export {{ Button, buttonVariants }} from \"./button\";\n\
+             export {{ Button, buttonVariants }} from \"./button\";\n\
+             {EXPORT_BLOCK_END}\n"
+        );
+
+        let deduped = dedupe_barrel(&barrel);
+
+        assert_eq!(
+            deduped.content,
+            format!(
+                "export {{ Card }} from \"./card\";\n\
+                 export {{ Badge }} from \"./badge\";\n\
+                 {EXPORT_BLOCK_START}\n\
+                 export {{ Button, buttonVariants }} from \"./button\";\n\
+                 {EXPORT_BLOCK_END}\n"
+            )
+        );
+        assert_eq!(
+            deduped.removed,
+            [
+                "export { Card } from \"./card\";",
+                "export { Button } from \"./button\";",
+                "export { Button, buttonVariants } from \"./button\";",
+            ]
+        );
+        assert!(deduped.conflicts.is_empty());
+        assert_eq!(dedupe_barrel(&deduped.content).content, deduped.content);
+    }
+
+    #[test]
+    fn dedupe_barrel_reports_names_exported_from_several_modules() {
+        let barrel = format!(
+            "export {{ Button }} from './legacy/button'\n\
+             {EXPORT_BLOCK_START}\n\
+             export {{ Button }} from './button'\n\
+             export {{ Card as Panel }} from './card'\n\
+             {EXPORT_BLOCK_END}\n\
+             export {{ Panel }} from './panel'\n"
+        );
+
+        let deduped = dedupe_barrel(&barrel);
+
+        assert_eq!(deduped.content, barrel);
+        assert!(deduped.removed.is_empty());
+        let conflicts: Vec<(&str, Vec<&str>)> = deduped
+            .conflicts
+            .iter()
+            .map(|(name, modules)| (name.as_str(), modules.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(
+            conflicts,
+            [
+                ("Button", vec!["./button", "./legacy/button"]),
+                ("Panel", vec!["./card", "./panel"]),
+            ]
+        );
+    }
+}
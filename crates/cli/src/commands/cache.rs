@@ -4,6 +4,7 @@ use nocta_core::cache;
 
 use crate::commands::{CommandOutcome, CommandResult};
 use crate::reporter::ConsoleReporter;
+use crate::util::warn_cache_fallback;
 
 #[derive(Args, Debug)]
 pub struct CacheArgs {
@@ -20,6 +21,15 @@ pub enum CacheCommand {
         /// Confirm cache deletion without interactive prompt.
         #[arg(long, short = 'y', alias = "yes")]
         force: bool,
+        /// Report what would be removed without deleting anything (takes precedence over `--force`).
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+    /// Remove cached registry data that no longer parses (e.g. after an interrupted write).
+    Verify {
+        /// List corrupt entries without deleting them.
+        #[arg(long = "dry-run")]
+        dry_run: bool,
     },
 }
 
@@ -28,9 +38,27 @@ pub async fn run(reporter: &ConsoleReporter, args: CacheArgs) -> CommandResult {
         CacheCommand::Info => {
             let dir = cache::cache_dir();
             reporter.info(format!("Cache directory: {}", dir.display()));
+            warn_cache_fallback(reporter);
             Ok(CommandOutcome::Completed)
         }
-        CacheCommand::Clear { force } => {
+        CacheCommand::Clear { force, dry_run } => {
+            if dry_run {
+                let dir = cache::cache_dir();
+                let usage = cache::cache_usage().context("failed to inspect cache")?;
+                reporter.info(format!("[dry-run] Would remove {}", dir.display()));
+                reporter.info(format!(
+                    "[dry-run] {} cached {} ({})",
+                    usage.entries,
+                    if usage.entries == 1 {
+                        "entry"
+                    } else {
+                        "entries"
+                    },
+                    format_bytes(usage.bytes)
+                ));
+                return Ok(CommandOutcome::NoOp);
+            }
+
             if !force {
                 reporter.warn("Cache not cleared. Re-run with `--force` to confirm deletion.");
                 return Ok(CommandOutcome::NoOp);
@@ -40,5 +68,57 @@ pub async fn run(reporter: &ConsoleReporter, args: CacheArgs) -> CommandResult {
             reporter.info("Cache directory removed.");
             Ok(CommandOutcome::Completed)
         }
+        CacheCommand::Verify { dry_run } => {
+            let corrupt = cache::verify_cache(dry_run).context("failed to verify cache")?;
+            if corrupt.is_empty() {
+                reporter.info("All cache entries are valid.");
+                return Ok(CommandOutcome::NoOp);
+            }
+
+            for entry in &corrupt {
+                reporter.info(format!(
+                    "{}{} {} ({})",
+                    if dry_run { "[dry-run] " } else { "" },
+                    if dry_run { "Would remove" } else { "Removed" },
+                    entry.path.display(),
+                    entry.reason
+                ));
+            }
+            reporter.info(format!(
+                "{}{} corrupt cache {} {}.",
+                if dry_run { "[dry-run] " } else { "" },
+                corrupt.len(),
+                if corrupt.len() == 1 {
+                    "entry"
+                } else {
+                    "entries"
+                },
+                if dry_run {
+                    "would be removed"
+                } else {
+                    "removed"
+                }
+            ));
+            Ok(if dry_run {
+                CommandOutcome::NoOp
+            } else {
+                CommandOutcome::Completed
+            })
+        }
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
     }
+    format!("{:.1} {}", value, UNITS[unit])
 }
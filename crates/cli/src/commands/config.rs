@@ -0,0 +1,319 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use clap::{Args, Subcommand};
+use owo_colors::{OwoColorize, Stream};
+use serde_json::{Value, json};
+
+use crate::commands::{CommandOutcome, CommandResult};
+use crate::reporter::ConsoleReporter;
+use crate::util::{canonicalize_path, warn_cache_fallback};
+use nocta_core::cache;
+use nocta_core::config::{
+    config_path, config_schema, get_config_value, read_config, set_config_value, write_config,
+};
+use nocta_core::deps::find_program;
+use nocta_core::framework::detect_framework;
+use nocta_core::registry::{asset_ttl, registry_ttl};
+use nocta_core::types::{CacheConfig, Config};
+use nocta_core::workspace::{
+    PackageManagerKind, WorkspaceManifest, detect_package_manager, find_repo_root,
+    load_workspace_manifest,
+};
+
+#[derive(Args, Debug)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: Option<ConfigCommand>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Print the effective settings resolved for the current directory.
+    #[command(alias = "env")]
+    Print {
+        /// Emit the settings as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the JSON schema for nocta.config.json to stdout.
+    Schema,
+    /// Print one value from nocta.config.json by dotted key, e.g. `aliases.components`.
+    Get {
+        #[arg(value_name = "key")]
+        key: String,
+    },
+    /// Set one value in nocta.config.json by dotted key, e.g. `tailwind.css src/app.css`.
+    /// Values are parsed as JSON when possible (`true`, `300000`, `["a"]`), otherwise as text.
+    Set {
+        #[arg(value_name = "key")]
+        key: String,
+        #[arg(value_name = "value")]
+        value: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistryUrlSource {
+    Flag,
+    Env,
+    Config,
+    Manifest,
+    Default,
+}
+
+impl RegistryUrlSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RegistryUrlSource::Flag => "flag",
+            RegistryUrlSource::Env => "env",
+            RegistryUrlSource::Config => "config",
+            RegistryUrlSource::Manifest => "manifest",
+            RegistryUrlSource::Default => "default",
+        }
+    }
+}
+
+pub struct RegistrySettings<'a> {
+    pub url: &'a str,
+    pub source: RegistryUrlSource,
+}
+
+pub async fn run(
+    registry: RegistrySettings<'_>,
+    reporter: &ConsoleReporter,
+    args: ConfigArgs,
+) -> CommandResult {
+    match args.command.unwrap_or(ConfigCommand::Print { json: false }) {
+        ConfigCommand::Print { json } => {
+            let snapshot = collect_settings(&registry)?;
+            if json {
+                reporter.info(serde_json::to_string_pretty(&snapshot)?);
+            } else {
+                print_settings(reporter, &snapshot);
+            }
+            Ok(CommandOutcome::Completed)
+        }
+        ConfigCommand::Schema => {
+            reporter.info(serde_json::to_string_pretty(&config_schema())?);
+            Ok(CommandOutcome::Completed)
+        }
+        ConfigCommand::Get { key } => {
+            let config = require_config()?;
+            match get_config_value(&config, &key)? {
+                Some(Value::String(value)) => reporter.info(value),
+                Some(value) => reporter.info(serde_json::to_string_pretty(&value)?),
+                None => {
+                    return Err(anyhow!(
+                        "`{}` is not set in {}",
+                        key,
+                        config_path().display()
+                    ));
+                }
+            }
+            Ok(CommandOutcome::Completed)
+        }
+        ConfigCommand::Set { key, value } => {
+            let config = require_config()?;
+            let updated = set_config_value(&config, &key, &value)?;
+            write_config(&updated)?;
+            reporter.info(format!(
+                "{}",
+                format!("Updated {} in {}", key, config_path().display())
+                    .if_supports_color(Stream::Stdout, |text| text.green())
+            ));
+            Ok(CommandOutcome::Completed)
+        }
+    }
+}
+
+fn require_config() -> Result<Config> {
+    read_config()
+        .with_context(|| format!("failed to load {}", config_path().display()))?
+        .ok_or_else(|| {
+            anyhow!(
+                "{} not found. Run \"npx nocta-ui init\" first",
+                config_path().display()
+            )
+        })
+}
+
+fn collect_settings(registry: &RegistrySettings<'_>) -> Result<Value> {
+    let current_dir = canonicalize_path(&std::env::current_dir()?);
+    let repo_root =
+        canonicalize_path(&find_repo_root(&current_dir).unwrap_or_else(|| current_dir.clone()));
+    let manifest = load_workspace_manifest(&repo_root)
+        .map_err(|err| anyhow!("failed to read workspace manifest: {}", err))?;
+    let package_manager = resolve_package_manager(manifest.as_ref(), &repo_root);
+    let detection = detect_framework();
+    let config =
+        read_config().with_context(|| format!("failed to load {}", config_path().display()))?;
+    let cache_config = config.as_ref().and_then(|config| config.cache.as_ref());
+
+    Ok(json!({
+        "registry": {
+            "url": registry.url,
+            "source": registry.source.as_str(),
+        },
+        "cache": {
+            "directory": cache::cache_dir().display().to_string(),
+            "directorySource": cache::cache_dir_source().as_str(),
+            "registryTtlMs": duration_ms(registry_ttl(
+                cache_config.and_then(CacheConfig::registry_ttl)
+            )),
+            "assetTtlMs": duration_ms(asset_ttl(cache_config.and_then(CacheConfig::asset_ttl))),
+        },
+        "framework": {
+            "kind": detection.framework.as_str(),
+            "version": detection.version,
+        },
+        "packageManager": package_manager.as_str(),
+        "packageManagerPath": find_program(package_manager.as_str())
+            .map(|path| path.display().to_string()),
+        "workspace": {
+            "currentDir": current_dir.display().to_string(),
+            "repoRoot": repo_root.display().to_string(),
+            "manifest": manifest,
+        },
+        "config": {
+            "path": config_path().display().to_string(),
+            "contents": config,
+        },
+    }))
+}
+
+fn resolve_package_manager(
+    manifest: Option<&WorkspaceManifest>,
+    repo_root: &Path,
+) -> PackageManagerKind {
+    manifest
+        .and_then(|manifest| manifest.package_manager)
+        .or_else(|| detect_package_manager(repo_root))
+        .unwrap_or(PackageManagerKind::Npm)
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    duration.as_millis() as u64
+}
+
+fn print_settings(reporter: &ConsoleReporter, snapshot: &Value) {
+    let text = |pointer: &str| -> String {
+        match snapshot.pointer(pointer) {
+            Some(Value::String(value)) => value.clone(),
+            Some(Value::Null) | None => "none".into(),
+            Some(other) => other.to_string(),
+        }
+    };
+
+    reporter.info(format!(
+        "{}",
+        "Registry:".if_supports_color(Stream::Stdout, |text| text.blue())
+    ));
+    reporter.info(format!(
+        "   {}",
+        format!("{} ({})", text("/registry/url"), text("/registry/source"))
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+
+    reporter.info(format!(
+        "\n{}",
+        "Cache:".if_supports_color(Stream::Stdout, |text| text.blue())
+    ));
+    reporter.info(format!(
+        "   {}",
+        format!("Directory: {}", text("/cache/directory"))
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    warn_cache_fallback(reporter);
+    reporter.info(format!(
+        "   {}",
+        format!("Registry TTL: {}ms", text("/cache/registryTtlMs"))
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "   {}",
+        format!("Asset TTL: {}ms", text("/cache/assetTtlMs"))
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+
+    reporter.info(format!(
+        "\n{}",
+        "Project:".if_supports_color(Stream::Stdout, |text| text.blue())
+    ));
+    reporter.info(format!(
+        "   {}",
+        format!(
+            "Framework: {} {}",
+            text("/framework/kind"),
+            text("/framework/version")
+        )
+        .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "   {}",
+        format!("Package manager: {}", text("/packageManager"))
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    if snapshot
+        .pointer("/packageManagerPath")
+        .is_none_or(Value::is_null)
+    {
+        reporter.warn(format!(
+            "   {}",
+            format!(
+                "Warning: `{}` was not found on PATH; dependency installs will fail",
+                text("/packageManager")
+            )
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+    }
+    reporter.info(format!(
+        "   {}",
+        format!("Repo root: {}", text("/workspace/repoRoot"))
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "   {}",
+        format!("Current directory: {}", text("/workspace/currentDir"))
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+
+    if let Some(Value::Array(workspaces)) = snapshot.pointer("/workspace/manifest/workspaces") {
+        reporter.info(format!(
+            "\n{}",
+            "Workspaces:".if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
+        for entry in workspaces {
+            let field = |key: &str| entry.get(key).and_then(Value::as_str).unwrap_or("");
+            reporter.info(format!(
+                "   {}",
+                format!("{} [{}] ({})", field("name"), field("kind"), field("root"))
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+    }
+
+    reporter.info(format!(
+        "\n{}",
+        format!("{}:", config_path().display())
+            .if_supports_color(Stream::Stdout, |text| text.blue())
+    ));
+    match snapshot.pointer("/config/contents") {
+        Some(Value::Null) | None => {
+            reporter.info(format!(
+                "   {}",
+                "not found".if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+        Some(config) => {
+            let pretty = serde_json::to_string_pretty(config).unwrap_or_default();
+            for line in pretty.lines() {
+                reporter.info(format!(
+                    "   {}",
+                    line.if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
+            }
+        }
+    }
+}
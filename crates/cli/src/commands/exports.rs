@@ -0,0 +1,28 @@
+use clap::{Args, Subcommand};
+
+use crate::commands::CommandResult;
+use crate::commands::add::dedupe_export_barrels;
+use crate::reporter::ConsoleReporter;
+
+#[derive(Args, Debug)]
+pub struct ExportsArgs {
+    #[command(subcommand)]
+    pub command: ExportsCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ExportsCommand {
+    /// Remove duplicate re-exports from the configured export barrels and report names
+    /// exported from more than one module.
+    Dedupe {
+        /// List the lines that would be removed without rewriting any barrel.
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+}
+
+pub async fn run(reporter: &ConsoleReporter, args: ExportsArgs) -> CommandResult {
+    match args.command {
+        ExportsCommand::Dedupe { dry_run } => dedupe_export_barrels(reporter, dry_run),
+    }
+}
@@ -4,31 +4,36 @@ use std::io;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result, anyhow};
-use clap::Args;
-use dialoguer::{Input, MultiSelect, Select, theme::ColorfulTheme};
-use indicatif::ProgressBar;
-use owo_colors::OwoColorize;
+use clap::{Args, ValueEnum};
+use dialoguer::{Confirm, Input, MultiSelect, Select, theme::ColorfulTheme};
+use owo_colors::{OwoColorize, Stream};
 use pathdiff::diff_paths;
-use serde_json::Value;
+use serde_json::{Value, json};
 
 use crate::commands::{CommandOutcome, CommandResult};
-use crate::reporter::ConsoleReporter;
+use crate::journal::{FileChange, ensure_change_record, rollback_and_report};
+use crate::report::{DependencyRecord, write_report};
+use crate::reporter::{ConsoleReporter, prompts_enabled};
 use crate::util::{
-    canonicalize_path, create_spinner, normalize_relative_path, normalize_relative_path_buf,
+    Spinner, canonicalize_path, create_spinner, ensure_outside_node_modules,
+    normalize_relative_path, normalize_relative_path_buf, print_planned_install,
+    warn_cache_fallback,
 };
-use nocta_core::config::{read_config, write_config};
+use nocta_core::config::{config_path, read_config, write_config};
 use nocta_core::deps::{
     DependencyScope, RequirementIssue, RequirementIssueReason, check_project_requirements,
-    plan_dependency_install,
+    get_installed_dependencies_at, plan_dependency_install,
 };
 use nocta_core::framework::{AppStructure, FrameworkKind, detect_framework};
 use nocta_core::fs::{file_exists, write_file};
+use nocta_core::lockfile::component_content_hash;
 use nocta_core::registry::RegistryClient;
-use nocta_core::rollback::rollback_changes;
-use nocta_core::tailwind::{TailwindCheck, add_design_tokens_to_css, check_tailwind_installation};
+use nocta_core::tailwind::{
+    TailwindCheck, add_design_tokens_to_css, check_tailwind_installation, css_has_tailwind_import,
+};
 use nocta_core::types::{
-    AliasPrefixes, Aliases, Config, ExportsConfig, ExportsTargetConfig, TailwindConfig,
-    WorkspaceConfig, WorkspaceKind, WorkspaceLink,
+    AliasPrefixes, Aliases, Config, ExportsConfig, ExportsTargetConfig, RegistryRequirements,
+    RegistryStyle, TailwindConfig, WorkspaceConfig, WorkspaceKind, WorkspaceLink,
 };
 use nocta_core::workspace::{
     PackageManagerContext, PackageManagerKind, WORKSPACE_MANIFEST_FILE, WorkspaceManifest,
@@ -40,8 +45,107 @@ use nocta_core::workspace::{
 pub struct InitArgs {
     #[arg(long = "dry-run")]
     pub dry_run: bool,
+    /// Icon library used for the scaffolded icons module (`none` skips it).
+    #[arg(long = "icons", value_enum, value_name = "set", default_value_t = IconSet::Radix)]
+    pub icons: IconSet,
+    /// Leave the Tailwind CSS file untouched instead of injecting design tokens.
+    #[arg(long = "skip-tokens")]
+    pub skip_tokens: bool,
+    /// Write a JSON report of the created files and installed dependencies to this path.
+    #[arg(long = "report", value_name = "file")]
+    pub report: Option<PathBuf>,
+    /// Complete an existing setup by creating only the missing helpers and design tokens.
+    #[arg(long = "repair")]
+    pub repair: bool,
+    /// Only write nocta.config.json and the workspace manifest; skip dependencies, helpers,
+    /// design tokens and the Tailwind CSS v4 requirement.
+    #[arg(long = "components-only", conflicts_with = "repair")]
+    pub components_only: bool,
+    /// Skip the summary printed after initializing; only the success line and warnings remain.
+    #[arg(long = "no-summary")]
+    pub no_summary: bool,
+    /// Don't write nocta.workspace.json for single-package projects (ignored in monorepos).
+    #[arg(long = "no-manifest")]
+    pub no_manifest: bool,
+    /// Extra argument passed to the package manager install command (repeatable).
+    #[arg(
+        long = "install-arg",
+        value_name = "arg",
+        env = "NOCTA_INSTALL_ARGS",
+        value_delimiter = ' ',
+        allow_hyphen_values = true
+    )]
+    pub install_args: Vec<String>,
+    /// Subpath export written to a shared UI package's package.json, e.g. `./*=./src/components/ui/*`
+    /// (repeatable). Stored in `exports.subpaths`.
+    #[arg(long = "export-subpath", value_name = "subpath=target", value_parser = parse_export_subpath)]
+    pub export_subpaths: Vec<(String, String)>,
+    /// Registry style stored in `style`. Prompted for when the registry offers several.
+    #[arg(long = "style", value_name = "name")]
+    pub style: Option<String>,
+}
+
+fn parse_export_subpath(input: &str) -> std::result::Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((subpath, target)) if !subpath.trim().is_empty() && !target.trim().is_empty() => {
+            Ok((subpath.trim().to_string(), target.trim().to_string()))
+        }
+        _ => Err(format!(
+            "invalid subpath export `{}` (expected e.g. ./*=./src/components/ui/*)",
+            input
+        )),
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IconSet {
+    #[default]
+    Radix,
+    Lucide,
+    None,
+}
+
+impl IconSet {
+    fn as_str(self) -> &'static str {
+        match self {
+            IconSet::Radix => "radix",
+            IconSet::Lucide => "lucide",
+            IconSet::None => "none",
+        }
+    }
+
+    fn asset_path(self) -> Option<&'static str> {
+        match self {
+            IconSet::Radix => Some("lib/icons.ts"),
+            IconSet::Lucide => Some("lib/icons-lucide.ts"),
+            IconSet::None => None,
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            IconSet::Radix => "Base Radix Icons mapping",
+            IconSet::Lucide => "Base Lucide icons mapping (requires lucide-react)",
+            IconSet::None => "No icons module",
+        }
+    }
 }
 
+/// Files at the repository root that dependency installs rewrite or create.
+const INSTALL_MANIFESTS: &[&str] = &[
+    "package.json",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "bun.lock",
+    "bun.lockb",
+];
+
+/// Style written to the config when the registry declares none.
+const DEFAULT_STYLE: &str = "default";
+
+/// Scopes for registries that publish a flat `requirements` map, so shared UI packages
+/// still leave React to their consumers.
 const SHARED_UI_PEER_DEPENDENCIES: &[&str] = &["react", "react-dom"];
 const SHARED_UI_DEV_DEPENDENCIES: &[&str] = &["@types/react"];
 
@@ -49,9 +153,22 @@ struct InitCommand<'a> {
     client: &'a RegistryClient,
     reporter: &'a ConsoleReporter,
     dry_run: bool,
+    icons: IconSet,
+    skip_tokens: bool,
     prefix: String,
-    spinner: ProgressBar,
+    spinner: Spinner,
     created_paths: Vec<PathBuf>,
+    /// Every file this run writes, snapshotted first so a failure restores the project.
+    file_changes: Vec<FileChange>,
+    installed_dependencies: Vec<DependencyRecord>,
+    report: Option<PathBuf>,
+    repair: bool,
+    components_only: bool,
+    no_summary: bool,
+    no_manifest: bool,
+    install_args: Vec<String>,
+    export_subpaths: Vec<(String, String)>,
+    style: Option<String>,
 }
 
 impl<'a> InitCommand<'a> {
@@ -67,64 +184,119 @@ impl<'a> InitCommand<'a> {
             client,
             reporter,
             dry_run,
+            icons: args.icons,
+            skip_tokens: args.skip_tokens,
             prefix,
             spinner,
             created_paths: Vec::new(),
+            file_changes: Vec::new(),
+            installed_dependencies: Vec::new(),
+            report: args.report,
+            repair: args.repair,
+            components_only: args.components_only,
+            no_summary: args.no_summary,
+            no_manifest: args.no_manifest,
+            install_args: args.install_args,
+            export_subpaths: args.export_subpaths,
+            style: args.style,
         }
     }
 
     async fn execute(&mut self) -> CommandResult {
-        if read_config()?.is_some() {
+        if let Some(config) = read_config()? {
             self.spinner.finish_and_clear();
-            self.reporter
-                .warn(format!("{}", "nocta.config.json already exists!".yellow()));
+            if self.repair || self.confirm_repair()? {
+                return self.complete_existing_setup(&config).await;
+            }
+            self.reporter.warn(format!(
+                "{}",
+                format!("{} already exists!", config_path().display())
+                    .if_supports_color(Stream::Stdout, |text| text.yellow())
+            ));
             self.reporter.info(format!(
                 "{}",
-                "Your project is already initialized.".dimmed()
+                "Your project is already initialized."
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+            self.reporter.info(format!(
+                "{}",
+                "Run `nocta-ui init --repair` to create any missing helpers or design tokens."
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
             ));
             return Ok(CommandOutcome::NoOp);
         }
 
         let workspace = self.resolve_workspace()?;
-        let tailwind = match self.ensure_tailwind_installed()? {
-            Some(check) => check,
-            None => return Ok(CommandOutcome::NoOp),
+        let tailwind = if self.components_only {
+            None
+        } else {
+            match self.ensure_tailwind_installed()? {
+                Some(check) => Some(check),
+                None => return Ok(CommandOutcome::NoOp),
+            }
         };
         let framework_detection = match self.detect_framework(&workspace)? {
             Some(detection) => detection,
             None => return Ok(CommandOutcome::NoOp),
         };
         let requirements = self.client.registry_requirements().await?;
-        let required_dependencies: BTreeMap<String, String> = requirements
-            .iter()
-            .map(|(n, v)| (n.clone(), v.clone()))
-            .collect();
+        let required_dependencies: BTreeMap<String, String> =
+            requirements.all().into_iter().collect();
         let manage_dependencies = dependencies_managed_in_workspace(&workspace);
 
-        self.handle_dependency_checks(manage_dependencies, &workspace, &requirements)?;
-        if !self.ensure_tailwind_v4(&tailwind)? {
-            return Ok(CommandOutcome::NoOp);
+        if let Some(tailwind) = tailwind.as_ref() {
+            self.handle_dependency_checks(manage_dependencies, &workspace, &requirements.all())?;
+            if !self.ensure_tailwind_v4(tailwind)? {
+                return Ok(CommandOutcome::NoOp);
+            }
         }
 
+        let style = self.select_style().await?;
+
         let mut config = build_config(workspace.config_workspace.kind, &framework_detection)?;
+        config.style = style;
         config.alias_prefixes = Some(AliasPrefixes {
             components: Some(config_alias_prefix(&framework_detection)),
             utils: Some(config_alias_prefix(&framework_detection)),
         });
         ensure_default_exports_config(&mut config, workspace.config_workspace.kind);
+        if !self.export_subpaths.is_empty() {
+            if workspace.config_workspace.kind == WorkspaceKind::Ui {
+                let exports = config.exports.get_or_insert_with(ExportsConfig::default);
+                exports
+                    .subpaths
+                    .get_or_insert_with(BTreeMap::new)
+                    .extend(self.export_subpaths.iter().cloned());
+            } else {
+                self.reporter.warn(format!(
+                    "{}",
+                    "--export-subpath ignored: package.json exports are only managed for shared UI workspaces"
+                        .if_supports_color(Stream::Stdout, |text| text.yellow())
+                ));
+            }
+        }
         config.workspace = Some(workspace.config_workspace.clone());
 
         self.write_config(&config)?;
         self.ensure_package_exports(&workspace, &config)?;
-        self.handle_dependencies(manage_dependencies, &required_dependencies, &workspace)?;
-
-        let (utils_created, icons_created) = self
-            .sync_registry_assets(manage_dependencies, &config)
-            .await?;
-        let tokens_added = self
-            .apply_tailwind_tokens(manage_dependencies, &workspace, &config)
-            .await?;
-        let tailwind_is_v4 = tailwind_v4(&tailwind);
+        let (utils_created, icons_created, tokens_added) = if self.components_only {
+            (None, None, false)
+        } else {
+            self.handle_dependencies(manage_dependencies, &requirements, &workspace)?;
+            let (utils_created, icons_created) = self
+                .sync_registry_assets(manage_dependencies, &config)
+                .await?;
+            let tokens_added = self
+                .apply_tailwind_tokens(manage_dependencies, &config)
+                .await?;
+            (utils_created, icons_created, tokens_added)
+        };
+        let tailwind_import_missing = manage_dependencies
+            && !self.dry_run
+            && !self.skip_tokens
+            && !self.components_only
+            && !css_has_tailwind_import(&config.tailwind.css);
+        let tailwind_is_v4 = tailwind.as_ref().is_some_and(tailwind_v4);
         self.persist_workspace_manifest(&workspace)?;
 
         self.finish();
@@ -136,10 +308,95 @@ impl<'a> InitCommand<'a> {
             icons_created,
             tokens_added,
             tailwind_is_v4,
+            tailwind_import_missing,
             &config,
             &framework_detection,
         );
 
+        if let Some(report_path) = self.report.clone() {
+            self.write_report(&report_path, Some(&workspace.config_workspace))
+                .await?;
+            self.reporter.info(format!(
+                "\n{}",
+                format!("Install report written to {}", report_path.display())
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+
+        Ok(CommandOutcome::Completed)
+    }
+
+    fn confirm_repair(&self) -> Result<bool> {
+        if !prompts_enabled() {
+            return Ok(false);
+        }
+
+        let prompt = format!(
+            "{} already exists. Complete the setup by creating any missing pieces?",
+            config_path().display()
+        );
+        let confirmed = Confirm::new()
+            .with_prompt(prompt)
+            .default(false)
+            .interact()?;
+        Ok(confirmed)
+    }
+
+    /// Re-runs the idempotent steps of `init` against an existing config, creating only
+    /// the helpers and design tokens an interrupted run left behind.
+    async fn complete_existing_setup(&mut self, config: &Config) -> CommandResult {
+        self.spinner = create_spinner(format!("{}Checking existing setup...", self.prefix));
+        let manage_here = config.workspace.as_ref().is_none_or(|workspace| {
+            workspace.kind != WorkspaceKind::App || workspace.linked_workspaces.is_empty()
+        });
+
+        let (utils_created, icons_created) = self.sync_registry_assets(manage_here, config).await?;
+        let tokens_added = self.apply_tailwind_tokens(manage_here, config).await?;
+        self.finish();
+
+        let created: Vec<String> = utils_created
+            .iter()
+            .chain(icons_created.iter())
+            .map(|path| path.display().to_string())
+            .chain(tokens_added.then(|| format!("{} (design tokens)", config.tailwind.css)))
+            .collect();
+
+        if created.is_empty() {
+            self.reporter.info(format!(
+                "{}",
+                "Setup is already complete - nothing to repair."
+                    .if_supports_color(Stream::Stdout, |text| text.green())
+            ));
+            return Ok(CommandOutcome::NoOp);
+        }
+
+        let heading = if self.dry_run {
+            "[dry-run] Would complete setup:"
+        } else {
+            "Setup completed:"
+        };
+        self.reporter.info(format!(
+            "{}",
+            heading.if_supports_color(Stream::Stdout, |text| text.green())
+        ));
+        for item in &created {
+            self.reporter.info(format!(
+                "   {}",
+                item.as_str()
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+
+        if let Some(report_path) = self.report.clone() {
+            self.write_report(&report_path, config.workspace.as_ref())
+                .await?;
+            self.reporter.info(format!(
+                "\n{}",
+                format!("Install report written to {}", report_path.display())
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+
         Ok(CommandOutcome::Completed)
     }
 
@@ -150,7 +407,12 @@ impl<'a> InitCommand<'a> {
         self.spinner.suspend(|| {
             resolved = Some(resolve_workspace_context());
         });
-        resolved.expect("workspace resolution to run")
+        let mut workspace = resolved.expect("workspace resolution to run")?;
+        workspace
+            .package_manager_context
+            .extra_install_args
+            .extend(self.install_args.iter().cloned());
+        Ok(workspace)
     }
 
     fn ensure_tailwind_installed(&mut self) -> Result<Option<TailwindCheck>> {
@@ -197,8 +459,7 @@ impl<'a> InitCommand<'a> {
             let requirements_base = workspace
                 .package_manager_context
                 .workspace_root
-                .as_ref()
-                .map(|path| path.as_path())
+                .as_deref()
                 .unwrap_or_else(|| Path::new("."));
             let requirement_issues = check_project_requirements(requirements_base, requirements)?;
             if !requirement_issues.is_empty() {
@@ -219,7 +480,7 @@ impl<'a> InitCommand<'a> {
                 reporter.info(format!(
                     "{}",
                     "Detected linked shared UI workspace(s); skipping dependency checks and installation for this workspace."
-                        .dimmed()
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
                 ));
             });
             Ok(())
@@ -242,64 +503,54 @@ impl<'a> InitCommand<'a> {
             self.reporter.blank();
             self.reporter.info(format!(
                 "{}",
-                "[dry-run] Would create configuration:".blue()
+                "[dry-run] Would create configuration:"
+                    .if_supports_color(Stream::Stdout, |text| text.blue())
+            ));
+            self.reporter.info(format!(
+                "   {}",
+                config_path()
+                    .display()
+                    .to_string()
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
             ));
-            self.reporter
-                .info(format!("   {}", "nocta.config.json".dimmed()));
             Ok(())
         } else {
-            write_config(config).context("failed to write nocta.config.json")?;
-            self.created_paths.push(PathBuf::from("nocta.config.json"));
+            ensure_change_record(config_path(), &mut self.file_changes)?;
+            write_config(config)
+                .with_context(|| format!("failed to write {}", config_path().display()))?;
+            self.created_paths.push(config_path().to_path_buf());
             Ok(())
         }
     }
 
+    /// Snapshots the package.json and lockfiles a package manager install may rewrite.
+    fn journal_install_manifests(&mut self, workspace: &WorkspaceResolution) -> Result<()> {
+        ensure_change_record(
+            &workspace.workspace_root_abs.join("package.json"),
+            &mut self.file_changes,
+        )?;
+        for name in INSTALL_MANIFESTS {
+            ensure_change_record(&workspace.repo_root.join(name), &mut self.file_changes)?;
+        }
+        Ok(())
+    }
+
     fn handle_dependencies(
         &mut self,
         manage_here: bool,
-        required: &BTreeMap<String, String>,
+        requirements: &RegistryRequirements,
         workspace: &WorkspaceResolution,
     ) -> Result<()> {
         if manage_here {
-            let is_shared_ui = workspace.config_workspace.kind == WorkspaceKind::Ui;
-            let mut install_groups: Vec<(DependencyScope, BTreeMap<String, String>)> = Vec::new();
-
-            if is_shared_ui {
-                let mut peer = BTreeMap::new();
-                let mut dev = BTreeMap::new();
-                let mut regular = BTreeMap::new();
-
-                for (dep, version) in required {
-                    let name = dep.as_str();
-                    if SHARED_UI_PEER_DEPENDENCIES.contains(&name) {
-                        peer.insert(dep.clone(), version.clone());
-                    } else if SHARED_UI_DEV_DEPENDENCIES.contains(&name) {
-                        dev.insert(dep.clone(), version.clone());
-                    } else {
-                        regular.insert(dep.clone(), version.clone());
-                    }
-                }
-
-                if !peer.is_empty() {
-                    install_groups.push((DependencyScope::Peer, peer));
-                }
-                if !dev.is_empty() {
-                    install_groups.push((DependencyScope::Dev, dev));
-                }
-                if !regular.is_empty() {
-                    install_groups.push((DependencyScope::Regular, regular));
-                }
-            } else if !required.is_empty() {
-                let regular: BTreeMap<String, String> = required
-                    .iter()
-                    .map(|(k, v)| (k.clone(), v.clone()))
-                    .collect();
-                install_groups.push((DependencyScope::Regular, regular));
-            }
+            let install_groups =
+                requirement_install_groups(requirements, workspace.config_workspace.kind);
 
             if install_groups.is_empty() {
                 return Ok(());
             }
+            if !self.dry_run {
+                self.journal_install_manifests(workspace)?;
+            }
 
             if self.dry_run {
                 self.spinner.set_message(format!(
@@ -322,15 +573,29 @@ impl<'a> InitCommand<'a> {
 
                 let install_map: HashMap<String, String> =
                     deps.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+                let records: Vec<DependencyRecord> = deps
+                    .iter()
+                    .map(|(name, requested)| DependencyRecord {
+                        workspace: workspace.workspace_root_str.clone(),
+                        name: name.clone(),
+                        requested: requested.clone(),
+                        resolved: None,
+                        scope,
+                    })
+                    .collect();
 
                 if self.dry_run {
                     self.reporter.info(format!(
                         "{}",
-                        format!("[dry-run] Would install {}:", scope_label).blue()
+                        format!("[dry-run] Would install {}:", scope_label)
+                            .if_supports_color(Stream::Stdout, |text| text.blue())
                     ));
                     for (dep, version) in deps {
-                        self.reporter
-                            .info(format!("   {}", format!("{}@{}", dep, version).dimmed()));
+                        self.reporter.info(format!(
+                            "   {}",
+                            format!("{}@{}", dep, version)
+                                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+                        ));
                     }
 
                     if let Some(plan) = plan_dependency_install(
@@ -338,11 +603,9 @@ impl<'a> InitCommand<'a> {
                         &workspace.package_manager_context,
                         scope,
                     )? {
-                        self.reporter.info(format!(
-                            "{}",
-                            format!("   Command: {}", plan.command_line().join(" ")).dimmed()
-                        ));
+                        print_planned_install(self.reporter, &plan);
                     }
+                    self.installed_dependencies.extend(records);
                     continue;
                 }
 
@@ -376,11 +639,27 @@ impl<'a> InitCommand<'a> {
                             reporter.warn(format!(
                                 "{}",
                                 format!("{}; you can install them manually", scope_failure)
-                                    .yellow()
+                                    .if_supports_color(Stream::Stdout, |text| text.yellow())
+                            ));
+                            reporter.info(format!(
+                                "{}",
+                                format!("Run: {}", command)
+                                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+                            ));
+                            reporter.error(format!(
+                                "{}",
+                                format!("Error: {}", err)
+                                    .if_supports_color(Stream::Stderr, |text| text.red())
                             ));
-                            reporter.info(format!("{}", format!("Run: {}", command).dimmed()));
-                            reporter.error(format!("{}", format!("Error: {}", err).red()));
                         });
+                    } else {
+                        let resolved =
+                            get_installed_dependencies_at(&workspace.workspace_root_abs)?;
+                        self.installed_dependencies
+                            .extend(records.into_iter().map(|record| DependencyRecord {
+                                resolved: resolved.get(&record.name).cloned(),
+                                ..record
+                            }));
                     }
                 }
             }
@@ -389,7 +668,7 @@ impl<'a> InitCommand<'a> {
             self.reporter.info(format!(
                 "{}",
                 "[dry-run] Would skip dependency installation in this workspace (managed via linked shared UI workspace)."
-                    .blue()
+                    .if_supports_color(Stream::Stdout, |text| text.blue())
             ));
         }
         Ok(())
@@ -404,12 +683,32 @@ impl<'a> InitCommand<'a> {
             return Ok(());
         }
 
-        let Some(exports_cfg) = config.exports.as_ref().and_then(|cfg| cfg.components()) else {
+        let Some(exports_cfg) = config.exports.as_ref() else {
             return Ok(());
         };
 
-        let barrel = exports_cfg.barrel_path().trim();
-        if barrel.is_empty() {
+        // `"."` points at the components barrel; `subpaths` add granular entries.
+        let mut desired: Vec<(String, String)> = Vec::new();
+        if let Some(components) = exports_cfg.components() {
+            let barrel = components.barrel_path().trim();
+            if !barrel.is_empty() {
+                desired.push((".".into(), sanitize_barrel_for_exports(barrel)));
+            }
+        }
+        for (subpath, target) in exports_cfg.subpaths.iter().flatten() {
+            if subpath.trim().is_empty() || target.trim().is_empty() {
+                continue;
+            }
+            let Some(key) = sanitize_export_key(subpath) else {
+                self.reporter.warn(format!(
+                    "Skipping export subpath \"{}\": keys must stay inside the package.",
+                    subpath
+                ));
+                continue;
+            };
+            desired.push((key, sanitize_barrel_for_exports(target)));
+        }
+        if desired.is_empty() {
             return Ok(());
         }
 
@@ -426,50 +725,8 @@ impl<'a> InitCommand<'a> {
 
         let mut json: Value =
             serde_json::from_str(&contents).context("failed to parse package.json")?;
-        let export_value = sanitize_barrel_for_exports(barrel);
-        let export_json = Value::String(export_value.clone());
-        let mut changed = false;
-
-        match json.get_mut("exports") {
-            Some(Value::Object(map)) => match map.get(".") {
-                Some(Value::String(current)) => {
-                    if current != &export_value {
-                        map.insert(".".into(), export_json.clone());
-                        changed = true;
-                    }
-                }
-                Some(Value::Object(_)) | Some(Value::Array(_)) => {
-                    // Respect existing complex shape; do not modify.
-                    return Ok(());
-                }
-                Some(_) => {
-                    // Unsupported scalar type. Leave untouched.
-                    return Ok(());
-                }
-                None => {
-                    map.insert(".".into(), export_json.clone());
-                    changed = true;
-                }
-            },
-            Some(Value::String(current)) => {
-                if current != &export_value {
-                    json["exports"] = Value::String(export_value.clone());
-                    changed = true;
-                }
-            }
-            Some(_) => {
-                // Unsupported shape; leave untouched.
-                return Ok(());
-            }
-            None => {
-                let mut map = serde_json::Map::new();
-                map.insert(".".into(), export_json.clone());
-                json["exports"] = Value::Object(map);
-                changed = true;
-            }
-        }
-
-        if !changed {
+        let changes = merge_package_exports(&mut json, &desired);
+        if changes.is_empty() {
             return Ok(());
         }
 
@@ -478,32 +735,39 @@ impl<'a> InitCommand<'a> {
 
         if self.dry_run {
             self.reporter.blank();
+            for (key, export_value) in &changes {
+                self.reporter.info(format!(
+                    "{}",
+                    format!(
+                        "[dry-run] Would set exports[\"{}\"] = \"{}\" in {}",
+                        key,
+                        export_value,
+                        display_path.display()
+                    )
+                    .if_supports_color(Stream::Stdout, |text| text.blue())
+                ));
+            }
+            return Ok(());
+        }
+
+        let updated = serde_json::to_string_pretty(&json)?;
+        ensure_change_record(&pkg_path, &mut self.file_changes)?;
+        fs::write(&pkg_path, updated)
+            .with_context(|| format!("failed to write {}", pkg_path.display()))?;
+        self.reporter.blank();
+        for (key, export_value) in &changes {
             self.reporter.info(format!(
                 "{}",
                 format!(
-                    "[dry-run] Would set exports[\".\"] = \"{}\" in {}",
+                    "Configured exports[\"{}\"] = \"{}\" in {}",
+                    key,
                     export_value,
                     display_path.display()
                 )
-                .blue()
+                .if_supports_color(Stream::Stdout, |text| text.green())
             ));
-            return Ok(());
         }
 
-        let updated = serde_json::to_string_pretty(&json)?;
-        fs::write(&pkg_path, updated)
-            .with_context(|| format!("failed to write {}", pkg_path.display()))?;
-        self.reporter.blank();
-        self.reporter.info(format!(
-            "{}",
-            format!(
-                "Configured exports[\".\"] = \"{}\" in {}",
-                export_value,
-                display_path.display()
-            )
-            .green()
-        ));
-
         Ok(())
     }
 
@@ -525,22 +789,29 @@ impl<'a> InitCommand<'a> {
                 "lib/utils.ts",
                 &utils_path,
                 &mut self.created_paths,
+                &mut self.file_changes,
                 "Utility functions",
             )
             .await?;
 
-            self.spinner
-                .set_message(format!("{}Creating base icons component...", self.prefix));
-            let icons_created = ensure_registry_asset(
-                self.client,
-                self.dry_run,
-                self.reporter,
-                "lib/icons.ts",
-                &icons_path,
-                &mut self.created_paths,
-                "Icons component",
-            )
-            .await?;
+            let icons_created = match self.icons.asset_path() {
+                Some(asset_path) => {
+                    self.spinner
+                        .set_message(format!("{}Creating base icons component...", self.prefix));
+                    ensure_registry_asset(
+                        self.client,
+                        self.dry_run,
+                        self.reporter,
+                        asset_path,
+                        &icons_path,
+                        &mut self.created_paths,
+                        &mut self.file_changes,
+                        "Icons component",
+                    )
+                    .await?
+                }
+                None => false,
+            };
             Ok((
                 utils_created.then_some(utils_path),
                 icons_created.then_some(icons_path),
@@ -555,21 +826,26 @@ impl<'a> InitCommand<'a> {
                 reporter.info(format!(
                     "{}",
                     "Linked shared UI workspace manages shared helpers; skipping utility and icon scaffolding."
-                        .dimmed()
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
                 ));
             });
             Ok((None, None))
         }
     }
 
-    async fn apply_tailwind_tokens(
-        &mut self,
-        manage_here: bool,
-        _workspace: &WorkspaceResolution,
-        config: &Config,
-    ) -> Result<bool> {
+    /// Picks the registry style for the config: `--style` when given, otherwise a prompt
+    /// when the registry offers several, otherwise its only (or first) style.
+    async fn select_style(&self) -> Result<String> {
+        let styles = self.client.styles().await?;
+        match preselected_style(&styles, self.style.as_deref(), prompts_enabled())? {
+            Some(style) => Ok(style),
+            None => self.spinner.suspend(|| prompt_style(&styles)),
+        }
+    }
+
+    async fn apply_tailwind_tokens(&mut self, manage_here: bool, config: &Config) -> Result<bool> {
         let tailwind_css = config.tailwind.css.clone();
-        if !manage_here {
+        if !manage_here || self.skip_tokens {
             return Ok(false);
         }
 
@@ -579,23 +855,87 @@ impl<'a> InitCommand<'a> {
             self.reporter.blank();
             self.reporter.info(format!(
                 "{}",
-                format!("[dry-run] Would update {}", tailwind_css).blue()
+                format!("[dry-run] Would update {}", tailwind_css)
+                    .if_supports_color(Stream::Stdout, |text| text.blue())
             ));
             return Ok(true);
         }
 
-        let added = add_design_tokens_to_css(self.client, &tailwind_css).await?;
+        ensure_change_record(Path::new(&tailwind_css), &mut self.file_changes)?;
+        let added = add_design_tokens_to_css(self.client, &tailwind_css, &config.style).await?;
         if added {
             self.created_paths.push(PathBuf::from(&tailwind_css));
         }
         Ok(added)
     }
 
+    async fn write_report(&self, path: &Path, workspace: Option<&WorkspaceConfig>) -> Result<()> {
+        let version = self.client.fetch_registry().await?.version;
+        let current_dir = canonicalize_path(&std::env::current_dir()?);
+        let files: Vec<Value> = self
+            .created_paths
+            .iter()
+            .map(|created| {
+                let display = if created.is_absolute() {
+                    diff_paths(created, &current_dir).unwrap_or_else(|| created.clone())
+                } else {
+                    created.clone()
+                };
+                json!({
+                    "path": normalize_relative_path(&display),
+                    "hash": fs::read_to_string(created).ok().map(|content| component_content_hash(&content)),
+                })
+            })
+            .collect();
+
+        write_report(
+            path,
+            "init",
+            self.dry_run,
+            json!({
+                "registries": [{
+                    "name": Value::Null,
+                    "url": self.client.base_url(),
+                    "version": version,
+                }],
+                "workspace": workspace.map(|workspace| json!({
+                    "kind": workspace.kind,
+                    "root": workspace.root,
+                    "packageName": workspace.package_name,
+                })),
+                "icons": self.icons.as_str(),
+                "files": files,
+                "dependencies": self
+                    .installed_dependencies
+                    .iter()
+                    .map(DependencyRecord::to_json)
+                    .collect::<Vec<_>>(),
+            }),
+        )
+    }
+
     fn persist_workspace_manifest(&mut self, workspace: &WorkspaceResolution) -> Result<()> {
+        if self.no_manifest {
+            if !workspace.is_monorepo {
+                return Ok(());
+            }
+            let reporter = self.reporter;
+            self.spinner.suspend(|| {
+                reporter.warn(format!(
+                    "{}",
+                    format!(
+                        "--no-manifest ignored: monorepos need {} to locate their workspaces",
+                        WORKSPACE_MANIFEST_FILE
+                    )
+                    .if_supports_color(Stream::Stdout, |text| text.yellow())
+                ));
+            });
+        }
         if self.dry_run {
             return Ok(());
         }
 
+        ensure_change_record(&workspace.manifest_path, &mut self.file_changes)?;
         write_workspace_manifest(&workspace.repo_root, &workspace.manifest)
             .map_err(|err| anyhow!("failed to write {}: {}", WORKSPACE_MANIFEST_FILE, err))?;
         if !workspace.manifest_existed {
@@ -604,6 +944,7 @@ impl<'a> InitCommand<'a> {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn print_summary(
         &self,
         manage_dependencies_here: bool,
@@ -613,9 +954,26 @@ impl<'a> InitCommand<'a> {
         icons_path: Option<PathBuf>,
         tokens_added: bool,
         tailwind_is_v4: bool,
+        tailwind_import_missing: bool,
         config: &Config,
         framework_detection: &nocta_core::framework::FrameworkDetection,
     ) {
+        if self.no_summary {
+            let message = if self.dry_run {
+                format!("[dry-run] {} would be created", config_path().display())
+            } else {
+                format!("nocta-ui initialized ({})", config_path().display())
+            };
+            self.reporter.info(format!(
+                "{}",
+                message.if_supports_color(Stream::Stdout, |text| text.green())
+            ));
+            if tailwind_import_missing {
+                print_tailwind_import_missing(self.reporter, config);
+            }
+            return;
+        }
+
         let framework_label = if framework_detection.framework == FrameworkKind::Unknown {
             format!(
                 "Custom ({})",
@@ -633,18 +991,19 @@ impl<'a> InitCommand<'a> {
             dependencies,
             !manage_dependencies_here,
             utils_path.as_deref(),
-            icons_path.as_deref(),
+            icons_path.as_deref().map(|path| (path, self.icons)),
             tokens_added,
+            self.skip_tokens,
+            self.components_only,
             tailwind_is_v4,
+            tailwind_import_missing,
             workspace,
         );
     }
 
     fn rollback(&self) {
-        if !self.dry_run && !self.created_paths.is_empty() {
-            let _ = rollback_changes(&self.created_paths);
-            self.reporter
-                .warn(format!("{}", "Rolled back partial changes".yellow()));
+        if !self.dry_run {
+            rollback_and_report(self.reporter, &self.file_changes);
         }
     }
 
@@ -708,7 +1067,7 @@ fn resolve_workspace_context() -> Result<WorkspaceResolution> {
         .as_ref()
         .map(|entry| entry.kind)
         .unwrap_or_else(|| guess_workspace_kind(&workspace_root_str));
-    let workspace_kind = if existing_entry.is_some() || !monorepo_detected {
+    let workspace_kind = if existing_entry.is_some() || !monorepo_detected || !prompts_enabled() {
         default_kind
     } else {
         prompt_workspace_kind(&theme, default_kind)?
@@ -721,7 +1080,7 @@ fn resolve_workspace_context() -> Result<WorkspaceResolution> {
 
     if !monorepo_detected {
         package_name = None;
-    } else if package_name.is_none() {
+    } else if package_name.is_none() && prompts_enabled() {
         let input: String = Input::with_theme(&theme)
             .with_prompt("Workspace package name (leave blank to skip)")
             .allow_empty(true)
@@ -739,12 +1098,15 @@ fn resolve_workspace_context() -> Result<WorkspaceResolution> {
         .cloned()
         .collect();
 
-    let linked_workspaces =
-        if workspace_kind == WorkspaceKind::App && monorepo_detected && !available_ui.is_empty() {
-            prompt_linked_workspaces(&theme, &available_ui, &workspace_root_abs, &repo_root)?
-        } else {
-            Vec::new()
-        };
+    let linked_workspaces = if workspace_kind == WorkspaceKind::App
+        && monorepo_detected
+        && !available_ui.is_empty()
+        && prompts_enabled()
+    {
+        prompt_linked_workspaces(&theme, &available_ui, &workspace_root_abs, &repo_root)?
+    } else {
+        Vec::new()
+    };
 
     let config_workspace = WorkspaceConfig {
         kind: workspace_kind,
@@ -805,9 +1167,11 @@ fn resolve_workspace_context() -> Result<WorkspaceResolution> {
 
 fn guess_workspace_kind(path: &str) -> WorkspaceKind {
     let lower = path.to_ascii_lowercase();
-    if lower.contains("/ui") || lower.contains("ui/") || lower.contains("packages/ui") {
-        WorkspaceKind::Ui
-    } else if lower.contains("package") && lower.contains("ui") {
+    if lower.contains("/ui")
+        || lower.contains("ui/")
+        || lower.contains("packages/ui")
+        || (lower.contains("package") && lower.contains("ui"))
+    {
         WorkspaceKind::Ui
     } else if lower.contains("lib") || lower.contains("library") {
         WorkspaceKind::Library
@@ -844,6 +1208,22 @@ fn prompt_workspace_kind(
     Ok(kind)
 }
 
+fn prompt_style(styles: &[RegistryStyle]) -> Result<String> {
+    let items: Vec<String> = styles
+        .iter()
+        .map(|style| match style.description.as_deref() {
+            Some(description) => format!("{}  ({})", style.name, description),
+            None => style.name.clone(),
+        })
+        .collect();
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Which style would you like to use?")
+        .items(&items)
+        .default(0)
+        .interact()?;
+    Ok(styles[selection].name.clone())
+}
+
 fn prompt_linked_workspaces(
     theme: &ColorfulTheme,
     entries: &[WorkspaceManifestEntry],
@@ -917,6 +1297,8 @@ pub async fn run(
     reporter: &ConsoleReporter,
     args: InitArgs,
 ) -> CommandResult {
+    ensure_outside_node_modules("init")?;
+    warn_cache_fallback(reporter);
     let mut command = InitCommand::new(client, reporter, args);
     match command.execute().await {
         Ok(outcome) => Ok(outcome),
@@ -932,23 +1314,51 @@ fn print_tailwind_missing_message(reporter: &ConsoleReporter, check: &TailwindCh
     let _ = check;
     reporter.error(format!(
         "{}",
-        "Tailwind CSS is required but not found!".red()
+        "Tailwind CSS is required but not found!"
+            .if_supports_color(Stream::Stderr, |text| text.red())
     ));
     reporter.error(format!(
         "{}",
-        "Tailwind CSS is not installed or not found in node_modules".red()
+        "Tailwind CSS is not installed or not found in node_modules"
+            .if_supports_color(Stream::Stderr, |text| text.red())
+    ));
+    reporter.warn(format!(
+        "{}",
+        "Please install Tailwind CSS first:"
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   npm install -D tailwindcss".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   # or".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   yarn add -D tailwindcss".if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
-    reporter.warn(format!("{}", "Please install Tailwind CSS first:".yellow()));
-    reporter.info(format!("{}", "   npm install -D tailwindcss".dimmed()));
-    reporter.info(format!("{}", "   # or".dimmed()));
-    reporter.info(format!("{}", "   yarn add -D tailwindcss".dimmed()));
-    reporter.info(format!("{}", "   # or".dimmed()));
-    reporter.info(format!("{}", "   pnpm add -D tailwindcss".dimmed()));
-    reporter.info(format!("{}", "   # or".dimmed()));
-    reporter.info(format!("{}", "   bun add -D tailwindcss".dimmed()));
     reporter.info(format!(
         "{}",
-        "Visit https://tailwindcss.com/docs/installation for setup guide".blue()
+        "   # or".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   pnpm add -D tailwindcss".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   # or".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   bun add -D tailwindcss".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "Visit https://tailwindcss.com/docs/installation for setup guide"
+            .if_supports_color(Stream::Stdout, |text| text.blue())
     ));
 }
 
@@ -958,24 +1368,40 @@ fn print_framework_unknown_message(
 ) {
     reporter.error(format!(
         "{}",
-        "Unsupported project structure detected!".red()
+        "Unsupported project structure detected!"
+            .if_supports_color(Stream::Stderr, |text| text.red())
     ));
     reporter.error(format!(
         "{}",
-        "Could not detect a supported React framework".red()
+        "Could not detect a supported React framework"
+            .if_supports_color(Stream::Stderr, |text| text.red())
+    ));
+    reporter.warn(format!(
+        "{}",
+        "nocta-ui supports:".if_supports_color(Stream::Stdout, |text| text.yellow())
     ));
-    reporter.warn(format!("{}", "nocta-ui supports:".yellow()));
     reporter.info(format!(
         "{}",
-        "   • Next.js (App Router or Pages Router)".dimmed()
+        "   • Next.js (App Router or Pages Router)"
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
-    reporter.info(format!("{}", "   • Vite + React".dimmed()));
     reporter.info(format!(
         "{}",
-        "   • React Router 7 (Framework Mode)".dimmed()
+        "   • Vite + React".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   • React Router 7 (Framework Mode)"
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   • TanStack Start".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "Detection details:".if_supports_color(Stream::Stdout, |text| text.blue())
     ));
-    reporter.info(format!("{}", "   • TanStack Start".dimmed()));
-    reporter.info(format!("{}", "Detection details:".blue()));
     reporter.info(format!(
         "{}",
         format!(
@@ -986,7 +1412,7 @@ fn print_framework_unknown_message(
                 "✗"
             }
         )
-        .dimmed()
+        .if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
     reporter.info(format!(
         "{}",
@@ -998,7 +1424,7 @@ fn print_framework_unknown_message(
                 "✗"
             }
         )
-        .dimmed()
+        .if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
     reporter.info(format!(
         "{}",
@@ -1010,32 +1436,109 @@ fn print_framework_unknown_message(
                 detection.details.config_files.join(", ")
             }
         )
-        .dimmed()
+        .if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
     if !detection.details.has_react_dependency {
-        reporter.warn(format!("{}", "Install React first:".yellow()));
-        reporter.info(format!("{}", "   npm install react react-dom".dimmed()));
+        reporter.warn(format!(
+            "{}",
+            "Install React first:".if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
         reporter.info(format!(
             "{}",
-            "   npm install -D @types/react @types/react-dom".dimmed()
+            "   npm install react react-dom"
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+        reporter.info(format!(
+            "{}",
+            "   npm install -D @types/react @types/react-dom"
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
         ));
     } else {
-        reporter.warn(format!("{}", "Set up a supported framework:".yellow()));
-        reporter.info(format!("{}", "   Next.js:".blue()));
-        reporter.info(format!("{}", "     npx create-next-app@latest".dimmed()));
-        reporter.info(format!("{}", "   Vite + React:".blue()));
+        reporter.warn(format!(
+            "{}",
+            "Set up a supported framework:".if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        reporter.info(format!(
+            "{}",
+            "   Next.js:".if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
+        reporter.info(format!(
+            "{}",
+            "     npx create-next-app@latest"
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
         reporter.info(format!(
             "{}",
-            "     npm create vite@latest . -- --template react-ts".dimmed()
+            "   Vite + React:".if_supports_color(Stream::Stdout, |text| text.blue())
         ));
-        reporter.info(format!("{}", "   React Router 7:".blue()));
         reporter.info(format!(
             "{}",
-            "     npx create-react-router@latest".dimmed()
+            "     npm create vite@latest . -- --template react-ts"
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
         ));
-        reporter.info(format!("{}", "   TanStack Start:".blue()));
-        reporter.info(format!("{}", "     npm create tanstack@latest".dimmed()));
+        reporter.info(format!(
+            "{}",
+            "   React Router 7:".if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
+        reporter.info(format!(
+            "{}",
+            "     npx create-react-router@latest"
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+        reporter.info(format!(
+            "{}",
+            "   TanStack Start:".if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
+        reporter.info(format!(
+            "{}",
+            "     npm create tanstack@latest"
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+    }
+}
+
+/// Splits registry requirements into install groups for a workspace of `kind`.
+///
+/// Shared UI workspaces install each package in the scope the registry declares (falling
+/// back to the `SHARED_UI_*` lists for flat requirements). Apps and libraries are the
+/// final consumer, so peer requirements are installed as regular dependencies there.
+fn requirement_install_groups(
+    requirements: &RegistryRequirements,
+    kind: WorkspaceKind,
+) -> Vec<(DependencyScope, BTreeMap<String, String>)> {
+    let mut regular: BTreeMap<String, String> =
+        requirements.dependencies.clone().into_iter().collect();
+    let mut dev: BTreeMap<String, String> =
+        requirements.dev_dependencies.clone().into_iter().collect();
+    let mut peer: BTreeMap<String, String> =
+        requirements.peer_dependencies.clone().into_iter().collect();
+
+    if kind == WorkspaceKind::Ui {
+        if !requirements.is_scoped() {
+            regular.retain(|name, version| {
+                if SHARED_UI_PEER_DEPENDENCIES.contains(&name.as_str()) {
+                    peer.insert(name.clone(), version.clone());
+                    false
+                } else if SHARED_UI_DEV_DEPENDENCIES.contains(&name.as_str()) {
+                    dev.insert(name.clone(), version.clone());
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+    } else {
+        regular.append(&mut peer);
     }
+
+    [
+        (DependencyScope::Peer, peer),
+        (DependencyScope::Dev, dev),
+        (DependencyScope::Regular, regular),
+    ]
+    .into_iter()
+    .filter(|(_, deps)| !deps.is_empty())
+    .collect()
 }
 
 fn print_requirement_issues(
@@ -1045,50 +1548,75 @@ fn print_requirement_issues(
 ) {
     reporter.warn(format!(
         "{}",
-        "Project dependencies are missing or out of date.".yellow()
+        "Project dependencies are missing or out of date."
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
     ));
     if dry_run {
         reporter.info(format!(
             "{}",
-            "[dry-run] They would be installed automatically:".blue()
+            "[dry-run] They would be installed automatically:"
+                .if_supports_color(Stream::Stdout, |text| text.blue())
         ));
     } else {
-        reporter.info(format!("{}", "Installing required versions...".blue()));
+        reporter.info(format!(
+            "{}",
+            "Installing required versions...".if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
     }
     for issue in issues {
         reporter.warn(format!(
             "{}",
-            format!("   {}: requires {}", issue.name, issue.required).yellow()
+            format!("   {}: requires {}", issue.name, issue.required)
+                .if_supports_color(Stream::Stdout, |text| text.yellow())
         ));
         if let Some(installed) = &issue.installed {
             reporter.info(format!(
                 "{}",
-                format!("      installed: {}", installed).dimmed()
+                format!("      installed: {}", installed)
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
             ));
         } else {
-            reporter.info(format!("{}", "      installed: not found".dimmed()));
+            reporter.info(format!(
+                "{}",
+                "      installed: not found"
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
         }
         if let Some(declared) = &issue.declared {
             reporter.info(format!(
                 "{}",
-                format!("      declared: {}", declared).dimmed()
+                format!("      declared: {}", declared)
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
             ));
         }
         match issue.reason {
             RequirementIssueReason::Outdated => {
                 reporter.info(format!(
                     "{}",
-                    "      will be updated to a compatible version".dimmed()
+                    "      will be updated to a compatible version"
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
                 ));
             }
             RequirementIssueReason::Unknown => {
                 reporter.info(format!(
                     "{}",
-                    "      unable to determine installed version, forcing install".dimmed()
+                    "      unable to determine installed version, forcing install"
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
                 ));
             }
             RequirementIssueReason::Missing => {
-                reporter.info(format!("{}", "      will be installed".dimmed()));
+                reporter.info(format!(
+                    "{}",
+                    "      will be installed"
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
+            }
+            RequirementIssueReason::InvalidRange => {
+                reporter.warn(format!(
+                    "{}",
+                    "      the registry declares an invalid version range; report this to the registry maintainers"
+                        .if_supports_color(Stream::Stdout, |text| text.yellow())
+                ));
             }
         }
     }
@@ -1113,28 +1641,55 @@ fn tailwind_major(check: &TailwindCheck) -> Option<u64> {
 }
 
 fn print_tailwind_v4_required(reporter: &ConsoleReporter, check: &TailwindCheck) {
-    reporter.error(format!("{}", "Tailwind CSS v4 is required".red()));
+    reporter.error(format!(
+        "{}",
+        "Tailwind CSS v4 is required".if_supports_color(Stream::Stderr, |text| text.red())
+    ));
     reporter.error(format!(
         "{}",
         format!(
             "Detected Tailwind version that is not v4: {}",
             check.version.clone().unwrap_or_else(|| "unknown".into())
         )
-        .red()
+        .if_supports_color(Stream::Stderr, |text| text.red())
+    ));
+    reporter.warn(format!(
+        "{}",
+        "Please upgrade to Tailwind CSS v4:"
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   npm install -D tailwindcss@latest"
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   # or".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   yarn add -D tailwindcss@latest".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   # or".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   pnpm add -D tailwindcss@latest".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   # or".if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
-    reporter.warn(format!("{}", "Please upgrade to Tailwind CSS v4:".yellow()));
     reporter.info(format!(
         "{}",
-        "   npm install -D tailwindcss@latest".dimmed()
+        "   bun add -D tailwindcss@latest".if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
-    reporter.info(format!("{}", "   # or".dimmed()));
-    reporter.info(format!("{}", "   yarn add -D tailwindcss@latest".dimmed()));
-    reporter.info(format!("{}", "   # or".dimmed()));
-    reporter.info(format!("{}", "   pnpm add -D tailwindcss@latest".dimmed()));
-    reporter.info(format!("{}", "   # or".dimmed()));
-    reporter.info(format!("{}", "   bun add -D tailwindcss@latest".dimmed()));
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn ensure_registry_asset(
     client: &RegistryClient,
     dry_run: bool,
@@ -1142,6 +1697,7 @@ async fn ensure_registry_asset(
     asset_path: &str,
     target_path: &Path,
     created_paths: &mut Vec<PathBuf>,
+    file_changes: &mut Vec<FileChange>,
     label: &str,
 ) -> Result<bool> {
     if file_exists(target_path) {
@@ -1151,7 +1707,7 @@ async fn ensure_registry_asset(
                 "{} already exists - skipping creation",
                 target_path.display()
             )
-            .yellow()
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
         ));
         return Ok(false);
     }
@@ -1159,9 +1715,16 @@ async fn ensure_registry_asset(
     if dry_run {
         reporter.info(format!(
             "{}",
-            format!("[dry-run] Would create {}:", label).blue()
+            format!("[dry-run] Would create {}:", label)
+                .if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
+        reporter.info(format!(
+            "   {}",
+            target_path
+                .display()
+                .to_string()
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
         ));
-        reporter.info(format!("   {}", target_path.display().to_string().dimmed()));
         return Ok(true);
     }
 
@@ -1169,6 +1732,7 @@ async fn ensure_registry_asset(
         .fetch_registry_asset(asset_path)
         .await
         .with_context(|| format!("failed to fetch registry asset {}", asset_path))?;
+    ensure_change_record(target_path, file_changes)?;
     write_file(target_path, &asset)
         .with_context(|| format!("failed to write {}", target_path.display()))?;
     created_paths.push(target_path.to_path_buf());
@@ -1220,28 +1784,30 @@ fn build_config(
     match detection.framework {
         FrameworkKind::NextJs => {
             let app_router = detection.details.app_structure == Some(AppStructure::AppRouter);
+            // Projects using the `src/` directory convention keep everything under `src/`.
+            let root = if detection.details.uses_src_dir {
+                "src/"
+            } else {
+                ""
+            };
             Ok(Config {
-                schema: None,
-                style: "default".into(),
+                style: DEFAULT_STYLE.into(),
                 tailwind: TailwindConfig {
                     css: if app_router {
-                        "app/globals.css".into()
+                        format!("{}app/globals.css", root)
                     } else {
-                        "styles/globals.css".into()
+                        format!("{}styles/globals.css", root)
                     },
                 },
                 aliases: Aliases {
-                    components: "components/ui".into(),
-                    utils: "lib/utils".into(),
+                    components: format!("{}components/ui", root).into(),
+                    utils: format!("{}lib/utils", root).into(),
                 },
-                alias_prefixes: None,
-                exports: None,
-                workspace: None,
+                ..Default::default()
             })
         }
         FrameworkKind::ViteReact => Ok(Config {
-            schema: None,
-            style: "default".into(),
+            style: DEFAULT_STYLE.into(),
             tailwind: TailwindConfig {
                 css: "src/App.css".into(),
             },
@@ -1249,13 +1815,10 @@ fn build_config(
                 components: "src/components/ui".into(),
                 utils: "src/lib/utils".into(),
             },
-            alias_prefixes: None,
-            exports: None,
-            workspace: None,
+            ..Default::default()
         }),
         FrameworkKind::ReactRouter => Ok(Config {
-            schema: None,
-            style: "default".into(),
+            style: DEFAULT_STYLE.into(),
             tailwind: TailwindConfig {
                 css: "app/app.css".into(),
             },
@@ -1263,9 +1826,7 @@ fn build_config(
                 components: "app/components/ui".into(),
                 utils: "app/lib/utils".into(),
             },
-            alias_prefixes: None,
-            exports: None,
-            workspace: None,
+            ..Default::default()
         }),
         FrameworkKind::TanstackStart => {
             let css_candidates = [
@@ -1288,8 +1849,7 @@ fn build_config(
                 .unwrap_or("src/styles.css");
 
             Ok(Config {
-                schema: None,
-                style: "default".into(),
+                style: DEFAULT_STYLE.into(),
                 tailwind: TailwindConfig {
                     css: css_path.into(),
                 },
@@ -1297,9 +1857,7 @@ fn build_config(
                     components: "src/components/ui".into(),
                     utils: "src/lib/utils".into(),
                 },
-                alias_prefixes: None,
-                exports: None,
-                workspace: None,
+                ..Default::default()
             })
         }
         FrameworkKind::Unknown => build_shared_workspace_config(workspace_kind),
@@ -1335,8 +1893,7 @@ fn build_shared_workspace_config(kind: WorkspaceKind) -> Result<Config> {
     };
 
     Ok(Config {
-        schema: None,
-        style: "default".into(),
+        style: DEFAULT_STYLE.into(),
         tailwind: TailwindConfig {
             css: css_path.into(),
         },
@@ -1344,9 +1901,7 @@ fn build_shared_workspace_config(kind: WorkspaceKind) -> Result<Config> {
             components: components_path.into(),
             utils: utils_path.into(),
         },
-        alias_prefixes: None,
-        exports: None,
-        workspace: None,
+        ..Default::default()
     })
 }
 
@@ -1355,23 +1910,34 @@ fn ensure_default_exports_config(config: &mut Config, workspace_kind: WorkspaceK
         return;
     }
 
-    let components_path = config.aliases.components.filesystem_path();
-    let default_barrel = default_components_barrel_path(components_path);
+    let components_barrel = default_barrel_path(config.aliases.components.filesystem_path());
+    let utils_barrel = default_barrel_path(config.aliases.utils.filesystem_path());
     let exports = config.exports.get_or_insert_with(ExportsConfig::default);
 
     match exports.components_mut() {
         Some(target) => {
             if target.barrel.trim().is_empty() {
-                target.barrel = default_barrel;
+                target.barrel = components_barrel;
             }
         }
         None => {
-            exports.components = Some(ExportsTargetConfig::new(default_barrel));
+            exports.components = Some(ExportsTargetConfig::new(components_barrel));
+        }
+    }
+
+    match exports.utils_mut() {
+        Some(target) => {
+            if target.barrel.trim().is_empty() {
+                target.barrel = utils_barrel;
+            }
+        }
+        None => {
+            exports.utils = Some(ExportsTargetConfig::new(utils_barrel));
         }
     }
 }
 
-fn default_components_barrel_path(path: &str) -> String {
+fn default_barrel_path(path: &str) -> String {
     let normalized = path.trim().trim_start_matches("./").trim_start_matches('/');
 
     if normalized.is_empty() {
@@ -1387,6 +1953,116 @@ fn default_components_barrel_path(path: &str) -> String {
     }
 }
 
+/// The style `init` uses without asking: the requested one, which the registry must offer,
+/// `DEFAULT_STYLE` when it offers none, or its only style (its first when `prompts` is
+/// off). `None` means the user should pick.
+fn preselected_style(
+    styles: &[RegistryStyle],
+    requested: Option<&str>,
+    prompts: bool,
+) -> Result<Option<String>> {
+    if let Some(requested) = requested {
+        let known = if styles.is_empty() {
+            requested == DEFAULT_STYLE
+        } else {
+            styles.iter().any(|style| style.name == requested)
+        };
+        if !known {
+            let available: Vec<&str> = if styles.is_empty() {
+                vec![DEFAULT_STYLE]
+            } else {
+                styles.iter().map(|style| style.name.as_str()).collect()
+            };
+            anyhow::bail!(
+                "unknown style `{}` (available: {})",
+                requested,
+                available.join(", ")
+            );
+        }
+        return Ok(Some(requested.to_string()));
+    }
+
+    Ok(match styles {
+        [] => Some(DEFAULT_STYLE.to_string()),
+        [first, ..] if styles.len() == 1 || !prompts => Some(first.name.clone()),
+        _ => None,
+    })
+}
+
+/// Writes the `desired` `(key, target)` pairs into `package.json`'s `exports`, returning
+/// the entries that changed. A string `exports` is replaced when only `"."` is desired and
+/// otherwise becomes the `"."` entry of an object (unless `desired` sets `"."` itself);
+/// object and array entries and other shapes are left untouched.
+fn merge_package_exports(json: &mut Value, desired: &[(String, String)]) -> Vec<(String, String)> {
+    let mut changes: Vec<(String, String)> = Vec::new();
+
+    match json.get_mut("exports") {
+        Some(Value::Object(map)) => {
+            for (key, export_value) in desired {
+                match map.get(key) {
+                    Some(Value::String(current)) if current == export_value => {}
+                    Some(Value::String(_)) | None => {
+                        map.insert(key.clone(), Value::String(export_value.clone()));
+                        changes.push((key.clone(), export_value.clone()));
+                    }
+                    // Respect existing complex shapes and unsupported scalars; do not modify.
+                    Some(_) => {}
+                }
+            }
+        }
+        Some(Value::String(current)) => {
+            let only_root = desired.iter().all(|(key, _)| key == ".");
+            if only_root {
+                let export_value = &desired[0].1;
+                if current != export_value {
+                    json["exports"] = Value::String(export_value.clone());
+                    changes.push((".".into(), export_value.clone()));
+                }
+            } else {
+                // Subpaths need the object form; the string becomes the `"."` entry.
+                let mut map = serde_json::Map::new();
+                map.insert(".".into(), Value::String(current.clone()));
+                for (key, export_value) in desired {
+                    if map.get(key).and_then(Value::as_str) != Some(export_value) {
+                        changes.push((key.clone(), export_value.clone()));
+                    }
+                    map.insert(key.clone(), Value::String(export_value.clone()));
+                }
+                json["exports"] = Value::Object(map);
+            }
+        }
+        Some(_) => {
+            // Unsupported shape; leave untouched.
+        }
+        None => {
+            let mut map = serde_json::Map::new();
+            for (key, export_value) in desired {
+                map.insert(key.clone(), Value::String(export_value.clone()));
+                changes.push((key.clone(), export_value.clone()));
+            }
+            json["exports"] = Value::Object(map);
+        }
+    }
+
+    changes
+}
+
+/// Normalizes a `subpaths` key to the `"."`/`"./…"` form `package.json` exports require.
+/// Keys that leave the package (`../…`) are rejected.
+fn sanitize_export_key(subpath: &str) -> Option<String> {
+    let normalized = subpath.trim().replace('\\', "/");
+    let normalized = normalized.trim_start_matches('/');
+    let key = if normalized == "." || normalized.starts_with("./") {
+        normalized.to_string()
+    } else {
+        format!("./{}", normalized)
+    };
+    if key.split('/').any(|segment| segment == "..") {
+        return None;
+    }
+    Some(key)
+}
+
 fn sanitize_barrel_for_exports(path: &str) -> String {
     let mut normalized = path.trim().replace('\\', "/");
     normalized = normalized.trim_start_matches("./").to_string();
@@ -1402,6 +2078,22 @@ fn sanitize_barrel_for_exports(path: &str) -> String {
     format!("./{}", normalized)
 }
 
+fn print_tailwind_import_missing(reporter: &ConsoleReporter, config: &Config) {
+    reporter.warn(format!(
+        "\n{}",
+        format!(
+            "{} does not import Tailwind; the design tokens will not take effect.",
+            config.tailwind.css
+        )
+        .if_supports_color(Stream::Stdout, |text| text.yellow())
+    ));
+    reporter.info(format!(
+        "{}",
+        "   Add @import \"tailwindcss\"; at the top of the file."
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+}
+
 fn dependencies_managed_in_workspace(workspace: &WorkspaceResolution) -> bool {
     if workspace.config_workspace.kind == WorkspaceKind::App
         && !workspace.config_workspace.linked_workspaces.is_empty()
@@ -1411,6 +2103,7 @@ fn dependencies_managed_in_workspace(workspace: &WorkspaceResolution) -> bool {
     true
 }
 
+#[allow(clippy::too_many_arguments)]
 fn print_init_summary(
     reporter: &ConsoleReporter,
     dry_run: bool,
@@ -1419,16 +2112,23 @@ fn print_init_summary(
     dependencies: &BTreeMap<String, String>,
     dependencies_managed_elsewhere: bool,
     utils_path: Option<&Path>,
-    icons_path: Option<&Path>,
+    icons: Option<(&Path, IconSet)>,
     tokens_added: bool,
+    tokens_skipped: bool,
+    components_only: bool,
     tailwind_is_v4: bool,
+    tailwind_import_missing: bool,
     workspace: &WorkspaceResolution,
 ) {
     reporter.blank();
-    reporter.info(format!("{}", "Configuration created:".green()));
     reporter.info(format!(
         "{}",
-        format!("   nocta.config.json ({})", framework_info).dimmed()
+        "Configuration created:".if_supports_color(Stream::Stdout, |text| text.green())
+    ));
+    reporter.info(format!(
+        "{}",
+        format!("   {} ({})", config_path().display(), framework_info)
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
     reporter.info(format!(
         "{}",
@@ -1437,7 +2137,7 @@ fn print_init_summary(
             workspace_kind_label(workspace.config_workspace.kind),
             workspace.workspace_root_str
         )
-        .dimmed()
+        .if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
     reporter.info(format!(
         "{}",
@@ -1449,19 +2149,32 @@ fn print_init_summary(
                 "single workspace"
             }
         )
-        .dimmed()
+        .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "{}",
+        format!("   Style: {}", config.style)
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
     if let Some(package) = workspace.config_workspace.package_name.as_deref() {
-        reporter.info(format!("{}", format!("   Package: {}", package).dimmed()));
+        reporter.info(format!(
+            "{}",
+            format!("   Package: {}", package)
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
     }
 
     if !workspace.config_workspace.linked_workspaces.is_empty() {
-        reporter.info(format!("{}", "\nLinked workspaces:".blue()));
+        reporter.info(format!(
+            "{}",
+            "\nLinked workspaces:".if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
         for link in &workspace.config_workspace.linked_workspaces {
             let label = link.package_name.as_deref().unwrap_or(&link.root);
             reporter.info(format!(
                 "   {}",
-                format!("{} ({})", label, link.config).dimmed()
+                format!("{} ({})", label, link.config)
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
             ));
         }
     }
@@ -1482,91 +2195,347 @@ fn print_init_summary(
     };
     reporter.info(format!(
         "{}",
-        format!("   Manifest: {} ({})", manifest_display, manifest_action).dimmed()
+        format!("   Manifest: {} ({})", manifest_display, manifest_action)
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
 
-    if dependencies_managed_elsewhere {
+    if components_only {
+        reporter.info(format!(
+            "\n{}",
+            "Skipped (--components-only):".if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        for skipped in [
+            "Dependency installation",
+            "Utility functions and icons scaffolding",
+            "Design tokens",
+            "Tailwind CSS v4 check",
+        ] {
+            reporter.info(format!(
+                "   {}",
+                format!("• {}", skipped).if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+        if !dependencies.is_empty() {
+            reporter.info(format!(
+                "{}",
+                "   Components expect these dependencies:"
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+            for (dep, version) in dependencies {
+                reporter.info(format!(
+                    "   {}",
+                    format!("{}@{}", dep, version)
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
+            }
+        }
+        reporter.info(format!(
+            "{}",
+            format!(
+                "   Components import cn() from {}; provide it yourself.",
+                config.aliases.utils.filesystem_path()
+            )
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+    } else if dependencies_managed_elsewhere {
         reporter.info(format!(
             "\n{}",
-            "Dependencies managed via linked shared UI workspace(s).".blue()
+            "Dependencies managed via linked shared UI workspace(s)."
+                .if_supports_color(Stream::Stdout, |text| text.blue())
         ));
         if !dependencies.is_empty() {
             reporter.info(format!(
                 "{}",
-                "   Ensure the linked workspace includes:".dimmed()
+                "   Ensure the linked workspace includes:"
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
             ));
             for (dep, version) in dependencies {
-                reporter.info(format!("   {}", format!("{}@{}", dep, version).dimmed()));
+                reporter.info(format!(
+                    "   {}",
+                    format!("{}@{}", dep, version)
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
             }
         }
     } else {
         let dep_heading = if dry_run {
-            "[dry-run] Would install dependencies:".blue()
+            "[dry-run] Would install dependencies:"
         } else {
-            "Dependencies installed:".blue()
+            "Dependencies installed:"
         };
-        reporter.info(format!("\n{}", dep_heading));
+        reporter.info(format!(
+            "\n{}",
+            dep_heading.if_supports_color(Stream::Stdout, |text| text.blue())
+        ));
         for (dep, version) in dependencies {
-            reporter.info(format!("   {}", format!("{}@{}", dep, version).dimmed()));
+            reporter.info(format!(
+                "   {}",
+                format!("{}@{}", dep, version)
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
         }
     }
 
     if let Some(path) = utils_path {
-        reporter.info(format!("{}", "\nUtility functions created:".green()));
-        reporter.info(format!("   {}", path.display().to_string().dimmed()));
+        reporter.info(format!(
+            "{}",
+            "\nUtility functions created:".if_supports_color(Stream::Stdout, |text| text.green())
+        ));
+        reporter.info(format!(
+            "   {}",
+            path.display()
+                .to_string()
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
         reporter.info(format!(
             "   {}",
-            "• cn() function for className merging".dimmed()
+            "• cn() function for className merging"
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
         ));
     }
 
-    if let Some(path) = icons_path {
-        reporter.info(format!("{}", "\nIcons component created:".green()));
-        reporter.info(format!("   {}", path.display().to_string().dimmed()));
-        reporter.info(format!("   {}", "• Base Radix Icons mapping".dimmed()));
+    if let Some((path, icon_set)) = icons {
+        reporter.info(format!(
+            "{}",
+            "\nIcons component created:".if_supports_color(Stream::Stdout, |text| text.green())
+        ));
+        reporter.info(format!(
+            "   {}",
+            path.display()
+                .to_string()
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+        reporter.info(format!(
+            "   {}",
+            format!("• {}", icon_set.description())
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
     }
 
     match (tokens_added, dependencies_managed_elsewhere) {
+        _ if components_only => {}
+        _ if tokens_skipped => {
+            reporter.info(format!(
+                "\n{}",
+                "Design tokens skipped (by request)"
+                    .if_supports_color(Stream::Stdout, |text| text.yellow())
+            ));
+        }
         (true, _) => {
             let heading = if dry_run {
-                "[dry-run] Would add color variables:".green()
+                "[dry-run] Would add color variables:"
             } else {
-                "Color variables added:".green()
+                "Color variables added:"
             };
-            reporter.info(format!("\n{}", heading));
-            reporter.info(format!("   {}", config.tailwind.css.as_str().dimmed()));
+            reporter.info(format!(
+                "\n{}",
+                heading.if_supports_color(Stream::Stdout, |text| text.green())
+            ));
+            reporter.info(format!(
+                "   {}",
+                config
+                    .tailwind
+                    .css
+                    .as_str()
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
             reporter.info(format!(
                 "   {}",
-                "• Semantic tokens (background, foreground, primary, border, etc.)".dimmed()
+                "• Semantic tokens (background, foreground, primary, border, etc.)"
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
             ));
         }
         (false, true) => {
             reporter.info(format!(
                 "\n{}",
-                "Design tokens managed in linked shared UI workspace.".blue()
+                "Design tokens managed in linked shared UI workspace."
+                    .if_supports_color(Stream::Stdout, |text| text.blue())
             ));
         }
         (false, false) => {
             reporter.info(format!(
                 "\n{}",
-                "Design tokens skipped (already exist or error occurred)".yellow()
+                "Design tokens skipped (already exist or error occurred)"
+                    .if_supports_color(Stream::Stdout, |text| text.yellow())
             ));
         }
     }
 
     if tailwind_is_v4 {
-        reporter.info(format!("\n{}", "Tailwind v4 detected!".blue()));
         reporter.info(format!(
-            "{}",
-            "   Make sure your CSS file includes @import \"tailwindcss\";".dimmed()
+            "\n{}",
+            "Tailwind v4 detected!".if_supports_color(Stream::Stdout, |text| text.blue())
         ));
     }
 
+    if tailwind_import_missing {
+        print_tailwind_import_missing(reporter, config);
+    }
+
     let final_heading = if dry_run {
-        "[dry-run] You could then add components:".blue()
+        "[dry-run] You could then add components:"
     } else {
-        "You can now add components:".blue()
+        "You can now add components:"
     };
-    reporter.info(format!("\n{}", final_heading));
-    reporter.info(format!("   {}", "npx nocta-ui add button".dimmed()));
+    reporter.info(format!(
+        "\n{}",
+        final_heading.if_supports_color(Stream::Stdout, |text| text.blue())
+    ));
+    reporter.info(format!(
+        "   {}",
+        "npx nocta-ui add button".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn desired(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(key, target)| (key.to_string(), target.to_string()))
+            .collect()
+    }
+
+    fn styles(names: &[&str]) -> Vec<RegistryStyle> {
+        names
+            .iter()
+            .map(|name| RegistryStyle {
+                name: name.to_string(),
+                description: None,
+                css: None,
+                variants: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn requested_style_must_be_offered() {
+        let offered = styles(&["default", "rounded"]);
+        assert_eq!(
+            preselected_style(&offered, Some("rounded"), true).unwrap(),
+            Some("rounded".to_string())
+        );
+        let err = preselected_style(&offered, Some("sharp"), true).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown style `sharp` (available: default, rounded)"
+        );
+    }
+
+    #[test]
+    fn registries_without_styles_only_offer_the_default() {
+        assert_eq!(
+            preselected_style(&[], None, true).unwrap(),
+            Some(DEFAULT_STYLE.to_string())
+        );
+        assert_eq!(
+            preselected_style(&[], Some(DEFAULT_STYLE), true).unwrap(),
+            Some(DEFAULT_STYLE.to_string())
+        );
+        assert!(preselected_style(&[], Some("rounded"), true).is_err());
+    }
+
+    #[test]
+    fn several_styles_prompt_unless_prompts_are_off() {
+        let offered = styles(&["default", "rounded"]);
+        assert_eq!(preselected_style(&offered, None, true).unwrap(), None);
+        assert_eq!(
+            preselected_style(&offered, None, false).unwrap(),
+            Some("default".to_string())
+        );
+        assert_eq!(
+            preselected_style(&styles(&["rounded"]), None, true).unwrap(),
+            Some("rounded".to_string())
+        );
+    }
+
+    #[test]
+    fn export_keys_keep_subpath_form() {
+        assert_eq!(sanitize_export_key("./button").as_deref(), Some("./button"));
+        assert_eq!(sanitize_export_key("button").as_deref(), Some("./button"));
+        assert_eq!(sanitize_export_key("./*").as_deref(), Some("./*"));
+        assert_eq!(sanitize_export_key(".").as_deref(), Some("."));
+        assert_eq!(sanitize_export_key("./").as_deref(), Some("./"));
+        assert_eq!(sanitize_export_key("../outside"), None);
+        assert_eq!(
+            sanitize_barrel_for_exports("src/index.ts"),
+            "./src/index.ts"
+        );
+    }
+
+    #[test]
+    fn missing_exports_become_an_object() {
+        let mut json = json!({ "name": "ui" });
+        let wanted = desired(&[(".", "./src/index.ts"), ("./*", "./src/components/ui/*")]);
+        let changes = merge_package_exports(&mut json, &wanted);
+        assert_eq!(changes, wanted);
+        assert_eq!(
+            json["exports"],
+            json!({ ".": "./src/index.ts", "./*": "./src/components/ui/*" })
+        );
+    }
+
+    #[test]
+    fn string_exports_are_replaced_when_only_the_root_is_desired() {
+        let mut json = json!({ "exports": "./dist/index.js" });
+        let changes = merge_package_exports(&mut json, &desired(&[(".", "./src/index.ts")]));
+        assert_eq!(changes, desired(&[(".", "./src/index.ts")]));
+        assert_eq!(json["exports"], json!("./src/index.ts"));
+
+        assert!(merge_package_exports(&mut json, &desired(&[(".", "./src/index.ts")])).is_empty());
+    }
+
+    #[test]
+    fn string_exports_with_subpaths_point_the_root_at_the_barrel() {
+        let mut json = json!({ "exports": "./dist/index.js" });
+        let wanted = desired(&[(".", "./src/index.ts"), ("./*", "./src/components/ui/*")]);
+        merge_package_exports(&mut json, &wanted);
+        assert_eq!(
+            json["exports"],
+            json!({ ".": "./src/index.ts", "./*": "./src/components/ui/*" })
+        );
+
+        // Without a barrel the string stays as the `"."` entry.
+        let mut json = json!({ "exports": "./dist/index.js" });
+        let changes = merge_package_exports(&mut json, &desired(&[("./*", "./src/*")]));
+        assert_eq!(changes, desired(&[("./*", "./src/*")]));
+        assert_eq!(
+            json["exports"],
+            json!({ ".": "./dist/index.js", "./*": "./src/*" })
+        );
+    }
+
+    #[test]
+    fn object_exports_update_strings_and_keep_complex_entries() {
+        let mut json = json!({
+            "exports": {
+                ".": { "import": "./dist/index.mjs", "types": "./dist/index.d.ts" },
+                "./button": "./old/button.tsx",
+                "./package.json": "./package.json"
+            }
+        });
+        let changes = merge_package_exports(
+            &mut json,
+            &desired(&[(".", "./src/index.ts"), ("./button", "./src/button.tsx")]),
+        );
+        assert_eq!(changes, desired(&[("./button", "./src/button.tsx")]));
+        assert_eq!(
+            json["exports"],
+            json!({
+                ".": { "import": "./dist/index.mjs", "types": "./dist/index.d.ts" },
+                "./button": "./src/button.tsx",
+                "./package.json": "./package.json"
+            })
+        );
+    }
+
+    #[test]
+    fn unsupported_exports_shapes_are_left_alone() {
+        let mut json = json!({ "exports": ["./a.js", "./b.js"] });
+        assert!(merge_package_exports(&mut json, &desired(&[(".", "./src/index.ts")])).is_empty());
+        assert_eq!(json["exports"], json!(["./a.js", "./b.js"]));
+    }
 }
@@ -1,44 +1,177 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
 use clap::Args;
-use owo_colors::OwoColorize;
+use owo_colors::{OwoColorize, Stream, Style};
 
+use crate::commands::add::{
+    build_workspace_context, locate_component_file, normalize_component_content,
+};
 use crate::commands::{CommandOutcome, CommandResult};
+use crate::report::utc_timestamp;
 use crate::reporter::ConsoleReporter;
-use nocta_core::RegistryClient;
+use crate::util::{parse_duration, warn_cache_fallback};
+use nocta_core::config::{config_path, read_config};
+use nocta_core::framework::{FrameworkKind, detect_framework};
+use nocta_core::installed::{mark_modified, scan_installed_components};
+use nocta_core::lockfile::{LOCK_FILE_NAME, read_lockfile_from, registry_version_behind};
+use nocta_core::types::{Config, Registry};
+use nocta_core::{RegistryClient, RegistrySet};
 
 #[derive(Args, Debug, Clone, Default)]
-pub struct ListArgs {}
+pub struct ListArgs {
+    /// Named registry (from nocta.config.json) to list components from.
+    #[arg(long = "registry", value_name = "name")]
+    pub registry: Option<String>,
+    /// Only show components already present in this project.
+    #[arg(long)]
+    pub installed: bool,
+    /// Show each component's internal dependencies as a tree.
+    #[arg(long, conflicts_with = "installed")]
+    pub tree: bool,
+    /// Only show installed components locked to an older registry version than the current one.
+    #[arg(long, conflicts_with_all = ["installed", "tree"])]
+    pub updatable: bool,
+    /// Only show installed files changed within this window, e.g. 12h or 7d (implies `--installed`).
+    #[arg(
+        long,
+        value_name = "duration",
+        value_parser = parse_duration,
+        conflicts_with_all = ["tree", "updatable"]
+    )]
+    pub since: Option<Duration>,
+    /// Only show components with files for the framework detected in this project.
+    #[arg(long, conflicts_with_all = ["installed", "updatable", "since"])]
+    pub compatible: bool,
+}
 
 pub async fn run(
     client: &RegistryClient,
     reporter: &ConsoleReporter,
-    _args: ListArgs,
+    args: ListArgs,
 ) -> CommandResult {
-    let registry = client.fetch_registry().await?;
+    warn_cache_fallback(reporter);
+    let config = read_config()?;
+    let registries = RegistrySet::try_new(
+        client,
+        config
+            .as_ref()
+            .and_then(|config| config.registries.as_ref()),
+    )
+    .context("failed to set up the configured registries")?;
+    if args.updatable {
+        // The lockfile lives in the primary workspace root, which may not be the current directory.
+        let lock_path = match config.as_ref() {
+            Some(config) => build_workspace_context(config, &detect_framework())?.lock_path(),
+            None => PathBuf::from(LOCK_FILE_NAME),
+        };
+        return print_updatable(&registries, reporter, &lock_path, args.registry.as_deref()).await;
+    }
 
-    reporter.info(format!(
-        "{}\n",
-        "Available nocta-ui components:".blue().bold()
-    ));
+    let registry_client = registries.client(args.registry.as_deref())?;
+    let registry = registry_client.fetch_registry().await?;
+
+    if args.installed || args.since.is_some() {
+        let Some(config) = config.as_ref() else {
+            reporter.error(format!(
+                "{}",
+                format!("{} not found", config_path().display())
+                    .if_supports_color(Stream::Stderr, |text| text.red())
+            ));
+            reporter.warn(format!(
+                "{}",
+                "Run \"npx nocta-ui init\" first"
+                    .if_supports_color(Stream::Stdout, |text| text.yellow())
+            ));
+            return Ok(CommandOutcome::NoOp);
+        };
+        let cutoff = args
+            .since
+            .map(|since| SystemTime::now().checked_sub(since).unwrap_or(UNIX_EPOCH));
+        return print_installed(registry_client, reporter, config, &registry, cutoff).await;
+    }
+
+    if args.tree {
+        return Ok(print_dependency_tree(reporter, &registry));
+    }
+
+    let framework = if args.compatible {
+        compatible_framework(reporter, config.is_some())
+    } else {
+        None
+    };
+
+    match framework {
+        Some(framework) => reporter.info(format!(
+            "{}\n",
+            format!("Available nocta-ui components for {}:", framework.as_str())
+                .if_supports_color(Stream::Stdout, |text| text.blue())
+                .if_supports_color(Stream::Stdout, |text| text.bold())
+        )),
+        None => reporter.info(format!(
+            "{}\n",
+            "Available nocta-ui components:".if_supports_color(Stream::Stdout, |text| text
+                .style(Style::new().blue().bold()))
+        )),
+    }
 
     let mut categories: Vec<_> = registry.categories.iter().collect();
     categories.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
 
     for (_, category) in categories {
-        reporter.info(format!("{}", category.name.yellow().bold()));
-        reporter.info(format!("  {}\n", category.description.clone().dimmed()));
-
-        let mut components: Vec<_> = category.components.iter().collect();
+        let mut components: Vec<_> = category
+            .components
+            .iter()
+            .filter(|name| {
+                framework.is_none_or(|framework| {
+                    registry
+                        .components
+                        .get(*name)
+                        .is_some_and(|component| component.supports_framework(framework))
+                })
+            })
+            .collect();
+        if components.is_empty() && framework.is_some() {
+            continue;
+        }
         components.sort();
 
+        reporter.info(format!(
+            "{}",
+            category.name.if_supports_color(Stream::Stdout, |text| text
+                .style(Style::new().yellow().bold()))
+        ));
+        reporter.info(format!(
+            "  {}\n",
+            category
+                .description
+                .clone()
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+
         for component_name in components {
             if let Some(component) = registry.components.get(component_name) {
-                reporter.info(format!("  {}", component.name.to_lowercase().green()));
-                reporter.info(format!("    {}", component.description.clone().dimmed()));
+                reporter.info(format!(
+                    "  {}",
+                    component
+                        .name
+                        .to_lowercase()
+                        .if_supports_color(Stream::Stdout, |text| text.green())
+                ));
+                reporter.info(format!(
+                    "    {}",
+                    component
+                        .description
+                        .clone()
+                        .if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
 
                 if !component.variants.is_empty() {
                     reporter.info(format!(
                         "  {} {}",
-                        "Variants:".blue(),
+                        "Variants:".if_supports_color(Stream::Stdout, |text| text.blue()),
                         component.variants.join(", ")
                     ));
                 }
@@ -46,7 +179,7 @@ pub async fn run(
                 if !component.sizes.is_empty() {
                     reporter.info(format!(
                         "  {} {}",
-                        "Sizes:".blue(),
+                        "Sizes:".if_supports_color(Stream::Stdout, |text| text.blue()),
                         component.sizes.join(", ")
                     ));
                 }
@@ -56,15 +189,372 @@ pub async fn run(
         }
     }
 
-    reporter.info(format!("{}", "Add a component:".blue()));
+    reporter.info(format!(
+        "{}",
+        "Add a component:".if_supports_color(Stream::Stdout, |text| text.blue())
+    ));
+    match args.registry.as_deref() {
+        Some(name) => reporter.info(format!(
+            "  {}",
+            format!("npx nocta-ui add @{}/<component-name>", name)
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        )),
+        None => reporter.info(format!(
+            "  {}",
+            "npx nocta-ui add <component-name>"
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        )),
+    }
+
+    reporter.info(format!(
+        "\n{}",
+        "Examples:".if_supports_color(Stream::Stdout, |text| text.blue())
+    ));
+    reporter.info(format!(
+        "  {}",
+        "npx nocta-ui add button".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "  {}",
+        "npx nocta-ui add card".if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+
+    Ok(CommandOutcome::Completed)
+}
+
+fn print_dependency_tree(reporter: &ConsoleReporter, registry: &Registry) -> CommandOutcome {
+    reporter.info(format!(
+        "{}\n",
+        "nocta-ui component dependencies:".if_supports_color(Stream::Stdout, |text| text
+            .style(Style::new().blue().bold()))
+    ));
+
+    let mut slugs: Vec<_> = registry.components.keys().collect();
+    slugs.sort();
+
+    for slug in slugs {
+        let mut pulled_in = HashSet::new();
+        collect_internal_dependencies(registry, slug, &mut pulled_in);
+        pulled_in.remove(slug.as_str());
+
+        if pulled_in.is_empty() {
+            reporter.info(format!(
+                "  {}",
+                slug.if_supports_color(Stream::Stdout, |text| text.green())
+            ));
+        } else {
+            reporter.info(format!(
+                "  {} {}",
+                slug.if_supports_color(Stream::Stdout, |text| text.green()),
+                format!(
+                    "(+{} component{})",
+                    pulled_in.len(),
+                    if pulled_in.len() == 1 { "" } else { "s" }
+                )
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+
+        let mut path = vec![slug.as_str()];
+        print_dependency_branches(reporter, registry, slug, "  ", &mut path);
+    }
+
+    CommandOutcome::Completed
+}
+
+fn collect_internal_dependencies<'a>(
+    registry: &'a Registry,
+    slug: &'a str,
+    seen: &mut HashSet<&'a str>,
+) {
+    if !seen.insert(slug) {
+        return;
+    }
+    if let Some(component) = registry.components.get(slug) {
+        for dependency in &component.internal_dependencies {
+            collect_internal_dependencies(registry, dependency, seen);
+        }
+    }
+}
+
+/// Prints the dependencies of `slug` below it; `path` holds the ancestors so cycles
+/// are reported once instead of recursing forever.
+fn print_dependency_branches<'a>(
+    reporter: &ConsoleReporter,
+    registry: &'a Registry,
+    slug: &str,
+    indent: &str,
+    path: &mut Vec<&'a str>,
+) {
+    let Some(component) = registry.components.get(slug) else {
+        return;
+    };
+
+    let count = component.internal_dependencies.len();
+    for (index, dependency) in component.internal_dependencies.iter().enumerate() {
+        let last = index + 1 == count;
+        let branch = if last { "└── " } else { "├── " };
+        let dependency = dependency.as_str();
+
+        if path.contains(&dependency) {
+            reporter.info(format!(
+                "{}{}{} {}",
+                indent,
+                branch.if_supports_color(Stream::Stdout, |text| text.dimmed()),
+                dependency,
+                "(cycle)".if_supports_color(Stream::Stdout, |text| text.yellow())
+            ));
+            continue;
+        }
+        if !registry.components.contains_key(dependency) {
+            reporter.info(format!(
+                "{}{}{} {}",
+                indent,
+                branch.if_supports_color(Stream::Stdout, |text| text.dimmed()),
+                dependency,
+                "(missing from registry)".if_supports_color(Stream::Stdout, |text| text.red())
+            ));
+            continue;
+        }
+
+        reporter.info(format!(
+            "{}{}{}",
+            indent,
+            branch.if_supports_color(Stream::Stdout, |text| text.dimmed()),
+            dependency
+        ));
+        let child_indent = format!("{}{}", indent, if last { "    " } else { "│   " });
+        path.push(dependency);
+        print_dependency_branches(reporter, registry, dependency, &child_indent, path);
+        path.pop();
+    }
+}
+
+/// Lists components from the lockfile at `lock_path` whose locked registry version is
+/// behind the registry's current version, limited to `registry` when given.
+async fn print_updatable(
+    registries: &RegistrySet<'_>,
+    reporter: &ConsoleReporter,
+    lock_path: &Path,
+    registry: Option<&str>,
+) -> CommandResult {
+    let Some(lockfile) = read_lockfile_from(lock_path)
+        .with_context(|| format!("failed to read {}", lock_path.display()))?
+    else {
+        reporter.warn(format!(
+            "{}",
+            format!(
+                "{} not found; install components with `add` first",
+                lock_path.display()
+            )
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        return Ok(CommandOutcome::NoOp);
+    };
+
+    let mut current_versions: HashMap<Option<String>, String> = HashMap::new();
+    let mut updatable = Vec::new();
+    for (key, locked) in &lockfile.components {
+        if registry.is_some() && locked.registry.as_deref() != registry {
+            continue;
+        }
+        if !current_versions.contains_key(&locked.registry) {
+            let fetched = registries
+                .client(locked.registry.as_deref())?
+                .fetch_registry()
+                .await?;
+            current_versions.insert(locked.registry.clone(), fetched.version);
+        }
+        let current = &current_versions[&locked.registry];
+        if registry_version_behind(&locked.registry_version, current) {
+            updatable.push((key, locked, current.clone()));
+        }
+    }
+
+    if updatable.is_empty() {
+        reporter.info(format!(
+            "{}",
+            "All installed components are up to date."
+                .if_supports_color(Stream::Stdout, |text| text.green())
+        ));
+        return Ok(CommandOutcome::NoOp);
+    }
+
+    reporter.info(format!(
+        "{}\n",
+        "Components with registry updates:".if_supports_color(Stream::Stdout, |text| text
+            .style(Style::new().blue().bold()))
+    ));
+    for (key, locked, current) in &updatable {
+        reporter.info(format!(
+            "  {} {}",
+            key.if_supports_color(Stream::Stdout, |text| text.green()),
+            format!("{} -> {}", locked.registry_version, current)
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+    }
+
+    let keys: Vec<&str> = updatable.iter().map(|(key, _, _)| key.as_str()).collect();
+    reporter.info(format!(
+        "\n{}",
+        "Update them:".if_supports_color(Stream::Stdout, |text| text.blue())
+    ));
     reporter.info(format!(
         "  {}",
-        "npx nocta-ui add <component-name>".dimmed()
+        format!("npx nocta-ui add {}", keys.join(" "))
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
     ));
 
-    reporter.info(format!("\n{}", "Examples:".blue()));
-    reporter.info(format!("  {}", "npx nocta-ui add button".dimmed()));
-    reporter.info(format!("  {}", "npx nocta-ui add card".dimmed()));
+    Ok(CommandOutcome::Completed)
+}
+
+async fn print_installed(
+    client: &RegistryClient,
+    reporter: &ConsoleReporter,
+    config: &Config,
+    registry: &Registry,
+    cutoff: Option<SystemTime>,
+) -> CommandResult {
+    let context = build_workspace_context(config, &detect_framework())?;
+
+    let mut located_files = HashMap::new();
+    let mut installed = scan_installed_components(registry, |slug, file| {
+        let located = locate_component_file(&context, slug, file)?;
+        located_files.insert(
+            located.absolute_path.clone(),
+            (located.handle, located.display_path),
+        );
+        Ok::<_, anyhow::Error>(located.absolute_path)
+    })?;
+    if let Some(cutoff) = cutoff {
+        for component in &mut installed {
+            component
+                .files
+                .retain(|file| file.modified_at.is_some_and(|time| time >= cutoff));
+        }
+        installed.retain(|component| !component.files.is_empty());
+    }
+    // Files recorded in the lockfile are compared with their recorded hash; only the rest
+    // are fetched from the registry.
+    let lock_path = context.lock_path();
+    let recorded: HashMap<String, String> = read_lockfile_from(&lock_path)
+        .with_context(|| format!("failed to read {}", lock_path.display()))?
+        .map(|lockfile| {
+            lockfile
+                .components
+                .into_values()
+                .flat_map(|component| component.files)
+                .map(|file| (file.path, file.hash))
+                .collect()
+        })
+        .unwrap_or_default();
+    mark_modified(
+        client,
+        &mut installed,
+        |file| {
+            let (_, display_path) = &located_files[&file.path];
+            recorded
+                .get(&context.lock_entry_path(display_path))
+                .cloned()
+        },
+        |file, content| {
+            let (handle, _) = located_files[&file.path];
+            normalize_component_content(content, handle, &file.registry_path)
+        },
+    )
+    .await?;
+
+    if installed.is_empty() {
+        let message = match cutoff {
+            Some(cutoff) => format!(
+                "No nocta-ui component files changed since {}.",
+                utc_timestamp(cutoff)
+            ),
+            None => "No nocta-ui components found in this project.".into(),
+        };
+        reporter.warn(format!(
+            "{}",
+            message.if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        return Ok(CommandOutcome::NoOp);
+    }
+
+    let heading = match cutoff {
+        Some(cutoff) => format!(
+            "Installed nocta-ui components changed since {}:",
+            utc_timestamp(cutoff)
+        ),
+        None => "Installed nocta-ui components:".into(),
+    };
+    reporter.info(format!(
+        "{}\n",
+        heading.if_supports_color(Stream::Stdout, |text| text
+            .style(Style::new().blue().bold()))
+    ));
+    for component in installed {
+        if component.is_modified() {
+            reporter.info(format!(
+                "  {} {}",
+                component
+                    .name
+                    .to_lowercase()
+                    .if_supports_color(Stream::Stdout, |text| text.green()),
+                "(modified)".if_supports_color(Stream::Stdout, |text| text.yellow())
+            ));
+        } else {
+            reporter.info(format!(
+                "  {}",
+                component
+                    .name
+                    .to_lowercase()
+                    .if_supports_color(Stream::Stdout, |text| text.green())
+            ));
+        }
+        for file in component.files {
+            let (_, display_path) = &located_files[&file.path];
+            let path = display_path.display().to_string();
+            if file.modified {
+                reporter.info(format!(
+                    "    {} {}",
+                    path.if_supports_color(Stream::Stdout, |text| text.dimmed()),
+                    "modified".if_supports_color(Stream::Stdout, |text| text.yellow())
+                ));
+            } else {
+                reporter.info(format!(
+                    "    {}",
+                    path.if_supports_color(Stream::Stdout, |text| text.dimmed())
+                ));
+            }
+        }
+    }
 
     Ok(CommandOutcome::Completed)
 }
+
+/// Framework `list --compatible` filters by, or `None` (with a note) when there is no
+/// initialized project or the framework cannot be detected.
+fn compatible_framework(reporter: &ConsoleReporter, has_config: bool) -> Option<FrameworkKind> {
+    if !has_config {
+        reporter.warn(format!(
+            "{}",
+            format!(
+                "{} not found; showing all components",
+                config_path().display()
+            )
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        return None;
+    }
+
+    match detect_framework().framework {
+        FrameworkKind::Unknown => {
+            reporter.warn(format!(
+                "{}",
+                "Could not detect the project's framework; showing all components"
+                    .if_supports_color(Stream::Stdout, |text| text.yellow())
+            ));
+            None
+        }
+        framework => Some(framework),
+    }
+}
@@ -1,5 +1,7 @@
 pub mod add;
 pub mod cache;
+pub mod config;
+pub mod exports;
 pub mod init;
 pub mod list;
 
@@ -9,6 +11,8 @@ use anyhow::Result;
 pub enum CommandOutcome {
     Completed,
     NoOp,
+    /// Finished, but some work failed and was already reported; exits nonzero.
+    PartialFailure,
 }
 
 pub type CommandResult = Result<CommandOutcome>;
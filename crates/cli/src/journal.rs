@@ -0,0 +1,204 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use owo_colors::{OwoColorize, Stream};
+
+use crate::reporter::ConsoleReporter;
+
+/// A file `add` or `init` is about to write, with what it held before (`None` if it did
+/// not exist) so a failed run can put it back.
+#[derive(Clone)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub previous_contents: Option<Vec<u8>>,
+}
+
+/// Snapshots `path` before its first write in this run; later writes keep the original.
+pub fn ensure_change_record(path: &Path, changes: &mut Vec<FileChange>) -> Result<()> {
+    if changes.iter().any(|change| change.path == path) {
+        return Ok(());
+    }
+
+    let previous_contents = if path.exists() {
+        Some(fs::read(path).with_context(|| format!("failed to snapshot {}", path.display()))?)
+    } else {
+        None
+    };
+
+    changes.push(FileChange {
+        path: path.to_path_buf(),
+        previous_contents,
+    });
+
+    Ok(())
+}
+
+/// What `rollback_file_changes` did to a single path.
+pub enum RollbackAction {
+    Restored { path: PathBuf, bytes: usize },
+    Removed(PathBuf),
+}
+
+impl RollbackAction {
+    pub fn describe(&self) -> String {
+        let relative = |path: &Path| {
+            std::env::current_dir()
+                .ok()
+                .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf))
+                .unwrap_or_else(|| path.to_path_buf())
+                .display()
+                .to_string()
+        };
+        match self {
+            RollbackAction::Restored { path, bytes } => format!(
+                "restored {} {} to {}",
+                bytes,
+                if *bytes == 1 { "byte" } else { "bytes" },
+                relative(path)
+            ),
+            RollbackAction::Removed(path) => format!("removed {}", relative(path)),
+        }
+    }
+}
+
+/// Undoes `changes` newest first. Actions completed before a failure are returned alongside
+/// the error so they can still be reported.
+pub fn rollback_file_changes(
+    changes: &[FileChange],
+) -> std::result::Result<Vec<RollbackAction>, (Vec<RollbackAction>, anyhow::Error)> {
+    let mut actions = Vec::new();
+    for change in changes.iter().rev() {
+        match undo_file_change(change) {
+            Ok(Some(action)) => actions.push(action),
+            Ok(None) => {}
+            Err(err) => return Err((actions, err)),
+        }
+    }
+    Ok(actions)
+}
+
+fn undo_file_change(change: &FileChange) -> Result<Option<RollbackAction>> {
+    match &change.previous_contents {
+        Some(contents) => {
+            if fs::read(&change.path).is_ok_and(|current| &current == contents) {
+                return Ok(None);
+            }
+            if let Some(parent) = change.path.parent()
+                && !parent.as_os_str().is_empty()
+            {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to recreate {}", parent.display()))?;
+            }
+            fs::write(&change.path, contents)
+                .with_context(|| format!("failed to restore {}", change.path.display()))?;
+            Ok(Some(RollbackAction::Restored {
+                path: change.path.clone(),
+                bytes: contents.len(),
+            }))
+        }
+        None => {
+            if change.path.exists() {
+                fs::remove_file(&change.path)
+                    .with_context(|| format!("failed to remove {}", change.path.display()))?;
+                Ok(Some(RollbackAction::Removed(change.path.clone())))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Rolls back `changes` and prints each restored or removed file.
+pub fn rollback_and_report(reporter: &ConsoleReporter, changes: &[FileChange]) {
+    let (actions, error) = match rollback_file_changes(changes) {
+        Ok(actions) => (actions, None),
+        Err((actions, err)) => (actions, Some(err)),
+    };
+
+    if !actions.is_empty() {
+        reporter.warn(format!(
+            "{}",
+            "Rolled back written files:".if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+        for action in &actions {
+            reporter.info(format!(
+                "   {}",
+                action
+                    .describe()
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+            ));
+        }
+    }
+    if let Some(err) = error {
+        reporter.error(format!(
+            "{}",
+            format!("Failed to roll back written files: {}", err)
+                .if_supports_color(Stream::Stderr, |text| text.red())
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn created_files_are_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("components/ui/button.tsx");
+        let mut changes = Vec::new();
+        ensure_change_record(&path, &mut changes).unwrap();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "new").unwrap();
+
+        let actions = rollback_file_changes(&changes)
+            .map_err(|(_, err)| err)
+            .unwrap();
+
+        assert!(!path.exists());
+        assert!(matches!(&actions[..], [RollbackAction::Removed(removed)] if removed == &path));
+    }
+
+    #[test]
+    fn edited_files_are_restored_to_their_first_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("package.json");
+        fs::write(&path, "original").unwrap();
+        let mut changes = Vec::new();
+        ensure_change_record(&path, &mut changes).unwrap();
+        fs::write(&path, "first edit").unwrap();
+        ensure_change_record(&path, &mut changes).unwrap();
+        fs::write(&path, "second edit").unwrap();
+
+        let actions = rollback_file_changes(&changes)
+            .map_err(|(_, err)| err)
+            .unwrap();
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "original");
+        assert!(matches!(
+            &actions[..],
+            [RollbackAction::Restored { path: restored, bytes: 8 }] if restored == &path
+        ));
+    }
+
+    #[test]
+    fn unchanged_files_are_left_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("globals.css");
+        fs::write(&path, "@import \"tailwindcss\";").unwrap();
+        let mut changes = Vec::new();
+        ensure_change_record(&path, &mut changes).unwrap();
+
+        let actions = rollback_file_changes(&changes)
+            .map_err(|(_, err)| err)
+            .unwrap();
+
+        assert!(actions.is_empty());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "@import \"tailwindcss\";"
+        );
+    }
+}
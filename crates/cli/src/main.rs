@@ -1,15 +1,28 @@
 mod commands;
+mod journal;
+mod report;
 mod reporter;
 mod util;
 
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
 use std::process;
+use std::time::Duration;
 
-use clap::{Parser, Subcommand};
+use anyhow::Context;
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 
-use commands::{CommandOutcome, CommandResult, add, cache, init, list};
+use commands::config::{RegistrySettings, RegistryUrlSource};
+use commands::{CommandOutcome, CommandResult, add, cache, config, exports, init, list};
 use nocta_core::RegistryClient;
+use nocta_core::config::{read_config, set_config_path};
 use nocta_core::constants::registry::DEFAULT_BASE_URL;
-use reporter::ConsoleReporter;
+use nocta_core::registry::{CacheStats, RegistryOptions};
+use nocta_core::types::CacheConfig;
+use nocta_core::workspace::{find_repo_root, load_workspace_manifest};
+use owo_colors::{OwoColorize, Stream};
+use reporter::{ConsoleReporter, OutputFormat};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -23,6 +36,63 @@ struct Cli {
     #[arg(long, global = true, env = "NOCTA_REGISTRY_URL")]
     registry_url: Option<String>,
 
+    /// Fetch components.json from this URL instead of the registry base URL (env: NOCTA_COMPONENTS_MANIFEST_URL)
+    #[arg(
+        long = "components-manifest",
+        global = true,
+        value_name = "url",
+        env = "NOCTA_COMPONENTS_MANIFEST_URL"
+    )]
+    components_manifest: Option<String>,
+
+    /// Accept invalid TLS certificates from registries (DANGEROUS: disables certificate checks)
+    #[arg(long, global = true)]
+    registry_insecure: bool,
+
+    /// Ignore fresh cache entries and revalidate every registry request
+    #[arg(long, global = true, alias = "no-cache")]
+    force_refresh: bool,
+
+    /// Serve cached registry data even when stale; only fetch on a cache miss
+    #[arg(long, global = true, conflicts_with = "force_refresh")]
+    prefer_offline: bool,
+
+    /// Print registry cache hit/miss statistics after the command finishes
+    #[arg(long, global = true)]
+    stats: bool,
+
+    /// Accept cached registry data up to this age, e.g. 30s, 5m, 1h (overrides TTL envs)
+    #[arg(long, global = true, value_name = "duration", value_parser = util::parse_duration)]
+    max_age: Option<Duration>,
+
+    /// Serve the registry from a pre-fetched JSON bundle instead of the network (`-` reads stdin)
+    #[arg(long, global = true, value_name = "path")]
+    registry_bundle: Option<PathBuf>,
+
+    /// Read component files from this directory when it has a file at the same registry path
+    #[arg(long, global = true, value_name = "dir")]
+    local: Option<PathBuf>,
+
+    /// Append a JSON record of every registry request (headers, status, cache decision) to this file
+    #[arg(long, global = true, value_name = "path")]
+    trace_file: Option<PathBuf>,
+
+    /// Use this config file instead of nocta.config.json in the current directory
+    #[arg(long, global = true, value_name = "path")]
+    config: Option<PathBuf>,
+
+    /// Disable colored output (also honored via the NO_COLOR env)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Print progress as plain lines instead of animated spinners (default when CI is set)
+    #[arg(long, global = true)]
+    plain_progress: bool,
+
+    /// Stdout format: human messages, or `add` progress events as a JSON array or JSON lines
+    #[arg(long, global = true, value_enum, value_name = "format", default_value_t = OutputFormat::Human)]
+    output_format: OutputFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -33,13 +103,24 @@ enum Commands {
     Add(add::AddArgs),
     List(list::ListArgs),
     Cache(cache::CacheArgs),
+    Config(config::ConfigArgs),
+    Exports(exports::ExportsArgs),
 }
 
 #[tokio::main]
 async fn main() {
     let reporter = ConsoleReporter::new();
-    match run(&reporter).await {
+    let result = run(&reporter).await;
+    if let Err(err) = &result {
+        reporter.event(
+            "error",
+            serde_json::json!({ "message": format!("{:#}", err) }),
+        );
+    }
+    reporter.flush_events();
+    match result {
         Ok(CommandOutcome::Completed) | Ok(CommandOutcome::NoOp) => {}
+        Ok(CommandOutcome::PartialFailure) => process::exit(1),
         Err(err) => {
             reporter.error(format!("Error: {:#}", err));
             process::exit(1);
@@ -48,16 +129,154 @@ async fn main() {
 }
 
 async fn run(reporter: &ConsoleReporter) -> CommandResult {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let task_runner = reporter::task_runner_detected();
+    // Structured formats print human-readable messages to stderr, styled for stdout.
+    let styled_stderr = cli.output_format != OutputFormat::Human && !io::stderr().is_terminal();
+    if cli.no_color || reporter::no_color_requested() || task_runner.is_some() || styled_stderr {
+        reporter::set_color_enabled(false);
+    }
+    if cli.plain_progress || reporter::ci_detected() || task_runner.is_some() {
+        reporter::set_plain_progress(true);
+    }
+    if task_runner.is_some() {
+        reporter::set_prompts_enabled(false);
+    }
+    if let Some(path) = cli.config.as_ref() {
+        set_config_path(path);
+    }
+    reporter::set_output_format(cli.output_format);
+
+    // Commands re-read the config themselves and report its errors; here it only supplies
+    // the default registry, cache TTLs and the style used to pick component file variants.
+    let project_config = read_config().ok().flatten();
+    let config_registry = project_config
+        .as_ref()
+        .and_then(|config| config.registry.clone())
+        .filter(|url| !url.trim().is_empty());
+    let manifest_registry = if cli.registry_url.is_none() && config_registry.is_none() {
+        manifest_registry_url()
+    } else {
+        None
+    };
+    let registry_url = cli
+        .registry_url
+        .as_deref()
+        .or(config_registry.as_deref())
+        .or(manifest_registry.as_deref())
+        .unwrap_or(DEFAULT_BASE_URL);
+    let registry_source = match matches.value_source("registry_url") {
+        Some(ValueSource::CommandLine) => RegistryUrlSource::Flag,
+        Some(ValueSource::EnvVariable) => RegistryUrlSource::Env,
+        _ if config_registry.is_some() => RegistryUrlSource::Config,
+        _ if manifest_registry.is_some() => RegistryUrlSource::Manifest,
+        _ => RegistryUrlSource::Default,
+    };
 
-    let registry_url = cli.registry_url.as_deref().unwrap_or(DEFAULT_BASE_URL);
+    if let Some(dir) = cli.local.as_ref()
+        && !dir.is_dir()
+    {
+        anyhow::bail!("--local directory {} does not exist", dir.display());
+    }
+
+    if cli.registry_insecure {
+        reporter.warn(format!(
+            "{}",
+            "Warning: --registry-insecure disables TLS certificate verification for registry requests."
+                .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+    }
 
-    let client = RegistryClient::new(registry_url);
+    let cache_config = project_config
+        .as_ref()
+        .and_then(|config| config.cache.clone());
+    let options = RegistryOptions {
+        force_refresh: cli.force_refresh,
+        prefer_offline: cli.prefer_offline,
+        max_age: cli.max_age,
+        registry_ttl: cache_config.as_ref().and_then(CacheConfig::registry_ttl),
+        asset_ttl: cache_config.as_ref().and_then(CacheConfig::asset_ttl),
+        local_dir: cli.local,
+        accept_invalid_certs: cli.registry_insecure,
+        trace_file: cli.trace_file,
+        style: project_config.map(|config| config.style),
+        components_manifest_url: cli.components_manifest,
+    };
+    let client = match cli.registry_bundle.as_deref() {
+        Some(path) => RegistryClient::try_from_bundle(
+            registry_url,
+            util::read_registry_bundle(path)?,
+            options,
+        ),
+        None => RegistryClient::try_with_options(registry_url, options),
+    }
+    .context("failed to set up the registry client")?;
 
-    match cli.command {
+    let show_stats = cli.stats;
+    let result = match cli.command {
         Commands::Init(args) => init::run(&client, reporter, args).await,
         Commands::Add(args) => add::run(&client, reporter, args).await,
         Commands::List(args) => list::run(&client, reporter, args).await,
         Commands::Cache(args) => cache::run(reporter, args).await,
+        Commands::Exports(args) => exports::run(reporter, args).await,
+        Commands::Config(args) => {
+            let settings = RegistrySettings {
+                url: registry_url,
+                source: registry_source,
+            };
+            config::run(settings, reporter, args).await
+        }
+    };
+
+    if show_stats {
+        print_cache_stats(reporter, client.cache_stats());
     }
+
+    result
+}
+
+/// Registry pinned in the repo's `nocta.workspace.json`, if any. An unreadable manifest is
+/// left for the commands that load it to report.
+fn manifest_registry_url() -> Option<String> {
+    let current_dir = std::env::current_dir().ok()?;
+    let repo_root = find_repo_root(&current_dir)?;
+    load_workspace_manifest(&repo_root)
+        .ok()
+        .flatten()
+        .and_then(|manifest| manifest.registry)
+        .filter(|url| !url.trim().is_empty())
+}
+
+fn print_cache_stats(reporter: &ConsoleReporter, stats: CacheStats) {
+    reporter.blank();
+    reporter.info(format!(
+        "{}",
+        "Registry cache statistics:".if_supports_color(Stream::Stdout, |text| text.blue())
+    ));
+    reporter.info(format!(
+        "   {}",
+        format!("Cache hits: {}", stats.hits)
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "   {}",
+        format!("Network fetches: {}", stats.network)
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "   {}",
+        format!("Revalidated (304): {}", stats.revalidated)
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "   {}",
+        format!("Stale fallbacks: {}", stats.stale_fallbacks)
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    reporter.info(format!(
+        "   {}",
+        format!("Total requests: {}", stats.total())
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
 }
@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use nocta_core::deps::DependencyScope;
+use serde_json::{Map, Value, json};
+
+/// A dependency an `add`/`init` run installed (or would install on a dry run).
+#[derive(Debug, Clone)]
+pub struct DependencyRecord {
+    pub workspace: String,
+    pub name: String,
+    pub requested: String,
+    /// Version found in `node_modules` after installing; `None` on dry runs.
+    pub resolved: Option<String>,
+    pub scope: DependencyScope,
+}
+
+impl DependencyRecord {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "workspace": self.workspace,
+            "name": self.name,
+            "requested": self.requested,
+            "resolved": self.resolved,
+            "scope": match self.scope {
+                DependencyScope::Regular => "dependencies",
+                DependencyScope::Dev => "devDependencies",
+                DependencyScope::Peer => "peerDependencies",
+            },
+        })
+    }
+}
+
+/// Writes the JSON report for `command` to `path`, stamping it with the command name,
+/// dry-run flag and current UTC time ahead of the command-specific `body` fields.
+pub fn write_report(path: &Path, command: &str, dry_run: bool, body: Value) -> Result<()> {
+    let mut report = Map::new();
+    report.insert("command".into(), json!(command));
+    report.insert("timestamp".into(), json!(utc_timestamp(SystemTime::now())));
+    report.insert("dryRun".into(), json!(dry_run));
+    if let Value::Object(fields) = body {
+        report.extend(fields);
+    }
+
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(&Value::Object(report))?;
+    fs::write(path, json + "\n")
+        .with_context(|| format!("failed to write report {}", path.display()))
+}
+
+/// Formats `time` as an RFC 3339 UTC timestamp, e.g. `2024-05-01T12:30:00Z`.
+pub fn utc_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil-from-days conversion (Howard Hinnant's algorithm).
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
@@ -1,3 +1,105 @@
+use std::io::{self, IsTerminal};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use clap::ValueEnum;
+use serde_json::{Map, Value, json};
+
+static COLOR_ENABLED: AtomicBool = AtomicBool::new(true);
+static PLAIN_PROGRESS: AtomicBool = AtomicBool::new(false);
+static PROMPTS_ENABLED: AtomicBool = AtomicBool::new(true);
+static OUTPUT_FORMAT: AtomicU8 = AtomicU8::new(OutputFormat::Human as u8);
+/// Events buffered for `--output-format json`, printed by `flush_events`.
+static EVENTS: Mutex<Vec<Value>> = Mutex::new(Vec::new());
+
+/// Enables or disables colored output for every reporter and spinner in this process.
+/// Styles are applied through `if_supports_color`: disabling forces them off, while
+/// enabling leaves the per-stream terminal detection in charge.
+pub fn set_color_enabled(enabled: bool) {
+    COLOR_ENABLED.store(enabled, Ordering::Relaxed);
+    if enabled {
+        owo_colors::unset_override();
+    } else {
+        owo_colors::set_override(false);
+    }
+}
+
+pub fn color_enabled() -> bool {
+    COLOR_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Replaces animated spinners with one line per progress message for every spinner in this process.
+pub fn set_plain_progress(enabled: bool) {
+    PLAIN_PROGRESS.store(enabled, Ordering::Relaxed);
+}
+
+pub fn plain_progress() -> bool {
+    PLAIN_PROGRESS.load(Ordering::Relaxed)
+}
+
+/// How command output is written to stdout.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OutputFormat {
+    /// Human-readable messages.
+    #[default]
+    Human,
+    /// A single JSON array of every progress event, printed when the command finishes.
+    Json,
+    /// One JSON progress event per line, printed as it happens.
+    Ndjson,
+}
+
+/// Selects the output format for every reporter in this process. Structured formats move
+/// human-readable messages to stderr so stdout only carries JSON.
+pub fn set_output_format(format: OutputFormat) {
+    OUTPUT_FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+pub fn output_format() -> OutputFormat {
+    match OUTPUT_FORMAT.load(Ordering::Relaxed) {
+        value if value == OutputFormat::Json as u8 => OutputFormat::Json,
+        value if value == OutputFormat::Ndjson as u8 => OutputFormat::Ndjson,
+        _ => OutputFormat::Human,
+    }
+}
+
+/// Whether the `CI` variable set by most CI providers marks this run as automated.
+pub fn ci_detected() -> bool {
+    std::env::var("CI").is_ok_and(|value| {
+        let value = value.trim();
+        !value.is_empty() && value != "0" && !value.eq_ignore_ascii_case("false")
+    })
+}
+
+/// Disables interactive prompts; commands fall back to their default-safe answers.
+pub fn set_prompts_enabled(enabled: bool) {
+    PROMPTS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether prompts can be shown: stdin is a terminal and prompts were not disabled.
+pub fn prompts_enabled() -> bool {
+    PROMPTS_ENABLED.load(Ordering::Relaxed) && io::stdin().is_terminal()
+}
+
+/// Names the monorepo task runner that launched this process, if any, based on the
+/// variables Turborepo and Nx set for every task they run.
+pub fn task_runner_detected() -> Option<&'static str> {
+    let set = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+    if set("TURBO_HASH") || set("TURBO_INVOCATION_DIR") {
+        Some("Turborepo")
+    } else if set("NX_TASK_TARGET_PROJECT") || set("NX_TASK_HASH") {
+        Some("Nx")
+    } else {
+        None
+    }
+}
+
+/// Whether the `NO_COLOR` convention (https://no-color.org) asks for plain output.
+pub fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
 pub struct ConsoleReporter;
 
 impl ConsoleReporter {
@@ -6,18 +108,63 @@ impl ConsoleReporter {
     }
 
     pub fn info<S: AsRef<str>>(&self, message: S) {
-        println!("{}", message.as_ref());
+        if output_format() == OutputFormat::Human {
+            println!("{}", message.as_ref());
+        } else {
+            eprintln!("{}", message.as_ref());
+        }
     }
 
     pub fn warn<S: AsRef<str>>(&self, message: S) {
-        println!("{}", message.as_ref());
+        self.info(message);
     }
 
     pub fn error<S: AsRef<str>>(&self, message: S) {
         eprintln!("{}", message.as_ref());
     }
 
+    /// Prints a progress line to stderr, keeping stdout free for command output.
+    pub fn progress<S: AsRef<str>>(&self, message: S) {
+        eprintln!("{}", message.as_ref());
+    }
+
     pub fn blank(&self) {
-        println!();
+        self.info("");
+    }
+
+    /// Emits a structured progress event, e.g. `event("fileWritten", json!({ "path": .. }))`.
+    /// Ignored in human output; `fields` must be a JSON object.
+    pub fn event(&self, name: &str, fields: Value) {
+        let format = output_format();
+        if format == OutputFormat::Human {
+            return;
+        }
+
+        let mut event = Map::new();
+        event.insert("event".into(), json!(name));
+        if let Value::Object(fields) = fields {
+            event.extend(fields);
+        }
+        let event = Value::Object(event);
+
+        if format == OutputFormat::Ndjson {
+            println!("{}", event);
+        } else if let Ok(mut events) = EVENTS.lock() {
+            events.push(event);
+        }
+    }
+
+    /// Prints the events buffered for `--output-format json` as one JSON array.
+    pub fn flush_events(&self) {
+        if output_format() != OutputFormat::Json {
+            return;
+        }
+        let events = EVENTS
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default();
+        if let Ok(json) = serde_json::to_string_pretty(&Value::Array(events)) {
+            println!("{}", json);
+        }
     }
 }
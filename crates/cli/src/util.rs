@@ -1,10 +1,66 @@
-use std::path::{Path, PathBuf};
+use std::cell::RefCell;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
+use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
+use nocta_core::RegistryBundle;
+use nocta_core::cache::{self, CacheDirSource};
+use nocta_core::deps::DependencyInstallPlan;
+use owo_colors::{OwoColorize, Stream};
+
+use crate::reporter::{ConsoleReporter, color_enabled, plain_progress};
 
 pub fn canonicalize_path(path: &Path) -> PathBuf {
-    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+    if let Ok(resolved) = std::fs::canonicalize(path) {
+        return resolved;
+    }
+
+    // Paths that do not exist yet are resolved through their deepest existing
+    // ancestor so they still share a prefix with other canonical paths.
+    let normalized = normalize_path_lexically(path);
+    let mut existing = normalized.clone();
+    let mut missing = Vec::new();
+    while !existing.exists() {
+        match existing.file_name() {
+            Some(name) => {
+                missing.push(name.to_os_string());
+                existing.pop();
+            }
+            None => return normalized,
+        }
+    }
+
+    let mut resolved = std::fs::canonicalize(&existing).unwrap_or(existing);
+    for segment in missing.iter().rev() {
+        resolved.push(segment);
+    }
+    resolved
+}
+
+/// Resolves `.` and `..` segments without touching the filesystem, keeping symlinks intact.
+pub fn normalize_path_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) {
+                    normalized.pop();
+                } else {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
 }
 
 pub fn normalize_relative_path(path: &Path) -> String {
@@ -32,14 +88,270 @@ pub fn normalize_relative_path_buf(path: PathBuf) -> String {
     normalize_relative_path(&path)
 }
 
-pub fn create_spinner(message: impl Into<String>) -> ProgressBar {
+/// Progress indicator for long-running steps: an animated `indicatif` spinner, or one
+/// stderr line per distinct message when plain progress is enabled (`--plain-progress` or `CI`).
+pub struct Spinner {
+    bar: Option<ProgressBar>,
+    last_message: RefCell<String>,
+}
+
+impl Spinner {
+    pub fn set_message(&self, message: impl Into<String>) {
+        let message = message.into();
+        match &self.bar {
+            Some(bar) => bar.set_message(message),
+            None => {
+                if *self.last_message.borrow() != message {
+                    ConsoleReporter::new().progress(&message);
+                    self.last_message.replace(message);
+                }
+            }
+        }
+    }
+
+    pub fn finish_and_clear(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+
+    pub fn finish_with_message(&self, message: impl Into<String>) {
+        match &self.bar {
+            Some(bar) => bar.finish_with_message(message.into()),
+            None => ConsoleReporter::new().progress(message.into()),
+        }
+    }
+
+    /// Turns the spinner into a determinate bar counting `total` files, advanced with
+    /// [`Spinner::inc`]; `None` switches back to the plain spinner. No-op in plain mode.
+    pub fn set_total(&self, total: Option<u64>) {
+        let Some(bar) = &self.bar else {
+            return;
+        };
+        match total {
+            Some(total) => {
+                bar.set_style(counter_style());
+                bar.set_length(total);
+                bar.set_position(0);
+            }
+            None => bar.set_style(spinner_style()),
+        }
+    }
+
+    pub fn inc(&self) {
+        if let Some(bar) = &self.bar {
+            bar.inc(1);
+        }
+    }
+
+    /// Hides the spinner while `f` prints, so its output is not interleaved with ticks.
+    pub fn suspend<F: FnOnce() -> R, R>(&self, f: F) -> R {
+        match &self.bar {
+            Some(bar) => bar.suspend(f),
+            None => f(),
+        }
+    }
+}
+
+pub fn create_spinner(message: impl Into<String>) -> Spinner {
+    if plain_progress() {
+        let spinner = Spinner {
+            bar: None,
+            last_message: RefCell::new(String::new()),
+        };
+        spinner.set_message(message);
+        return spinner;
+    }
+
     let pb = ProgressBar::new_spinner();
-    pb.set_style(
-        ProgressStyle::with_template("{spinner:.blue} {msg}")
-            .unwrap()
-            .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
-    );
+    pb.set_style(spinner_style());
     pb.enable_steady_tick(Duration::from_millis(80));
     pb.set_message(message.into());
-    pb
+    Spinner {
+        bar: Some(pb),
+        last_message: RefCell::new(String::new()),
+    }
+}
+
+fn spinner_style() -> ProgressStyle {
+    let template = if color_enabled() {
+        "{spinner:.blue} {msg}"
+    } else {
+        "{spinner} {msg}"
+    };
+    ProgressStyle::with_template(template)
+        .unwrap()
+        .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏")
+}
+
+fn counter_style() -> ProgressStyle {
+    let template = if color_enabled() {
+        "{spinner:.blue} {msg} [{bar:24.blue/dim}] {pos} of {len} files"
+    } else {
+        "{spinner} {msg} [{bar:24}] {pos} of {len} files"
+    };
+    ProgressStyle::with_template(template)
+        .unwrap()
+        .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏")
+        .progress_chars("=> ")
+}
+
+/// Reads a registry bundle from `path`, or from stdin when `path` is `-`.
+pub fn read_registry_bundle(path: &Path) -> Result<RegistryBundle> {
+    let text = if path == Path::new("-") {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .context("failed to read registry bundle from stdin")?;
+        text
+    } else {
+        fs::read_to_string(path)
+            .with_context(|| format!("failed to read registry bundle {}", path.display()))?
+    };
+
+    RegistryBundle::parse(&text).with_context(|| match path.to_str() {
+        Some("-") => "invalid registry bundle on stdin".to_string(),
+        _ => format!("invalid registry bundle {}", path.display()),
+    })
+}
+
+/// Refuses to run `command` from inside a `node_modules` directory, where repo root and
+/// framework detection would target a dependency instead of the project.
+pub fn ensure_outside_node_modules(command: &str) -> Result<()> {
+    let current_dir = std::env::current_dir().context("failed to read current directory")?;
+    if let Some(node_modules) = current_dir
+        .ancestors()
+        .find(|dir| dir.file_name().is_some_and(|name| name == "node_modules"))
+    {
+        let project_root = node_modules.parent().unwrap_or(node_modules);
+        anyhow::bail!(
+            "`nocta-ui {}` cannot run inside node_modules ({}); run it from your project root, e.g. {}",
+            command,
+            current_dir.display(),
+            project_root.display()
+        );
+    }
+    Ok(())
+}
+
+/// Parses human durations such as `90s`, `5m`, `1h` or `2d`; bare numbers are seconds.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let trimmed = input.trim();
+    let split = trimmed
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(trimmed.len());
+    let (digits, unit) = trimmed.split_at(split);
+    let value: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid duration `{}` (expected e.g. 30s, 5m, 1h)", input))?;
+
+    let seconds = match unit.trim() {
+        "ms" => return Ok(Duration::from_millis(value)),
+        "" | "s" | "sec" | "secs" => value,
+        "m" | "min" | "mins" => value.saturating_mul(60),
+        "h" | "hr" | "hrs" => value.saturating_mul(60 * 60),
+        "d" | "day" | "days" => value.saturating_mul(24 * 60 * 60),
+        other => {
+            return Err(format!(
+                "unknown duration unit `{}` (use ms, s, m, h or d)",
+                other
+            ));
+        }
+    };
+
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Prints the command a dry run would execute, warning when its package manager is not
+/// on `PATH` so the real run does not fail at spawn time.
+pub fn print_planned_install(reporter: &ConsoleReporter, plan: &DependencyInstallPlan) {
+    reporter.info(format!(
+        "{}",
+        format!("   Command: {}", plan.command_line().join(" "))
+            .if_supports_color(Stream::Stdout, |text| text.dimmed())
+    ));
+    if let Some(npmrc) = plan.npmrc.as_deref() {
+        reporter.info(format!(
+            "{}",
+            format!("   Registry settings: {}", npmrc.display())
+                .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        ));
+    }
+    if !plan.program_available() {
+        reporter.warn(format!(
+            "{}",
+            format!(
+                "   Warning: `{}` was not found on PATH; install {} or this command will fail",
+                plan.program,
+                plan.package_manager.as_str()
+            )
+            .if_supports_color(Stream::Stdout, |text| text.yellow())
+        ));
+    }
+}
+
+static CACHE_FALLBACK_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Warns, once per run, when the platform cache directory was unavailable and a fallback
+/// location is used.
+pub fn warn_cache_fallback(reporter: &ConsoleReporter) {
+    let source = cache::cache_dir_source();
+    if !source.is_fallback() || CACHE_FALLBACK_WARNED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    let detail = match source {
+        CacheDirSource::Temp => {
+            "the system temp directory is used, which may be cleared at any time"
+        }
+        _ => "a fallback location is used",
+    };
+    reporter.warn(format!(
+        "{}",
+        format!(
+            "Warning: the platform cache directory could not be determined, so {}. Set NOCTA_CACHE_DIR to choose a persistent location.",
+            detail
+        )
+        .if_supports_color(Stream::Stdout, |text| text.yellow())
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_path_lexically_resolves_dot_segments() {
+        assert_eq!(
+            normalize_path_lexically(Path::new("apps/./web/../ui")),
+            PathBuf::from("apps/ui")
+        );
+        assert_eq!(
+            normalize_path_lexically(Path::new("../packages/ui")),
+            PathBuf::from("../packages/ui")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn canonicalize_path_follows_symlinked_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = fs::canonicalize(dir.path()).unwrap();
+        fs::create_dir_all(root.join("real-ui")).unwrap();
+        fs::create_dir_all(root.join("packages")).unwrap();
+        std::os::unix::fs::symlink("../real-ui", root.join("packages/ui")).unwrap();
+
+        assert_eq!(
+            canonicalize_path(&root.join("packages/ui")),
+            root.join("real-ui")
+        );
+        // Files not written yet resolve through their deepest existing ancestor.
+        assert_eq!(
+            canonicalize_path(&root.join("packages/ui/components/button.tsx")),
+            root.join("real-ui/components/button.tsx")
+        );
+        assert_eq!(
+            canonicalize_path(&root.join("apps/../packages/ui/index.ts")),
+            root.join("real-ui/index.ts")
+        );
+    }
 }
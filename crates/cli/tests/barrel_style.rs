@@ -0,0 +1,83 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+/// A Next.js app whose components barrel already holds a hand-written export in single
+/// quotes without semicolons.
+fn app_with_barrel(barrel: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let app = dir.path();
+    common::write_next_app(app, ".");
+    let config_path = app.join("nocta.config.json");
+    let config = fs::read_to_string(&config_path).unwrap().replacen(
+        "\"style\": \"default\",",
+        "\"style\": \"default\",\n  \"exports\": { \"components\": { \"barrel\": \"components/ui/index.ts\" } },",
+        1,
+    );
+    fs::write(&config_path, config).unwrap();
+    fs::create_dir_all(app.join("components/ui")).unwrap();
+    fs::write(app.join("components/ui/index.ts"), barrel).unwrap();
+    dir
+}
+
+fn add_button(app: &Path) {
+    let output = common::nocta(app)
+        .args(["add", "button", "--yes"])
+        .output()
+        .expect("run nocta-ui");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn generated_exports_follow_the_barrel_and_do_not_churn() {
+    let project = app_with_barrel("export { Card } from './card'\n");
+    let barrel = project.path().join("components/ui/index.ts");
+
+    add_button(project.path());
+    let first = fs::read_to_string(&barrel).unwrap();
+    assert!(
+        first.contains("export { Button } from './button'\n"),
+        "{first}"
+    );
+    assert!(!first.contains('"'), "{first}");
+    assert!(
+        first.starts_with("export { Card } from './card'\n"),
+        "{first}"
+    );
+
+    add_button(project.path());
+    assert_eq!(fs::read_to_string(&barrel).unwrap(), first);
+}
+
+#[test]
+fn deduped_barrels_survive_another_add() {
+    let project = app_with_barrel("export { Card } from './card'\nexport { Card } from './card'\n");
+    let barrel = project.path().join("components/ui/index.ts");
+    add_button(project.path());
+
+    let output = common::nocta(project.path())
+        .args(["exports", "dedupe"])
+        .output()
+        .expect("run nocta-ui");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let deduped = fs::read_to_string(&barrel).unwrap();
+    assert_eq!(deduped.matches("export { Card }").count(), 1, "{deduped}");
+    assert!(
+        deduped.contains("export { Button } from './button'"),
+        "{deduped}"
+    );
+
+    add_button(project.path());
+    assert_eq!(fs::read_to_string(&barrel).unwrap(), deduped);
+}
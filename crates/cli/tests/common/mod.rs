@@ -0,0 +1,64 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// A single-component registry bundle: `button` writes `components/button.tsx`.
+pub const BUNDLE: &str = r#"{
+  "registry": {
+    "name": "nocta",
+    "version": "1.0.0",
+    "components": {
+      "button": {
+        "name": "Button",
+        "description": "A button",
+        "category": "form",
+        "files": [{ "name": "button.tsx", "path": "components/button.tsx", "type": "component" }],
+        "dependencies": {},
+        "exports": ["Button"]
+      }
+    },
+    "categories": {},
+    "requirements": {}
+  },
+  "components": { "components/button.tsx": "ZXhwb3J0IGNvbnN0IEJ1dHRvbiA9IDE7Cg==" }
+}"#;
+
+/// Writes a Next.js app whose config places the workspace at `workspace_root`.
+pub fn write_next_app(dir: &Path, workspace_root: &str) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(
+        dir.join("package.json"),
+        r#"{"name":"app","dependencies":{"next":"15.0.0","react":"19.0.0","tailwindcss":"^4.1.0"}}"#,
+    )
+    .unwrap();
+    fs::write(dir.join("next.config.js"), "module.exports = {};\n").unwrap();
+    fs::write(
+        dir.join("nocta.config.json"),
+        format!(
+            r#"{{
+  "style": "default",
+  "tailwind": {{ "css": "styles/globals.css" }},
+  "aliases": {{ "components": "components/ui", "utils": "lib/utils" }},
+  "workspace": {{ "kind": "app", "root": "{}" }}
+}}"#,
+            workspace_root
+        ),
+    )
+    .unwrap();
+    fs::write(dir.join("bundle.json"), BUNDLE).unwrap();
+}
+
+/// `nocta-ui` run from `dir` against `bundle.json`, with no terminal and no task runner.
+pub fn nocta(dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_nocta-ui"));
+    command
+        .current_dir(dir)
+        .env("NOCTA_CACHE_DIR", dir.join(".cache"))
+        .env_remove("TURBO_HASH")
+        .env_remove("NX_TASK_TARGET_PROJECT")
+        .args(["--no-color", "--registry-bundle", "bundle.json"])
+        .stdin(Stdio::null());
+    command
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn registry(slug: &str, file: &str) -> String {
+    format!(
+        r#"{{
+  "name": "{slug}s",
+  "version": "1.0.0",
+  "components": {{
+    "{slug}": {{
+      "name": "{slug}",
+      "description": "",
+      "category": "test",
+      "files": [{{ "name": "{file}", "path": "components/{file}", "type": "component" }}],
+      "dependencies": {{}},
+      "exports": []
+    }}
+  }},
+  "categories": {{}},
+  "requirements": {{}}
+}}"#
+    )
+}
+
+/// Serves a default registry at `/`, its manifest override at `/cdn/` and a named
+/// registry at `/acme/`, recording every requested path.
+fn serve() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&requests);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                match stream.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => request.extend_from_slice(&buffer[..read]),
+                }
+            }
+            let request = String::from_utf8_lossy(&request);
+            let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+            let body = match path.as_str() {
+                "/registry.json" => Some(registry("button", "button.tsx")),
+                "/components.json" => Some(
+                    r#"{"components/button.tsx":"ZXhwb3J0IGNvbnN0IFNoYXJlZCA9IDE7Cg=="}"#.into(),
+                ),
+                "/cdn/components.json" => Some(
+                    r#"{"components/button.tsx":"ZXhwb3J0IGNvbnN0IE92ZXJyaWRlID0gMTsK"}"#.into(),
+                ),
+                "/acme/registry.json" => Some(registry("card", "card.tsx")),
+                "/acme/components.json" => {
+                    Some(r#"{"components/card.tsx":"ZXhwb3J0IGNvbnN0IENhcmQgPSAxOwo="}"#.into())
+                }
+                _ => None,
+            };
+            log.lock().unwrap().push(path);
+            let response = match body {
+                Some(body) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                ),
+                None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .into(),
+            };
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    (base, requests)
+}
+
+#[test]
+fn manifest_override_applies_to_the_default_registry_only() {
+    let (base, requests) = serve();
+    let dir = tempfile::tempdir().expect("tempdir");
+    let app = dir.path();
+    common::write_next_app(app, ".");
+    let config_path = app.join("nocta.config.json");
+    let config = fs::read_to_string(&config_path).unwrap().replacen(
+        "\"style\": \"default\",",
+        &format!("\"style\": \"default\",\n  \"registries\": {{ \"acme\": \"{base}/acme\" }},"),
+        1,
+    );
+    fs::write(&config_path, config).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_nocta-ui"))
+        .current_dir(app)
+        .env("NOCTA_CACHE_DIR", app.join(".cache"))
+        .env("NO_PROXY", "127.0.0.1")
+        .env_remove("HTTP_PROXY")
+        .env_remove("http_proxy")
+        .env_remove("ALL_PROXY")
+        .env_remove("all_proxy")
+        .env_remove("TURBO_HASH")
+        .env_remove("NX_TASK_TARGET_PROJECT")
+        .env_remove("NOCTA_REGISTRY_URL")
+        .env_remove("NOCTA_COMPONENTS_MANIFEST_URL")
+        .args(["--no-color", "--registry-url", &base])
+        .args([
+            "--components-manifest",
+            &format!("{base}/cdn/components.json"),
+        ])
+        .args(["add", "button", "@acme/card", "--yes"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("run nocta-ui");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_eq!(
+        fs::read_to_string(app.join("components/ui/button.tsx")).unwrap(),
+        "export const Override = 1;\n"
+    );
+    assert_eq!(
+        fs::read_to_string(app.join("components/ui/card.tsx")).unwrap(),
+        "export const Card = 1;\n"
+    );
+    let requests = requests.lock().unwrap();
+    assert!(
+        requests.contains(&"/acme/components.json".to_string()),
+        "{requests:?}"
+    );
+    assert!(
+        !requests.contains(&"/components.json".to_string()),
+        "{requests:?}"
+    );
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use std::fs;
+
+use serde_json::Value;
+
+#[test]
+fn dry_run_report_hashes_the_content_that_would_be_written() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let app = dir.path();
+    common::write_next_app(app, ".");
+    let button = app.join("components/ui/button.tsx");
+    fs::create_dir_all(button.parent().unwrap()).unwrap();
+    fs::write(&button, "export const Button = 0;\n").unwrap();
+
+    let output = common::nocta(app)
+        .args([
+            "add",
+            "button",
+            "--yes",
+            "--dry-run",
+            "--report",
+            "report.json",
+        ])
+        .output()
+        .expect("run nocta-ui");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_eq!(
+        fs::read_to_string(&button).unwrap(),
+        "export const Button = 0;\n"
+    );
+    let report: Value =
+        serde_json::from_str(&fs::read_to_string(app.join("report.json")).unwrap()).unwrap();
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1, "{report}");
+    assert_eq!(
+        files[0]["hash"],
+        nocta_core::lockfile::component_content_hash("export const Button = 1;\n")
+    );
+}
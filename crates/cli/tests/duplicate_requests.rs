@@ -0,0 +1,26 @@
+mod common;
+
+#[test]
+fn repeated_components_are_counted_once() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    common::write_next_app(dir.path(), ".");
+
+    let output = common::nocta(dir.path())
+        .args(["add", "button", "Button", "button"])
+        .output()
+        .expect("run nocta-ui");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let all = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(all.contains("Installing button:"), "output: {}", all);
+    assert_eq!(all.matches("requested)").count(), 1, "output: {}", all);
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use std::fs;
+
+#[test]
+fn exports_diff_shows_the_managed_block_next_to_hand_written_exports() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let app = dir.path();
+    common::write_next_app(app, ".");
+    let config_path = app.join("nocta.config.json");
+    let config = fs::read_to_string(&config_path).unwrap().replacen(
+        "\"style\": \"default\",",
+        "\"style\": \"default\",\n  \"exports\": { \"components\": { \"barrel\": \"components/ui/index.ts\" } },",
+        1,
+    );
+    fs::write(&config_path, config).unwrap();
+    let barrel = app.join("components/ui/index.ts");
+    fs::create_dir_all(barrel.parent().unwrap()).unwrap();
+    let hand_written = "export { Card } from \"./card\";\nexport * from \"./theme\";\n";
+    fs::write(&barrel, hand_written).unwrap();
+
+    let output = common::nocta(app)
+        .args(["add", "button", "--yes", "--dry-run", "--exports-diff"])
+        .output()
+        .expect("run nocta-ui");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let diff: Vec<&str> = stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix("      "))
+        .collect();
+    assert!(diff.contains(&"--- a/components/ui/index.ts"), "{stdout}");
+    assert!(diff.contains(&"+++ b/components/ui/index.ts"), "{stdout}");
+    assert!(
+        diff.contains(&"+export { Button } from \"./button\";"),
+        "{stdout}"
+    );
+    assert!(
+        diff.contains(&" export { Card } from \"./card\";"),
+        "{stdout}"
+    );
+    assert!(
+        !diff
+            .iter()
+            .any(|line| line.starts_with('-') && !line.starts_with("---")),
+        "hand-written exports must not be removed: {stdout}"
+    );
+    assert_eq!(fs::read_to_string(&barrel).unwrap(), hand_written);
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use std::fs;
+
+/// `button` imports `button-group` through `@/` and a relative path, plus a relative
+/// import outside the components directory.
+const BUNDLE: &str = r#"{
+  "registry": {
+    "name": "nocta",
+    "version": "1.0.0",
+    "components": {
+      "button": {
+        "name": "Button",
+        "description": "A button",
+        "category": "form",
+        "files": [
+          { "name": "button.tsx", "path": "components/ui/button.tsx", "type": "component" },
+          { "name": "button-group.tsx", "path": "components/ui/button-group.tsx", "type": "component" }
+        ],
+        "dependencies": {},
+        "exports": ["Button"]
+      }
+    },
+    "categories": {},
+    "requirements": {}
+  },
+  "components": {
+    "components/ui/button.tsx": "aW1wb3J0IHsgR3JvdXAgfSBmcm9tICIuL2J1dHRvbi1ncm91cCI7CmltcG9ydCB7IEdyb3VwIGFzIEFsaWFzZWQgfSBmcm9tICJAL2NvbXBvbmVudHMvdWkvYnV0dG9uLWdyb3VwIjsKaW1wb3J0IHsgY24gfSBmcm9tICIuLi8uLi9saWIvdXRpbHMiOwpjb25zdCBsYWJlbCA9ICIuL2J1dHRvbi1ncm91cCI7CmV4cG9ydCBjb25zdCBCdXR0b24gPSAxOwo=",
+    "components/ui/button-group.tsx": "ZXhwb3J0IGNvbnN0IEdyb3VwID0gMTsK"
+  }
+}"#;
+
+#[test]
+fn relative_and_alias_imports_of_components_follow_filename_case() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let app = dir.path();
+    common::write_next_app(app, ".");
+    let config_path = app.join("nocta.config.json");
+    let config = fs::read_to_string(&config_path).unwrap().replacen(
+        "\"style\": \"default\",",
+        "\"style\": \"default\",\n  \"components\": { \"filenameCase\": \"pascal\" },",
+        1,
+    );
+    fs::write(&config_path, config).unwrap();
+    fs::write(app.join("bundle.json"), BUNDLE).unwrap();
+
+    let output = common::nocta(app)
+        .args(["add", "button"])
+        .output()
+        .expect("run nocta-ui");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(app.join("components/ui/ButtonGroup.tsx").exists());
+    let button = fs::read_to_string(app.join("components/ui/Button.tsx")).unwrap();
+    assert!(
+        button.contains(r#"import { Group } from "./ButtonGroup";"#),
+        "{button}"
+    );
+    assert!(
+        button.contains(r#"import { Group as Aliased } from "@/components/ui/ButtonGroup";"#),
+        "{button}"
+    );
+    // Relative imports outside the components directory and plain strings are untouched.
+    assert!(
+        button.contains(r#"import { cn } from "../../lib/utils";"#),
+        "{button}"
+    );
+    assert!(
+        button.contains(r#"const label = "./button-group";"#),
+        "{button}"
+    );
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+/// `button` installs fine, `card`'s file is missing from the bundle, `dialog` depends on
+/// `card`, and `popover` depends on a component the registry does not have.
+const BUNDLE: &str = r#"{
+  "registry": {
+    "name": "nocta",
+    "version": "1.0.0",
+    "components": {
+      "button": {
+        "name": "Button", "description": "", "category": "form",
+        "files": [{ "name": "button.tsx", "path": "components/button.tsx", "type": "component" }]
+      },
+      "card": {
+        "name": "Card", "description": "", "category": "layout",
+        "files": [{ "name": "card.tsx", "path": "components/card.tsx", "type": "component" }]
+      },
+      "dialog": {
+        "name": "Dialog", "description": "", "category": "overlay",
+        "files": [{ "name": "dialog.tsx", "path": "components/dialog.tsx", "type": "component" }],
+        "internalDependencies": ["card"]
+      },
+      "popover": {
+        "name": "Popover", "description": "", "category": "overlay",
+        "files": [{ "name": "popover.tsx", "path": "components/popover.tsx", "type": "component" }],
+        "internalDependencies": ["ghost"]
+      }
+    },
+    "categories": {}
+  },
+  "components": {
+    "components/button.tsx": "ZXhwb3J0IGNvbnN0IEJ1dHRvbiA9IDE7Cg==",
+    "components/dialog.tsx": "ZXhwb3J0IGNvbnN0IEJ1dHRvbiA9IDE7Cg==",
+    "components/popover.tsx": "ZXhwb3J0IGNvbnN0IEJ1dHRvbiA9IDE7Cg=="
+  }
+}"#;
+
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    common::write_next_app(dir.path(), ".");
+    fs::write(dir.path().join("bundle.json"), BUNDLE).unwrap();
+    dir
+}
+
+fn add(root: &Path, args: &[&str]) -> (Output, String) {
+    let output = common::nocta(root)
+        .arg("add")
+        .args(args)
+        .arg("--keep-going")
+        .output()
+        .expect("run nocta-ui");
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    (output, stderr)
+}
+
+#[test]
+fn failed_components_and_their_dependents_are_skipped() {
+    let project = project();
+    let (output, stderr) = add(project.path(), &["button", "card", "dialog"]);
+
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("2 components could not be installed"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains("Card: "), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Dialog: depends on Card, which failed"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(project.path().join("components/ui/button.tsx").is_file());
+    assert!(!project.path().join("components/ui/dialog.tsx").exists());
+}
+
+#[test]
+fn print_imports_reports_failures() {
+    let project = project();
+    let (output, stderr) = add(project.path(), &["button", "popover", "--print-imports"]);
+
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("1 component could not be installed"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("components/ui/button"),
+        "stdout: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn dependencies_only_reports_failures() {
+    let project = project();
+    let (output, stderr) = add(project.path(), &["button", "card", "--dependencies-only"]);
+
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("1 component could not be installed"),
+        "stderr: {}",
+        stderr
+    );
+}
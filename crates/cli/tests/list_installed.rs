@@ -0,0 +1,70 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+fn list_installed(root: &Path) -> String {
+    let output = common::nocta(root)
+        .args(["list", "--installed"])
+        .output()
+        .expect("run nocta-ui");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// A project with `button` installed from `common::BUNDLE`.
+fn project_with_button() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    common::write_next_app(dir.path(), ".");
+    let output = common::nocta(dir.path())
+        .args(["add", "button", "--yes"])
+        .output()
+        .expect("run nocta-ui");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    dir
+}
+
+#[test]
+fn locked_files_are_compared_with_the_recorded_hash() {
+    let project = project_with_button();
+    // The registry moved on; the installed copy still matches the lockfile.
+    fs::write(
+        project.path().join("bundle.json"),
+        common::BUNDLE.replace(
+            "ZXhwb3J0IGNvbnN0IEJ1dHRvbiA9IDE7Cg==",
+            "ZXhwb3J0IGNvbnN0IEJ1dHRvbiA9IDM7Cg==",
+        ),
+    )
+    .unwrap();
+
+    let stdout = list_installed(project.path());
+    assert!(
+        stdout.contains("components/ui/button.tsx"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(!stdout.contains("modified"), "stdout: {}", stdout);
+}
+
+#[test]
+fn local_edits_to_locked_files_are_flagged() {
+    let project = project_with_button();
+    fs::write(
+        project.path().join("components/ui/button.tsx"),
+        "export const Button = 2;\n",
+    )
+    .unwrap();
+
+    let stdout = list_installed(project.path());
+    assert!(stdout.contains("modified"), "stdout: {}", stdout);
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+/// A monorepo with the app at `apps/web`, whose workspace root is `apps/web`. Both the repo
+/// root and the app hold a `bundle.json`, so the CLI can run from either.
+fn monorepo() -> tempfile::TempDir {
+    let repo = tempfile::tempdir().expect("tempdir");
+    let root = repo.path();
+    fs::write(
+        root.join("package.json"),
+        r#"{"name":"root","private":true,"workspaces":["apps/*"]}"#,
+    )
+    .unwrap();
+    fs::write(root.join("bundle.json"), common::BUNDLE).unwrap();
+    common::write_next_app(&root.join("apps/web"), "apps/web");
+    repo
+}
+
+fn run(dir: &Path, args: &[&str]) -> Output {
+    let output = common::nocta(dir)
+        .args(args)
+        .output()
+        .expect("run nocta-ui");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+/// Publishes a newer registry version to both bundles.
+fn bump_registry_version(root: &Path) {
+    let bundle = common::BUNDLE.replace(r#""version": "1.0.0""#, r#""version": "1.1.0""#);
+    fs::write(root.join("bundle.json"), &bundle).unwrap();
+    fs::write(root.join("apps/web/bundle.json"), &bundle).unwrap();
+}
+
+#[test]
+fn updatable_reads_the_workspace_lockfile_from_the_repo_root() {
+    let repo = monorepo();
+    let root = repo.path();
+    run(&root.join("apps/web"), &["add", "button"]);
+    assert!(root.join("apps/web/nocta.lock.json").is_file());
+    bump_registry_version(root);
+
+    let output = run(
+        root,
+        &[
+            "--config",
+            "apps/web/nocta.config.json",
+            "list",
+            "--updatable",
+        ],
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("button 1.0.0 -> 1.1.0"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn add_from_the_repo_root_writes_the_workspace_lockfile() {
+    let repo = monorepo();
+    let root = repo.path();
+    run(
+        root,
+        &["--config", "apps/web/nocta.config.json", "add", "button"],
+    );
+
+    assert!(!root.join("nocta.lock.json").exists());
+    let lockfile = fs::read_to_string(root.join("apps/web/nocta.lock.json")).unwrap();
+    assert!(
+        lockfile.contains(r#""path": "components/ui/button.tsx""#),
+        "lockfile: {}",
+        lockfile
+    );
+    bump_registry_version(root);
+
+    let output = run(&root.join("apps/web"), &["list", "--updatable"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("button 1.0.0 -> 1.1.0"),
+        "stdout: {}",
+        stdout
+    );
+}
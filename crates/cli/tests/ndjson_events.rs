@@ -0,0 +1,118 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+/// `button` needs `clsx`, so a dry run plans a dependency install as well.
+const BUNDLE: &str = r#"{
+  "registry": {
+    "name": "nocta",
+    "version": "1.0.0",
+    "components": {
+      "button": {
+        "name": "Button",
+        "description": "A button",
+        "category": "form",
+        "files": [{ "name": "button.tsx", "path": "components/button.tsx", "type": "component" }],
+        "dependencies": { "clsx": "^2.1.0" },
+        "exports": ["Button"]
+      }
+    },
+    "categories": {},
+    "requirements": {}
+  },
+  "components": { "components/button.tsx": "ZXhwb3J0IGNvbnN0IEJ1dHRvbiA9IDE7Cg==" }
+}"#;
+
+const EVENTS: &[&str] = &[
+    "componentResolved",
+    "fileWritten",
+    "exportUpdated",
+    "dependencyInstalled",
+    "error",
+];
+
+fn app_with_barrel() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let app = dir.path();
+    common::write_next_app(app, ".");
+    let config_path = app.join("nocta.config.json");
+    let config = fs::read_to_string(&config_path).unwrap().replacen(
+        "\"style\": \"default\",",
+        "\"style\": \"default\",\n  \"exports\": { \"components\": { \"barrel\": \"components/ui/index.ts\" } },",
+        1,
+    );
+    fs::write(&config_path, config).unwrap();
+    fs::write(app.join("bundle.json"), BUNDLE).unwrap();
+    dir
+}
+
+/// Runs `add` with `--output-format ndjson` and parses stdout, which must hold only JSON
+/// objects with a documented `event` name, one per line.
+fn ndjson_events(app: &Path, args: &[&str]) -> (Option<i32>, Vec<Value>) {
+    let output = common::nocta(app)
+        .args(["--output-format", "ndjson", "add"])
+        .args(args)
+        .output()
+        .expect("run nocta-ui");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let events = stdout
+        .lines()
+        .map(|line| {
+            let event: Value = serde_json::from_str(line)
+                .unwrap_or_else(|err| panic!("not a JSON line ({err}): {line:?}"));
+            let name = event["event"].as_str().expect("event name");
+            assert!(EVENTS.contains(&name), "undocumented event {name}");
+            event
+        })
+        .collect();
+    (output.status.code(), events)
+}
+
+fn names(events: &[Value]) -> Vec<&str> {
+    events
+        .iter()
+        .map(|event| event["event"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn add_prints_one_event_per_line() {
+    let project = app_with_barrel();
+    let (code, events) = ndjson_events(project.path(), &["button", "--yes", "--dry-run"]);
+
+    assert_eq!(code, Some(0), "{events:?}");
+    assert_eq!(
+        names(&events),
+        [
+            "componentResolved",
+            "fileWritten",
+            "exportUpdated",
+            "dependencyInstalled"
+        ]
+    );
+    assert!(events.iter().all(|event| event["dryRun"] == true));
+    assert_eq!(events[0]["slug"], "button");
+    assert_eq!(events[1]["path"], "components/ui/button.tsx");
+    assert_eq!(events[2]["path"], "components/ui/index.ts");
+    assert_eq!(events[3]["name"], "clsx");
+}
+
+#[test]
+fn failures_end_with_an_error_event() {
+    let project = app_with_barrel();
+    fs::write(project.path().join("nocta.config.json"), "{").unwrap();
+    let (code, events) = ndjson_events(project.path(), &["button", "--yes"]);
+
+    assert_eq!(code, Some(1));
+    assert_eq!(names(&events), ["error"]);
+    assert!(
+        events[0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("failed to parse config file"),
+        "{events:?}"
+    );
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+/// `nocta-ui list` with no `--no-color` flag, so the environment alone decides.
+fn list(dir: &Path, env: &[(&str, &str)], extra: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_nocta-ui"))
+        .current_dir(dir)
+        .env("NOCTA_CACHE_DIR", dir.join(".cache"))
+        .env_remove("NO_COLOR")
+        .env_remove("FORCE_COLOR")
+        .env_remove("CLICOLOR_FORCE")
+        .env_remove("TURBO_HASH")
+        .env_remove("NX_TASK_TARGET_PROJECT")
+        .envs(env.iter().copied())
+        .args(["--registry-bundle", "bundle.json"])
+        .args(extra)
+        .arg("list")
+        .stdin(Stdio::null())
+        .output()
+        .expect("run nocta-ui")
+}
+
+fn has_escapes(output: &Output) -> bool {
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output.stdout.contains(&0x1b) || output.stderr.contains(&0x1b)
+}
+
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    common::write_next_app(dir.path(), ".");
+    dir
+}
+
+#[test]
+fn piped_output_is_plain() {
+    let dir = project();
+    assert!(!has_escapes(&list(dir.path(), &[], &[])));
+}
+
+#[test]
+fn forced_colors_are_kept() {
+    let dir = project();
+    assert!(has_escapes(&list(dir.path(), &[("FORCE_COLOR", "1")], &[])));
+}
+
+#[test]
+fn no_color_env_disables_colors() {
+    let dir = project();
+    let env = [("FORCE_COLOR", "1"), ("NO_COLOR", "1")];
+    assert!(!has_escapes(&list(dir.path(), &env, &[])));
+}
+
+#[test]
+fn task_runner_disables_colors() {
+    let dir = project();
+    let env = [("FORCE_COLOR", "1"), ("TURBO_HASH", "abc")];
+    assert!(!has_escapes(&list(dir.path(), &env, &[])));
+}
+
+#[test]
+fn no_color_flag_disables_colors() {
+    let dir = project();
+    let output = list(dir.path(), &[("FORCE_COLOR", "1")], &["--no-color"]);
+    assert!(!has_escapes(&output));
+}
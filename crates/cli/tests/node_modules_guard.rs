@@ -0,0 +1,36 @@
+mod common;
+
+use std::fs;
+
+#[test]
+fn add_and_init_refuse_to_run_inside_node_modules() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let app = dir.path().canonicalize().unwrap();
+    common::write_next_app(&app, ".");
+    let package = app.join("node_modules/@acme/ui");
+    fs::create_dir_all(&package).unwrap();
+    fs::write(package.join("package.json"), r#"{"name":"@acme/ui"}"#).unwrap();
+    fs::copy(app.join("bundle.json"), package.join("bundle.json")).unwrap();
+
+    for args in [&["add", "button", "--yes"][..], &["init"][..]] {
+        let output = common::nocta(&package)
+            .args(args)
+            .output()
+            .expect("run nocta-ui");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(1), "{args:?}: {stderr}");
+        assert!(
+            stderr.contains(&format!(
+                "`nocta-ui {}` cannot run inside node_modules",
+                args[0]
+            )),
+            "{stderr}"
+        );
+        assert!(
+            stderr.contains(&format!("e.g. {}", app.display())),
+            "{stderr}"
+        );
+    }
+    assert!(!package.join("nocta.config.json").exists());
+    assert!(!package.join("components").exists());
+}
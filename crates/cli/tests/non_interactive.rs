@@ -0,0 +1,70 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+use tempfile::TempDir;
+
+/// A Next.js project with `components/ui/button.tsx` already holding local edits.
+fn project_with_modified_button() -> TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    common::write_next_app(root, ".");
+    fs::create_dir_all(root.join("components/ui")).unwrap();
+    fs::write(
+        root.join("components/ui/button.tsx"),
+        "export const Button = 2;\n",
+    )
+    .unwrap();
+    dir
+}
+
+fn run_add(root: &Path, extra: &[&str]) -> Output {
+    common::nocta(root)
+        .arg("add")
+        .args(extra)
+        .output()
+        .expect("run nocta-ui")
+}
+
+#[test]
+fn overwrite_without_prompt_fails() {
+    let project = project_with_modified_button();
+    let output = run_add(project.path(), &["button"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("button.tsx"), "stderr: {}", stderr);
+    assert!(stderr.contains("--yes"), "stderr: {}", stderr);
+    assert_eq!(
+        fs::read_to_string(project.path().join("components/ui/button.tsx")).unwrap(),
+        "export const Button = 2;\n"
+    );
+}
+
+#[test]
+fn overwrite_with_yes_succeeds() {
+    let project = project_with_modified_button();
+    let output = run_add(project.path(), &["button", "--yes"]);
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(project.path().join("components/ui/button.tsx")).unwrap(),
+        "export const Button = 1;\n"
+    );
+}
+
+#[test]
+fn all_without_prompt_fails() {
+    let project = project_with_modified_button();
+    let output = run_add(project.path(), &["--all"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--yes"));
+}
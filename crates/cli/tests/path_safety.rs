@@ -0,0 +1,80 @@
+mod common;
+
+use std::fs;
+
+/// A bundle whose single component `widget` ships its file at `path`.
+fn bundle_with_file_path(path: &str) -> String {
+    format!(
+        r#"{{
+  "registry": {{
+    "name": "nocta",
+    "version": "1.0.0",
+    "components": {{
+      "widget": {{
+        "name": "Widget",
+        "description": "",
+        "category": "form",
+        "files": [{{ "name": "widget.tsx", "path": "{path}", "type": "component" }}],
+        "exports": ["Widget"]
+      }}
+    }},
+    "categories": {{}},
+    "requirements": {{}}
+  }},
+  "components": {{ "{path}": "ZXhwb3J0IGNvbnN0IFdpZGdldCA9IDE7Cg==" }}
+}}"#
+    )
+}
+
+#[test]
+fn registry_paths_escaping_the_workspace_are_refused() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let app = dir.path().join("app");
+    common::write_next_app(&app, ".");
+
+    for path in [
+        "components/../../../escape.tsx",
+        "components/../../../../tmp/escape.tsx",
+    ] {
+        fs::write(app.join("bundle.json"), bundle_with_file_path(path)).unwrap();
+        let output = common::nocta(&app)
+            .args(["add", "widget", "--yes"])
+            .output()
+            .expect("run nocta-ui");
+
+        assert_eq!(output.status.code(), Some(1), "path {}", path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("resolves outside the workspace root"),
+            "stderr: {}",
+            stderr
+        );
+    }
+    assert!(!dir.path().join("escape.tsx").exists());
+    assert!(!app.join("components").exists());
+}
+
+#[test]
+fn parent_segments_inside_the_workspace_are_allowed() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let app = dir.path();
+    common::write_next_app(app, ".");
+    fs::write(
+        app.join("bundle.json"),
+        bundle_with_file_path("components/forms/../widget.tsx"),
+    )
+    .unwrap();
+
+    let output = common::nocta(app)
+        .args(["add", "widget", "--yes"])
+        .output()
+        .expect("run nocta-ui");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(app.join("components/ui/widget.tsx").is_file());
+}
@@ -0,0 +1,141 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use serde_json::Value;
+
+const CONFIG_REGISTRY: &str = "https://config.example.com/registry";
+const MANIFEST_REGISTRY: &str = "https://manifest.example.com/registry";
+
+fn run(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_nocta-ui"))
+        .current_dir(dir)
+        .env("NOCTA_CACHE_DIR", dir.join(".cache"))
+        .env_remove("NOCTA_REGISTRY_URL")
+        .args(["--no-color"])
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .expect("run nocta-ui")
+}
+
+fn config_print(dir: &Path, extra: &[&str]) -> Output {
+    run(dir, &[extra, &["config", "print", "--json"]].concat())
+}
+
+fn registry(output: &Output) -> (String, String) {
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: Value = serde_json::from_slice(&output.stdout).expect("json output");
+    (
+        json["registry"]["url"].as_str().unwrap().to_string(),
+        json["registry"]["source"].as_str().unwrap().to_string(),
+    )
+}
+
+fn repo(config_registry: Option<&str>) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        dir.path().join("nocta.workspace.json"),
+        format!(r#"{{ "registry": "{}" }}"#, MANIFEST_REGISTRY),
+    )
+    .unwrap();
+    let registry = config_registry
+        .map(|url| format!(r#""registry": "{}","#, url))
+        .unwrap_or_default();
+    fs::write(
+        dir.path().join("nocta.config.json"),
+        format!(
+            r#"{{ {} "style": "default", "tailwind": {{ "css": "app/globals.css" }}, "aliases": {{ "components": "components/ui", "utils": "lib/utils" }} }}"#,
+            registry
+        ),
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn manifest_registry_is_used_without_config_or_flag() {
+    let dir = repo(None);
+    assert_eq!(
+        registry(&config_print(dir.path(), &[])),
+        (MANIFEST_REGISTRY.to_string(), "manifest".to_string())
+    );
+}
+
+#[test]
+fn config_registry_beats_manifest() {
+    let dir = repo(Some(CONFIG_REGISTRY));
+    assert_eq!(
+        registry(&config_print(dir.path(), &[])),
+        (CONFIG_REGISTRY.to_string(), "config".to_string())
+    );
+}
+
+#[test]
+fn flag_beats_config() {
+    let dir = repo(Some(CONFIG_REGISTRY));
+    let output = config_print(
+        dir.path(),
+        &["--registry-url", "https://flag.example.com/registry"],
+    );
+    assert_eq!(
+        registry(&output),
+        (
+            "https://flag.example.com/registry".to_string(),
+            "flag".to_string()
+        )
+    );
+}
+
+#[test]
+fn malformed_config_is_reported_by_commands_that_read_it() {
+    let dir = repo(None);
+    fs::write(dir.path().join("nocta.config.json"), "{ \"style\": ").unwrap();
+
+    let output = run(dir.path(), &["config", "get", "style"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("nocta.config.json"), "stderr: {}", stderr);
+}
+
+#[test]
+fn malformed_config_and_manifest_do_not_block_other_commands() {
+    let dir = repo(None);
+    fs::write(dir.path().join("nocta.config.json"), "{ \"style\": ").unwrap();
+    fs::write(
+        dir.path().join("nocta.workspace.json"),
+        "{ \"registry\": 1 }",
+    )
+    .unwrap();
+
+    for args in [&["config", "schema"][..], &["cache", "clear"][..]] {
+        let output = run(dir.path(), args);
+        assert_eq!(
+            output.status.code(),
+            Some(0),
+            "{:?} stderr: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+#[test]
+fn malformed_manifest_is_reported() {
+    let dir = repo(None);
+    fs::write(
+        dir.path().join("nocta.workspace.json"),
+        "{ \"registry\": 1 }",
+    )
+    .unwrap();
+
+    let output = config_print(dir.path(), &[]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("workspace manifest"), "stderr: {}", stderr);
+}
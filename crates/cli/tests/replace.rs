@@ -0,0 +1,118 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+/// `button` installed into `<tmp>/app`, with `extra` paths appended to its lockfile entry.
+/// Each extra entry records the installed button's hash.
+fn project_with_locked_files(extra: &[&str]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let app = dir.path().join("app");
+    common::write_next_app(&app, ".");
+    let output = common::nocta(&app)
+        .args(["add", "button"])
+        .output()
+        .expect("run nocta-ui");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let lock_path = app.join("nocta.lock.json");
+    let mut lockfile: Value =
+        serde_json::from_str(&fs::read_to_string(&lock_path).unwrap()).unwrap();
+    let files = lockfile["components"]["button"]["files"]
+        .as_array_mut()
+        .unwrap();
+    let hash = files[0]["hash"].clone();
+    for path in extra {
+        files.push(json!({ "path": path, "hash": hash }));
+    }
+    fs::write(&lock_path, serde_json::to_string_pretty(&lockfile).unwrap()).unwrap();
+    dir
+}
+
+fn replace(app: &Path) -> String {
+    let output = common::nocta(app)
+        .args(["add", "button", "--replace", "--yes"])
+        .output()
+        .expect("run nocta-ui");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+#[test]
+fn unmodified_stale_files_are_removed() {
+    let project = project_with_locked_files(&["components/ui/old-button.tsx"]);
+    let app = project.path().join("app");
+    fs::write(
+        app.join("components/ui/old-button.tsx"),
+        "export const Button = 1;\n",
+    )
+    .unwrap();
+
+    let output = replace(&app);
+
+    assert!(
+        output.contains("components/ui/old-button.tsx"),
+        "output: {}",
+        output
+    );
+    assert!(!app.join("components/ui/old-button.tsx").exists());
+}
+
+#[test]
+fn modified_stale_files_are_kept() {
+    let project = project_with_locked_files(&["components/ui/old-button.tsx"]);
+    let app = project.path().join("app");
+    fs::write(
+        app.join("components/ui/old-button.tsx"),
+        "export const Button = 42;\n",
+    )
+    .unwrap();
+
+    let output = replace(&app);
+
+    assert!(output.contains("has local changes"), "output: {}", output);
+    assert_eq!(
+        fs::read_to_string(app.join("components/ui/old-button.tsx")).unwrap(),
+        "export const Button = 42;\n"
+    );
+}
+
+#[test]
+fn entries_outside_the_workspace_are_never_removed() {
+    let project = project_with_locked_files(&["../outside.tsx"]);
+    let outside = project.path().join("outside.tsx");
+    fs::write(&outside, "export const Button = 1;\n").unwrap();
+    let absolute = outside.to_string_lossy().into_owned();
+    let project_abs = project_with_locked_files(&[&absolute]);
+
+    let output = replace(&project.path().join("app"));
+    assert!(
+        output.contains("outside the workspace"),
+        "output: {}",
+        output
+    );
+    let output = replace(&project_abs.path().join("app"));
+    assert!(
+        output.contains("outside the workspace"),
+        "output: {}",
+        output
+    );
+
+    assert!(outside.is_file());
+}
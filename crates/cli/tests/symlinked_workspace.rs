@@ -0,0 +1,49 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+use std::os::unix::fs::symlink;
+
+/// `apps/web` is a symlink to `real-web`, a sibling directory inside the same repo.
+#[test]
+fn add_from_symlinked_workspace_uses_workspace_paths() {
+    let repo = tempfile::tempdir().expect("tempdir");
+    let root = repo.path();
+    fs::write(
+        root.join("package.json"),
+        r#"{"name":"root","private":true,"workspaces":["apps/*"]}"#,
+    )
+    .unwrap();
+    common::write_next_app(&root.join("real-web"), "apps/web");
+    fs::create_dir_all(root.join("apps")).unwrap();
+    symlink("../real-web", root.join("apps/web")).unwrap();
+
+    let output = common::nocta(&root.join("apps/web"))
+        .args(["add", "button"])
+        .output()
+        .expect("run nocta-ui");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Workspace apps/web"), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("   components/ui/button.tsx"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(!stdout.contains(".."), "stdout: {}", stdout);
+
+    assert!(root.join("real-web/components/ui/button.tsx").is_file());
+    let lockfile = fs::read_to_string(root.join("real-web/nocta.lock.json")).unwrap();
+    assert!(
+        lockfile.contains(r#""path": "components/ui/button.tsx""#),
+        "lockfile: {}",
+        lockfile
+    );
+}
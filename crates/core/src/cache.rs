@@ -1,21 +1,68 @@
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Component, Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
+use crc32fast::Hasher as Crc32Hasher;
 use directories::BaseDirs;
 use once_cell::sync::Lazy;
 use tempfile::NamedTempFile;
 
+use crate::constants::registry as registry_constants;
+use crate::registry::HttpCacheMetadata;
+use crate::types::Registry;
+
 const DEFAULT_CACHE_DIR_NAME: &str = "nocta-ui";
 const MAX_CACHE_AGE_SECS: u64 = 30 * 24 * 60 * 60;
 const METADATA_SUFFIX: &str = ".meta";
+const LOCK_FILE_NAME: &str = ".lock";
+const PROJECT_LOCKS_DIR: &str = "locks";
 
-static CACHE_BASE_DIR: Lazy<PathBuf> = Lazy::new(resolve_cache_base_dir);
+static CACHE_BASE_DIR: Lazy<(PathBuf, CacheDirSource)> = Lazy::new(resolve_cache_base_dir);
 
 fn cache_base_dir() -> PathBuf {
-    CACHE_BASE_DIR.clone()
+    CACHE_BASE_DIR.0.clone()
+}
+
+/// Where the cache directory location came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheDirSource {
+    /// `NOCTA_CACHE_DIR`.
+    Env,
+    /// The platform cache directory reported by the OS.
+    Platform,
+    /// `$HOME/.cache`, used when the platform directory cannot be determined.
+    Home,
+    /// `%LOCALAPPDATA%`, the Windows equivalent of the `Home` fallback.
+    LocalAppData,
+    /// The system temp directory, which may be cleared at any time.
+    Temp,
+}
+
+impl CacheDirSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheDirSource::Env => "env",
+            CacheDirSource::Platform => "platform",
+            CacheDirSource::Home => "home",
+            CacheDirSource::LocalAppData => "localappdata",
+            CacheDirSource::Temp => "temp",
+        }
+    }
+
+    /// Whether the platform cache directory was unavailable and a fallback is in use.
+    pub fn is_fallback(&self) -> bool {
+        matches!(
+            self,
+            CacheDirSource::Home | CacheDirSource::LocalAppData | CacheDirSource::Temp
+        )
+    }
+}
+
+pub fn cache_dir_source() -> CacheDirSource {
+    CACHE_BASE_DIR.1
 }
 
 fn normalized_rel_path(rel_path: &str) -> PathBuf {
@@ -53,10 +100,10 @@ pub fn cache_dir() -> PathBuf {
 }
 
 fn ensure_parent_dir(path: &Path) -> io::Result<()> {
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent)?;
-        }
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
     }
     Ok(())
 }
@@ -66,42 +113,56 @@ pub fn read_cache_text(
     ttl: Option<Duration>,
     accept_stale: bool,
 ) -> io::Result<Option<String>> {
+    read_cache_bytes(rel_path, ttl, accept_stale)?
+        .map(|bytes| {
+            String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        })
+        .transpose()
+}
+
+pub fn read_cache_bytes(
+    rel_path: &str,
+    ttl: Option<Duration>,
+    accept_stale: bool,
+) -> io::Result<Option<Vec<u8>>> {
     let full_path = resolve_cache_path(rel_path);
     if !full_path.exists() {
         return Ok(None);
     }
 
-    if let Ok(metadata) = fs::metadata(&full_path) {
-        if let Ok(modified) = metadata.modified() {
-            if let Ok(elapsed) = SystemTime::now().duration_since(modified) {
-                if elapsed > max_cache_age() {
-                    purge_entry(rel_path);
-                    return Ok(None);
-                }
+    if let Ok(metadata) = fs::metadata(&full_path)
+        && let Ok(modified) = metadata.modified()
+        && let Ok(elapsed) = SystemTime::now().duration_since(modified)
+    {
+        if elapsed > max_cache_age() {
+            purge_entry(rel_path);
+            return Ok(None);
+        }
 
-                if !accept_stale {
-                    if let Some(ttl) = ttl {
-                        if elapsed > ttl {
-                            return Ok(None);
-                        }
-                    }
-                }
-            }
+        if !accept_stale
+            && let Some(ttl) = ttl
+            && elapsed > ttl
+        {
+            return Ok(None);
         }
     }
 
-    fs::read_to_string(full_path).map(Some)
+    fs::read(full_path).map(Some)
 }
 
 pub fn write_cache_text(rel_path: &str, contents: &str) -> io::Result<()> {
+    write_cache_bytes(rel_path, contents.as_bytes())
+}
+
+pub fn write_cache_bytes(rel_path: &str, contents: &[u8]) -> io::Result<()> {
     let full_path = resolve_cache_path(rel_path);
     ensure_parent_dir(&full_path)?;
     let parent_dir = full_path
         .parent()
         .map(Path::to_path_buf)
-        .unwrap_or_else(|| cache_base_dir());
+        .unwrap_or_else(cache_base_dir);
     let mut tmp = NamedTempFile::new_in(parent_dir)?;
-    tmp.write_all(contents.as_bytes())?;
+    tmp.write_all(contents)?;
     tmp.flush()?;
     tmp.persist(full_path).map(|_| ()).map_err(|err| err.error)
 }
@@ -129,6 +190,224 @@ pub fn remove_cache_metadata(rel_path: &str) -> io::Result<()> {
     Ok(())
 }
 
+/// Advisory lock over the cache directory. The lock is released when dropped.
+pub struct CacheLock {
+    _file: fs::File,
+}
+
+fn open_lock_file() -> io::Result<fs::File> {
+    open_lock_file_at(&cache_base_dir().join(LOCK_FILE_NAME))
+}
+
+fn open_lock_file_at(path: &Path) -> io::Result<fs::File> {
+    ensure_parent_dir(path)?;
+    fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+}
+
+/// Blocks until no other process holds an exclusive lock on the cache.
+pub fn lock_cache_shared() -> io::Result<CacheLock> {
+    let file = open_lock_file()?;
+    file.lock_shared()?;
+    Ok(CacheLock { _file: file })
+}
+
+/// Blocks until this process is the only holder of the cache lock.
+pub fn lock_cache_exclusive() -> io::Result<CacheLock> {
+    let file = open_lock_file()?;
+    file.lock()?;
+    Ok(CacheLock { _file: file })
+}
+
+/// Blocks until this process holds the lock for `path`, a project file that several
+/// CLI runs may read-modify-write. The lock file lives in the cache directory so the
+/// project tree stays clean.
+pub fn lock_project_file(path: &Path) -> io::Result<CacheLock> {
+    let mut hasher = Crc32Hasher::new();
+    hasher.update(path.to_string_lossy().as_bytes());
+    let lock_path = cache_base_dir().join(PROJECT_LOCKS_DIR).join(format!(
+        "{:08x}{}",
+        hasher.finalize(),
+        LOCK_FILE_NAME
+    ));
+    let file = open_lock_file_at(&lock_path)?;
+    file.lock()?;
+    Ok(CacheLock { _file: file })
+}
+
+/// Number of cached entries and the total bytes they occupy on disk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheUsage {
+    pub entries: u64,
+    pub bytes: u64,
+}
+
+/// Walks the cache directory and totals its contents. Metadata sidecars and lock
+/// files count towards `bytes` but not towards `entries`.
+pub fn cache_usage() -> io::Result<CacheUsage> {
+    let mut usage = CacheUsage::default();
+    let dir = cache_base_dir();
+    if dir.exists() {
+        accumulate_usage(&dir, &mut usage)?;
+    }
+    Ok(usage)
+}
+
+fn accumulate_usage(dir: &Path, usage: &mut CacheUsage) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            accumulate_usage(&entry.path(), usage)?;
+            continue;
+        }
+
+        usage.bytes += entry.metadata()?.len();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !name.ends_with(LOCK_FILE_NAME) && !name.ends_with(METADATA_SUFFIX) {
+            usage.entries += 1;
+        }
+    }
+    Ok(())
+}
+
+/// A cached file `verify_cache` could not parse, relative to the cache directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorruptCacheEntry {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+/// Parses every cached JSON entry and every metadata sidecar, removing the ones that fail
+/// unless `dry_run` is set. Registries, components manifests and sidecars must deserialize
+/// into the types the registry client reads them as; other JSON assets only need to be
+/// valid JSON. A corrupt entry takes its sidecar with it so the next fetch downloads it
+/// again instead of revalidating.
+///
+/// Holds the cache lock for the whole pass: shared for dry runs, exclusive otherwise so no
+/// fetch writes an entry while it is checked or removed.
+pub fn verify_cache(dry_run: bool) -> io::Result<Vec<CorruptCacheEntry>> {
+    verify_cache_at(&cache_base_dir(), dry_run)
+}
+
+fn verify_cache_at(dir: &Path, dry_run: bool) -> io::Result<Vec<CorruptCacheEntry>> {
+    let mut corrupt = Vec::new();
+    if !dir.exists() {
+        return Ok(corrupt);
+    }
+
+    let lock = open_lock_file_at(&dir.join(LOCK_FILE_NAME))?;
+    if dry_run {
+        lock.lock_shared()?;
+    } else {
+        lock.lock()?;
+    }
+    verify_dir(dir, dir, dry_run, &mut corrupt)?;
+    Ok(corrupt)
+}
+
+fn verify_dir(
+    base: &Path,
+    dir: &Path,
+    dry_run: bool,
+    corrupt: &mut Vec<CorruptCacheEntry>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            verify_dir(base, &path, dry_run, corrupt)?;
+            continue;
+        }
+
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let is_sidecar = name.ends_with(METADATA_SUFFIX);
+        if name.ends_with(LOCK_FILE_NAME) || (!is_sidecar && !name.ends_with(".json")) {
+            continue;
+        }
+        // A corrupt entry removed earlier in this pass may have taken this sidecar along.
+        if !path.exists() {
+            continue;
+        }
+
+        let relative = path.strip_prefix(base).unwrap_or(&path).to_path_buf();
+        let kind = if is_sidecar {
+            CachedJson::Metadata
+        } else {
+            CachedJson::for_entry(&relative)
+        };
+        let Err(reason) = fs::read(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| kind.parse(&bytes))
+        else {
+            continue;
+        };
+
+        if !dry_run {
+            fs::remove_file(&path)?;
+            if !is_sidecar {
+                let sidecar = dir.join(format!("{name}{METADATA_SUFFIX}"));
+                if sidecar.exists() {
+                    fs::remove_file(sidecar)?;
+                }
+            }
+        }
+        corrupt.push(CorruptCacheEntry {
+            path: relative,
+            reason,
+        });
+    }
+    Ok(())
+}
+
+/// What a cached JSON file is read back as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CachedJson {
+    /// `registry/<namespace>/registry.json`.
+    Registry,
+    /// `components.json` under a namespace's `assets/`.
+    ComponentsManifest,
+    /// A `.meta` sidecar.
+    Metadata,
+    Other,
+}
+
+impl CachedJson {
+    fn for_entry(relative: &Path) -> Self {
+        let parts: Vec<_> = relative
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect();
+        let parts: Vec<&str> = parts.iter().map(AsRef::as_ref).collect();
+        match parts.as_slice() {
+            ["registry", _, file] if *file == registry_constants::CACHE_PATH => Self::Registry,
+            ["registry", _, "assets", .., file]
+                if *file == registry_constants::COMPONENTS_MANIFEST =>
+            {
+                Self::ComponentsManifest
+            }
+            _ => Self::Other,
+        }
+    }
+
+    fn parse(self, bytes: &[u8]) -> Result<(), String> {
+        let result = match self {
+            Self::Registry => serde_json::from_slice::<Registry>(bytes).map(drop),
+            Self::ComponentsManifest => {
+                serde_json::from_slice::<HashMap<String, String>>(bytes).map(drop)
+            }
+            Self::Metadata => serde_json::from_slice::<HttpCacheMetadata>(bytes).map(drop),
+            Self::Other => serde_json::from_slice::<serde_json::Value>(bytes).map(drop),
+        };
+        result.map_err(|err| err.to_string())
+    }
+}
+
 pub fn clear_cache() -> io::Result<()> {
     let dir = cache_base_dir();
     if dir.exists() {
@@ -153,18 +432,152 @@ fn current_cache_dir_override() -> Option<PathBuf> {
         .map(PathBuf::from)
 }
 
-fn resolve_cache_base_dir() -> PathBuf {
+fn absolute_env_dir(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+fn resolve_cache_base_dir() -> (PathBuf, CacheDirSource) {
     if let Some(explicit) = current_cache_dir_override() {
-        return explicit;
+        return (explicit, CacheDirSource::Env);
     }
 
     if let Some(dirs) = BaseDirs::new() {
-        return dirs.cache_dir().join(DEFAULT_CACHE_DIR_NAME);
+        return (
+            dirs.cache_dir().join(DEFAULT_CACHE_DIR_NAME),
+            CacheDirSource::Platform,
+        );
     }
 
-    env::temp_dir().join(DEFAULT_CACHE_DIR_NAME)
+    // `BaseDirs` gives up in minimal containers (e.g. no passwd entry); the env vars
+    // usually still point at a persistent location, unlike the temp dir.
+    if cfg!(windows)
+        && let Some(local) = absolute_env_dir("LOCALAPPDATA")
+    {
+        return (
+            local.join(DEFAULT_CACHE_DIR_NAME),
+            CacheDirSource::LocalAppData,
+        );
+    }
+    if let Some(home) = absolute_env_dir("HOME") {
+        return (
+            home.join(".cache").join(DEFAULT_CACHE_DIR_NAME),
+            CacheDirSource::Home,
+        );
+    }
+
+    (
+        env::temp_dir().join(DEFAULT_CACHE_DIR_NAME),
+        CacheDirSource::Temp,
+    )
 }
 
 fn max_cache_age() -> Duration {
     Duration::from_secs(MAX_CACHE_AGE_SECS)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str =
+        r#"{"name":"nocta","version":"1.0.0","components":{},"categories":{},"requirements":{}}"#;
+
+    fn write(base: &Path, rel_path: &str, contents: &str) {
+        let path = base.join(rel_path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn corrupt_paths(corrupt: &[CorruptCacheEntry]) -> Vec<String> {
+        let mut paths: Vec<String> = corrupt
+            .iter()
+            .map(|entry| entry.path.to_string_lossy().replace('\\', "/"))
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn known_entries_must_deserialize_into_their_types() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        write(base, "registry/aaaa/registry.json", REGISTRY);
+        write(
+            base,
+            "registry/aaaa/registry.json.meta",
+            r#"{"etag":"\"v1\""}"#,
+        );
+        write(
+            base,
+            "registry/aaaa/assets/components.json",
+            r#"{"a.tsx":"YQ=="}"#,
+        );
+        write(base, "registry/bbbb/registry.json", r#"{"name":1}"#);
+        write(
+            base,
+            "registry/bbbb/registry.json.meta",
+            r#"{"etag":"\"v1\""}"#,
+        );
+        write(
+            base,
+            "registry/bbbb/assets/manifests/cccc/components.json",
+            r#"{"a.tsx":["not","a","string"]}"#,
+        );
+        write(base, "registry/bbbb/assets/data.json", r#"["any","json"]"#);
+        write(base, "registry/bbbb/assets/data.json.meta", r#"{"etag":5}"#);
+
+        let corrupt = verify_cache_at(base, false).unwrap();
+
+        assert_eq!(
+            corrupt_paths(&corrupt),
+            [
+                "registry/bbbb/assets/data.json.meta",
+                "registry/bbbb/assets/manifests/cccc/components.json",
+                "registry/bbbb/registry.json",
+            ]
+        );
+        assert!(base.join("registry/aaaa/registry.json").exists());
+        assert!(base.join("registry/aaaa/registry.json.meta").exists());
+        assert!(base.join("registry/aaaa/assets/components.json").exists());
+        assert!(base.join("registry/bbbb/assets/data.json").exists());
+        assert!(!base.join("registry/bbbb/registry.json").exists());
+        assert!(!base.join("registry/bbbb/registry.json.meta").exists());
+        assert!(!base.join("registry/bbbb/assets/data.json.meta").exists());
+    }
+
+    #[test]
+    fn dry_run_reports_without_removing() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        write(base, "registry/aaaa/registry.json", "{");
+
+        let corrupt = verify_cache_at(base, true).unwrap();
+
+        assert_eq!(corrupt_paths(&corrupt), ["registry/aaaa/registry.json"]);
+        assert!(base.join("registry/aaaa/registry.json").exists());
+    }
+
+    #[test]
+    fn verify_waits_for_readers_before_removing_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().to_path_buf();
+        write(&base, "registry/aaaa/registry.json", "{");
+
+        let reader = open_lock_file_at(&base.join(LOCK_FILE_NAME)).unwrap();
+        reader.lock_shared().unwrap();
+
+        let verifying = {
+            let base = base.clone();
+            std::thread::spawn(move || verify_cache_at(&base, false).unwrap())
+        };
+        std::thread::sleep(Duration::from_millis(200));
+        assert!(base.join("registry/aaaa/registry.json").exists());
+
+        drop(reader);
+        let corrupt = verifying.join().unwrap();
+        assert_eq!(corrupt_paths(&corrupt), ["registry/aaaa/registry.json"]);
+        assert!(!base.join("registry/aaaa/registry.json").exists());
+    }
+}
@@ -1,7 +1,9 @@
 use std::fs;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
+use serde_json::{Map, Value};
 use thiserror::Error;
 
 use crate::types::Config;
@@ -19,10 +21,161 @@ pub enum ConfigError {
     Serialize(serde_json::Error),
     #[error("failed to write config file: {0}")]
     Write(io::Error),
+    #[error("unknown config key `{0}` (valid keys: {1})")]
+    UnknownKey(String, String),
+    #[error("invalid value for `{0}`: {1}")]
+    InvalidValue(String, serde_json::Error),
+}
+
+/// JSON schema for `nocta.config.json`, generated from the [`Config`] types.
+pub fn config_schema() -> serde_json::Value {
+    schemars::schema_for!(Config).to_value()
+}
+
+/// Placeholder for free-form map keys (e.g. registry names) in [`config_keys`].
+const MAP_KEY_PLACEHOLDER: &str = "<name>";
+
+/// Every dotted key `config get`/`config set` accept, derived from the config schema.
+/// Map entries appear as `<name>`, e.g. `registries.<name>`.
+pub fn config_keys() -> Vec<String> {
+    let schema = config_schema();
+    let mut keys = Vec::new();
+    collect_schema_keys(&schema, &schema, "", &mut keys);
+    keys.retain(|key| key != "$schema");
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+fn collect_schema_keys(root: &Value, node: &Value, prefix: &str, keys: &mut Vec<String>) {
+    if let Some(target) = node.get("$ref").and_then(Value::as_str) {
+        if let Some(resolved) = root.pointer(target.trim_start_matches('#')) {
+            collect_schema_keys(root, resolved, prefix, keys);
+        }
+        return;
+    }
+
+    if !prefix.is_empty() {
+        keys.push(prefix.to_string());
+    }
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+
+    for variant in ["anyOf", "oneOf", "allOf"]
+        .into_iter()
+        .filter_map(|field| node.get(field).and_then(Value::as_array))
+        .flatten()
+    {
+        collect_schema_keys(root, variant, prefix, keys);
+    }
+    if let Some(properties) = node.get("properties").and_then(Value::as_object) {
+        for (key, property) in properties {
+            collect_schema_keys(root, property, &join(key), keys);
+        }
+    }
+    if let Some(values) = node
+        .get("additionalProperties")
+        .filter(|value| value.is_object())
+    {
+        collect_schema_keys(root, values, &join(MAP_KEY_PLACEHOLDER), keys);
+    }
+}
+
+fn validate_config_key(key: &str) -> Result<(), ConfigError> {
+    let keys = config_keys();
+    let segments: Vec<&str> = key.split('.').collect();
+    let known = keys.iter().any(|candidate| {
+        let pattern: Vec<&str> = candidate.split('.').collect();
+        pattern.len() == segments.len()
+            && pattern
+                .iter()
+                .zip(&segments)
+                .all(|(expected, actual)| *expected == MAP_KEY_PLACEHOLDER || expected == actual)
+    });
+    if known && segments.iter().all(|segment| !segment.is_empty()) {
+        Ok(())
+    } else {
+        Err(ConfigError::UnknownKey(key.to_string(), keys.join(", ")))
+    }
+}
+
+fn config_to_value(config: &Config) -> Result<Value, ConfigError> {
+    serde_json::to_value(config).map_err(ConfigError::Serialize)
+}
+
+/// Reads the dotted `key` (e.g. `aliases.components`) from `config`.
+/// Returns `None` for a known key that is not set.
+pub fn get_config_value(config: &Config, key: &str) -> Result<Option<Value>, ConfigError> {
+    validate_config_key(key)?;
+    let value = config_to_value(config)?;
+    Ok(key
+        .split('.')
+        .try_fold(&value, |node, segment| node.get(segment))
+        .filter(|value| !value.is_null())
+        .cloned())
+}
+
+/// Returns `config` with the dotted `key` set to `raw`. `raw` is parsed as JSON when
+/// possible (numbers, booleans, arrays, objects) and otherwise used as a string; missing
+/// parent objects are created. The result must still deserialize as a valid [`Config`].
+pub fn set_config_value(config: &Config, key: &str, raw: &str) -> Result<Config, ConfigError> {
+    validate_config_key(key)?;
+    let parsed = serde_json::from_str::<Value>(raw).ok();
+    let as_string = Value::String(raw.to_string());
+
+    let mut first_error = None;
+    for candidate in parsed.into_iter().chain([as_string]) {
+        let mut document = config_to_value(config)?;
+        let mut node = &mut document;
+        for segment in key.split('.') {
+            if !node.is_object() {
+                *node = Value::Object(Map::new());
+            }
+            node = node
+                .as_object_mut()
+                .expect("node was just made an object")
+                .entry(segment)
+                .or_insert(Value::Null);
+        }
+        *node = candidate;
+
+        match serde_json::from_value::<Config>(document) {
+            Ok(updated) => return Ok(updated),
+            Err(err) => {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    Err(ConfigError::InvalidValue(
+        key.to_string(),
+        first_error.expect("at least one candidate value was tried"),
+    ))
+}
+
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Makes `read_config`/`write_config` use `path` instead of `nocta.config.json` in the
+/// current directory for the rest of the process. Only the first call takes effect.
+pub fn set_config_path(path: impl Into<PathBuf>) {
+    let _ = CONFIG_PATH_OVERRIDE.set(path.into());
+}
+
+/// The config file `read_config`/`write_config` use.
+pub fn config_path() -> &'static Path {
+    CONFIG_PATH_OVERRIDE
+        .get()
+        .map(PathBuf::as_path)
+        .unwrap_or(Path::new(CONFIG_FILE_NAME))
 }
 
 pub fn read_config() -> Result<Option<Config>, ConfigError> {
-    read_config_from(CONFIG_FILE_NAME)
+    read_config_from(config_path())
 }
 
 pub fn read_config_from<P: AsRef<Path>>(path: P) -> Result<Option<Config>, ConfigError> {
@@ -41,7 +194,7 @@ pub fn read_config_from<P: AsRef<Path>>(path: P) -> Result<Option<Config>, Confi
 }
 
 pub fn write_config(config: &Config) -> Result<(), ConfigError> {
-    write_config_to(CONFIG_FILE_NAME, config)
+    write_config_to(config_path(), config)
 }
 
 pub fn write_config_to<P: AsRef<Path>>(path: P, config: &Config) -> Result<(), ConfigError> {
@@ -58,10 +211,10 @@ pub fn write_config_to<P: AsRef<Path>>(path: P, config: &Config) -> Result<(), C
 }
 
 pub fn ensure_parent_dir(path: &Path) -> io::Result<()> {
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent)?;
-        }
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
     }
     Ok(())
 }
@@ -7,7 +7,9 @@ use anyhow::{Context, Result};
 use semver::{Version, VersionReq};
 use serde::Deserialize;
 
-use crate::workspace::{PackageManagerContext, PackageManagerKind, detect_package_manager};
+use crate::workspace::{
+    PackageManagerContext, PackageManagerKind, detect_package_manager, package_json_has_workspaces,
+};
 
 const YARN_PNP_MARKERS: [&str; 3] = [".pnp.cjs", ".pnp.js", ".pnp.loader.mjs"];
 
@@ -16,6 +18,9 @@ pub enum RequirementIssueReason {
     Missing,
     Outdated,
     Unknown,
+    /// The required range itself is not valid semver, so the registry (not the project)
+    /// needs fixing.
+    InvalidRange,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -27,7 +32,7 @@ pub struct RequirementIssue {
     pub reason: RequirementIssueReason,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DependencyScope {
     Regular,
     Dev,
@@ -43,6 +48,10 @@ pub struct DependencyInstallPlan {
     pub workspace_descriptor: Option<String>,
     pub dependencies: Vec<String>,
     pub env: Vec<(String, String)>,
+    /// Inherited variables cleared before spawning so they cannot shadow `npmrc`.
+    pub env_remove: Vec<String>,
+    /// Project `.npmrc` the package manager will read registry and auth settings from.
+    pub npmrc: Option<PathBuf>,
     pub scope: DependencyScope,
 }
 
@@ -64,10 +73,18 @@ impl DependencyInstallPlan {
         self.workspace_descriptor.as_deref()
     }
 
+    /// Whether `program` resolves to an executable, so `execute` can spawn it.
+    pub fn program_available(&self) -> bool {
+        find_program(&self.program).is_some()
+    }
+
     pub fn execute(&self) -> Result<()> {
         let mut command = Command::new(&self.program);
         command.args(&self.args);
         command.current_dir(&self.working_directory);
+        for key in &self.env_remove {
+            command.env_remove(key);
+        }
         for (key, value) in &self.env {
             command.env(key, value);
         }
@@ -103,6 +120,8 @@ struct PackageJson {
     dependencies: HashMap<String, String>,
     #[serde(default)]
     dev_dependencies: HashMap<String, String>,
+    #[serde(default)]
+    package_manager: Option<String>,
 }
 
 fn read_package_json(base: &Path) -> Option<PackageJson> {
@@ -116,7 +135,7 @@ fn declared_dependencies(base: &Path) -> HashMap<String, String> {
         .map(|pkg| {
             pkg.dependencies
                 .into_iter()
-                .chain(pkg.dev_dependencies.into_iter())
+                .chain(pkg.dev_dependencies)
                 .collect()
         })
         .unwrap_or_default()
@@ -138,7 +157,33 @@ fn node_module_package_json_path(base: &Path, name: &str) -> Option<PathBuf> {
     None
 }
 
-fn read_installed_version(base: &Path, name: &str) -> Option<String> {
+/// Resolves `program` like a shell would: paths are checked directly, bare names are
+/// searched for in `PATH` (with the `PATHEXT` extensions on Windows).
+pub fn find_program(program: &str) -> Option<PathBuf> {
+    let candidate = Path::new(program);
+    if candidate.components().count() > 1 {
+        return candidate.is_file().then(|| candidate.to_path_buf());
+    }
+
+    let extensions: Vec<String> = if cfg!(windows) {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into())
+            .split(';')
+            .map(str::to_string)
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+
+    std::env::split_paths(&std::env::var_os("PATH")?).find_map(|dir| {
+        extensions.iter().find_map(|extension| {
+            let path = dir.join(format!("{program}{extension}"));
+            path.is_file().then_some(path)
+        })
+    })
+}
+
+pub(crate) fn read_installed_version(base: &Path, name: &str) -> Option<String> {
     let path = node_module_package_json_path(base, name)?;
     let contents = fs::read_to_string(path).ok()?;
     let value: serde_json::Value = serde_json::from_str(&contents).ok()?;
@@ -156,8 +201,56 @@ fn parse_version(version: &str) -> Option<Version> {
     Version::parse(normalize_version_str(version)).ok()
 }
 
-fn parse_version_req(range: &str) -> Option<VersionReq> {
-    VersionReq::parse(range).ok()
+/// An npm version range: alternatives separated by `||`, each a set of comparators that
+/// must all match.
+#[derive(Debug, Clone)]
+struct NpmRange(Vec<VersionReq>);
+
+impl NpmRange {
+    fn matches(&self, version: &Version) -> bool {
+        self.0.iter().any(|req| req.matches(version))
+    }
+}
+
+/// Parses npm range syntax (`^18 || ^19`, `>=18 <20`, `1.2 - 1.4`) into `semver` requirements.
+fn parse_version_req(range: &str) -> Option<NpmRange> {
+    range
+        .split("||")
+        .map(|alternative| parse_npm_comparator_set(alternative.trim()))
+        .collect::<Option<Vec<_>>>()
+        .map(NpmRange)
+}
+
+fn parse_npm_comparator_set(set: &str) -> Option<VersionReq> {
+    if set.is_empty() {
+        return Some(VersionReq::STAR);
+    }
+    if let Some((low, high)) = set.split_once(" - ") {
+        return VersionReq::parse(&format!(">={}, <={}", low.trim(), high.trim())).ok();
+    }
+
+    // npm separates comparators with spaces and allows one between an operator and its
+    // version (`>= 18`); `semver` wants them comma-separated and attached.
+    let mut comparators: Vec<String> = Vec::new();
+    let mut pending_operator = String::new();
+    for token in set.split_whitespace() {
+        if token
+            .chars()
+            .all(|ch| matches!(ch, '<' | '>' | '=' | '^' | '~'))
+        {
+            pending_operator.push_str(token);
+            continue;
+        }
+        comparators.push(format!(
+            "{}{}",
+            std::mem::take(&mut pending_operator),
+            token
+        ));
+    }
+    if !pending_operator.is_empty() {
+        return None;
+    }
+    VersionReq::parse(&comparators.join(", ")).ok()
 }
 
 fn extract_major(version: &str) -> Option<u64> {
@@ -176,6 +269,18 @@ fn extract_major(version: &str) -> Option<u64> {
     }
 }
 
+/// Returns the version of `name` installed for `base` when it falls outside `range`.
+///
+/// `range` uses npm range syntax. Packages that are not installed and ranges that do not
+/// parse are not reported.
+pub fn installed_version_outside_range(base: &Path, name: &str, range: &str) -> Option<String> {
+    let installed = read_installed_version(base, name)?;
+    let version = parse_version(&installed)?;
+    let requirement = parse_version_req(range)?;
+
+    (!requirement.matches(&version)).then_some(installed)
+}
+
 pub fn get_installed_dependencies_at<P: AsRef<Path>>(base: P) -> Result<HashMap<String, String>> {
     let base = base.as_ref();
     let declared = declared_dependencies(base);
@@ -232,32 +337,40 @@ pub fn plan_dependency_install(
     let workspace_package = context.workspace_package.clone();
 
     let mut env = Vec::new();
+    let yarn_flavor = (pm_kind == PackageManagerKind::Yarn).then(|| detect_yarn_flavor(&repo_root));
 
-    let (program, args, working_directory) = match pm_kind {
+    let (program, mut args, working_directory) = match pm_kind {
         PackageManagerKind::Yarn => {
+            let flavor = yarn_flavor.unwrap_or(YarnFlavor::Classic);
+            if flavor == YarnFlavor::Berry {
+                // Berry turns on immutable installs under CI, which rejects `yarn add`.
+                env.push(("YARN_ENABLE_IMMUTABLE_INSTALLS".into(), "false".into()));
+            }
+
             let mut args = Vec::new();
-            if let Some(package) = workspace_package.as_deref() {
-                args.push("workspace".into());
-                args.push(package.to_string());
-                args.push("add".into());
-                match scope {
-                    DependencyScope::Dev => args.push("--dev".into()),
-                    DependencyScope::Peer => args.push("--peer".into()),
-                    DependencyScope::Regular => {}
-                }
-                args.extend(deps_with_versions.clone());
-                ("yarn".into(), args, repo_root.clone())
+            let working_dir = if let Some(package) = workspace_package.as_deref() {
+                args.extend(["workspace".into(), package.to_string()]);
+                repo_root.clone()
             } else {
-                args.push("add".into());
-                match scope {
-                    DependencyScope::Dev => args.push("--dev".into()),
-                    DependencyScope::Peer => args.push("--peer".into()),
-                    DependencyScope::Regular => {}
-                }
-                args.extend(deps_with_versions.clone());
-                let working_dir = workspace_root.clone().unwrap_or_else(|| repo_root.clone());
-                ("yarn".into(), args, working_dir)
+                // Berry resolves the target workspace from the working directory.
+                workspace_root.clone().unwrap_or_else(|| repo_root.clone())
+            };
+            args.push("add".into());
+            match scope {
+                DependencyScope::Dev => args.push("--dev".into()),
+                DependencyScope::Peer => args.push("--peer".into()),
+                DependencyScope::Regular => {}
+            }
+            // Classic refuses to add to a workspaces root without `-W`; Berry rejects the flag.
+            if flavor == YarnFlavor::Classic
+                && workspace_package.is_none()
+                && working_dir == repo_root
+                && package_json_has_workspaces(&repo_root)
+            {
+                args.push("-W".into());
             }
+            args.extend(deps_with_versions.clone());
+            ("yarn".into(), args, working_dir)
         }
         PackageManagerKind::Pnpm => {
             let mut args = vec!["add".into()];
@@ -302,9 +415,17 @@ pub fn plan_dependency_install(
             }
             args.extend(deps_with_versions.clone());
 
-            if let Some(root) = workspace_root.as_ref() {
-                args.push("--cwd".into());
-                args.push(root.to_string_lossy().into_owned());
+            // Prefer targeting the workspace by package name, like the other managers.
+            match (workspace_package.as_deref(), workspace_root.as_ref()) {
+                (Some(package), _) => {
+                    args.push("--filter".into());
+                    args.push(package.to_string());
+                }
+                (None, Some(root)) => {
+                    args.push("--cwd".into());
+                    args.push(root.to_string_lossy().into_owned());
+                }
+                (None, None) => {}
             }
 
             if let Some(linker) = bun_install_linker(&repo_root) {
@@ -333,6 +454,22 @@ pub fn plan_dependency_install(
         }
     };
 
+    args.extend(context.extra_install_args.iter().cloned());
+
+    // Yarn Berry reads `.yarnrc.yml` only; every other manager honors `.npmrc`.
+    let npmrc = match yarn_flavor {
+        Some(YarnFlavor::Berry) => None,
+        _ => find_project_npmrc(&working_directory, &repo_root),
+    };
+    let env_remove = if let Some(npmrc) = npmrc.as_deref() {
+        // The package manager may not read the file itself when it lives above the install
+        // directory (e.g. `bun add --cwd`), so its registries are passed explicitly.
+        env.extend(npmrc_registry_env(npmrc));
+        inherited_registry_overrides(std::env::vars().map(|(key, _)| key))
+    } else {
+        Vec::new()
+    };
+
     Ok(Some(DependencyInstallPlan {
         package_manager: pm_kind,
         program,
@@ -341,19 +478,135 @@ pub fn plan_dependency_install(
         workspace_descriptor,
         dependencies: deps_with_versions,
         env,
+        env_remove,
+        npmrc,
         scope,
     }))
 }
 
+/// Nearest `.npmrc` between `working_directory` and `repo_root` (inclusive).
+fn find_project_npmrc(working_directory: &Path, repo_root: &Path) -> Option<PathBuf> {
+    for dir in working_directory.ancestors() {
+        let candidate = dir.join(".npmrc");
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if dir == repo_root {
+            break;
+        }
+    }
+    None
+}
+
+/// `registry` and `@scope:registry` entries of `npmrc` as `npm_config_*` variables. Values
+/// that rely on `${VAR}` expansion are left for the package manager to resolve.
+fn npmrc_registry_env(npmrc: &Path) -> Vec<(String, String)> {
+    let Ok(contents) = fs::read_to_string(npmrc) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && !line.starts_with(';'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(key, value)| {
+            (*key == "registry" || (key.starts_with('@') && key.ends_with(":registry")))
+                && !value.is_empty()
+                && !value.contains("${")
+        })
+        .map(|(key, value)| (format!("npm_config_{}", key), value.to_string()))
+        .collect()
+}
+
+/// `npm_config_registry` and `npm_config_@scope:registry` variables, as exported by `npx`
+/// and `npm run`. They take precedence over `.npmrc`, so a project pointing a scope at a
+/// private registry would otherwise install from the public one.
+fn inherited_registry_overrides(keys: impl Iterator<Item = String>) -> Vec<String> {
+    let mut overrides: Vec<String> = keys
+        .filter(|key| {
+            let lower = key.to_ascii_lowercase();
+            lower == "npm_config_registry"
+                || (lower.starts_with("npm_config_@") && lower.ends_with(":registry"))
+        })
+        .collect();
+    overrides.sort();
+    overrides
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum YarnFlavor {
+    Classic,
+    Berry,
+}
+
+/// Yarn 2+ (Berry) is identified by a `packageManager: "yarn@>=2"` pin, a `.yarnrc.yml`,
+/// PnP loader files or a Berry `yarn.lock`. Without any of those the installed `yarn` is
+/// asked for its version; anything else is treated as Yarn Classic.
+fn detect_yarn_flavor(repo_root: &Path) -> YarnFlavor {
+    yarn_flavor_from_project(repo_root)
+        .or_else(|| yarn_flavor_from_binary(repo_root))
+        .unwrap_or(YarnFlavor::Classic)
+}
+
+fn yarn_flavor_from_project(repo_root: &Path) -> Option<YarnFlavor> {
+    if let Some(pkg) = read_package_json(repo_root)
+        && let Some(spec) = pkg.package_manager.as_deref()
+        && let Some(version) = spec.strip_prefix("yarn@")
+        && let Some(major) = extract_major(version)
+    {
+        return Some(yarn_flavor_for_major(major));
+    }
+
+    if repo_root.join(".yarnrc.yml").exists()
+        || YARN_PNP_MARKERS
+            .iter()
+            .any(|marker| repo_root.join(marker).exists())
+    {
+        return Some(YarnFlavor::Berry);
+    }
+
+    // Classic lockfiles carry a `# yarn lockfile v1` banner; Berry's start with `__metadata`.
+    let lockfile = fs::read_to_string(repo_root.join("yarn.lock")).ok()?;
+    if lockfile.contains("yarn lockfile v1") {
+        Some(YarnFlavor::Classic)
+    } else if lockfile.contains("__metadata:") {
+        Some(YarnFlavor::Berry)
+    } else {
+        None
+    }
+}
+
+fn yarn_flavor_from_binary(repo_root: &Path) -> Option<YarnFlavor> {
+    let program = find_program("yarn")?;
+    let output = Command::new(program)
+        .arg("--version")
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    extract_major(String::from_utf8_lossy(&output.stdout).trim()).map(yarn_flavor_for_major)
+}
+
+fn yarn_flavor_for_major(major: u64) -> YarnFlavor {
+    if major >= 2 {
+        YarnFlavor::Berry
+    } else {
+        YarnFlavor::Classic
+    }
+}
+
 fn bun_install_linker(repo_root: &Path) -> Option<String> {
     const CANDIDATES: [&str; 3] = ["bunfig.toml", "bunfig.json", "bunfig"];
 
     for candidate in CANDIDATES {
         let path = repo_root.join(candidate);
-        if let Ok(contents) = fs::read_to_string(&path) {
-            if let Some(linker) = parse_bun_linker(&contents) {
-                return Some(linker);
-            }
+        if let Ok(contents) = fs::read_to_string(&path)
+            && let Some(linker) = parse_bun_linker(&contents)
+        {
+            return Some(linker);
         }
     }
 
@@ -421,15 +674,25 @@ pub fn check_project_requirements(
     let mut issues = Vec::new();
 
     for (name, required_range) in requirements {
+        if parse_version_req(required_range).is_none() {
+            issues.push(RequirementIssue {
+                name: name.clone(),
+                required: required_range.clone(),
+                installed: read_installed_version(base, name),
+                declared: declared.get(name).cloned(),
+                reason: RequirementIssueReason::InvalidRange,
+            });
+            continue;
+        }
+
         let module_path = node_module_package_json_path(base, name);
 
         if module_path.is_none() {
-            if uses_yarn_pnp {
-                if let Some(declared_spec) = declared.get(name) {
-                    if yarn_declared_satisfies(required_range, declared_spec) {
-                        continue;
-                    }
-                }
+            if uses_yarn_pnp
+                && let Some(declared_spec) = declared.get(name)
+                && yarn_declared_satisfies(required_range, declared_spec)
+            {
+                continue;
             }
 
             issues.push(RequirementIssue {
@@ -496,6 +759,49 @@ pub fn check_project_requirements(
     Ok(issues)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionRangeMerge {
+    KeepExisting,
+    UseIncoming,
+    Conflict { use_incoming: bool },
+}
+
+/// Decides which of two requested ranges for the same package should be installed.
+///
+/// When one range's minimum version satisfies the other, the higher of the two is
+/// kept. Ranges that cannot both be satisfied are reported as a conflict, preferring
+/// the range with the higher minimum so no component receives an older release than
+/// it declares. Specs that are not semver ranges (tags, workspace protocols) keep the
+/// existing value.
+pub fn merge_version_ranges(existing: &str, incoming: &str) -> VersionRangeMerge {
+    if existing.trim() == incoming.trim() {
+        return VersionRangeMerge::KeepExisting;
+    }
+
+    let (Some(existing_req), Some(incoming_req)) =
+        (parse_version_req(existing), parse_version_req(incoming))
+    else {
+        return VersionRangeMerge::KeepExisting;
+    };
+    let (Some(existing_min), Some(incoming_min)) = (
+        extract_version_from_spec(existing),
+        extract_version_from_spec(incoming),
+    ) else {
+        return VersionRangeMerge::KeepExisting;
+    };
+
+    if existing_req.matches(&incoming_min) && incoming_min > existing_min {
+        return VersionRangeMerge::UseIncoming;
+    }
+    if incoming_req.matches(&existing_min) || existing_req.matches(&incoming_min) {
+        return VersionRangeMerge::KeepExisting;
+    }
+
+    VersionRangeMerge::Conflict {
+        use_incoming: incoming_min > existing_min,
+    }
+}
+
 pub fn missing_dependencies(
     required: &HashMap<String, String>,
     installed: &HashMap<String, String>,
@@ -520,15 +826,13 @@ fn detect_yarn_pnp(base: &Path) -> bool {
         }
 
         let yarnrc = dir.join(".yarnrc.yml");
-        if yarnrc.exists() {
-            if let Ok(contents) = fs::read_to_string(&yarnrc) {
-                if contents
-                    .lines()
-                    .any(|line| line.trim().contains("nodeLinker: pnp"))
-                {
-                    return true;
-                }
-            }
+        if yarnrc.exists()
+            && let Ok(contents) = fs::read_to_string(&yarnrc)
+            && contents
+                .lines()
+                .any(|line| line.trim().contains("nodeLinker: pnp"))
+        {
+            return true;
         }
 
         current = dir.parent().map(|p| p.to_path_buf());
@@ -538,20 +842,18 @@ fn detect_yarn_pnp(base: &Path) -> bool {
 }
 
 fn yarn_declared_satisfies(required_range: &str, declared_spec: &str) -> bool {
-    if let Some(declared_version) = extract_version_from_spec(declared_spec) {
-        if let Some(required_req) = parse_version_req(required_range) {
-            if required_req.matches(&declared_version) {
-                return true;
-            }
-        }
+    if let Some(declared_version) = extract_version_from_spec(declared_spec)
+        && let Some(required_req) = parse_version_req(required_range)
+        && required_req.matches(&declared_version)
+    {
+        return true;
     }
 
-    if let Some(required_version) = extract_version_from_spec(required_range) {
-        if let Some(declared_req) = parse_version_req(declared_spec) {
-            if declared_req.matches(&required_version) {
-                return true;
-            }
-        }
+    if let Some(required_version) = extract_version_from_spec(required_range)
+        && let Some(declared_req) = parse_version_req(declared_spec)
+        && declared_req.matches(&required_version)
+    {
+        return true;
     }
 
     false
@@ -570,3 +872,363 @@ fn extract_version_from_spec(spec: &str) -> Option<Version> {
     let candidate = &numeric[..end];
     Version::parse(candidate).ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_with_react(version: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join("package.json"),
+            format!(r#"{{"dependencies":{{"react":"{}"}}}}"#, version),
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("node_modules/react")).unwrap();
+        fs::write(
+            root.join("node_modules/react/package.json"),
+            format!(r#"{{"name":"react","version":"{}"}}"#, version),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn malformed_requirement_ranges_are_invalid() {
+        let project = project_with_react("19.0.0");
+
+        for range in ["^19..0", ">>=18", "not-a-range"] {
+            let requirements = HashMap::from([("react".to_string(), range.to_string())]);
+            let issues = check_project_requirements(project.path(), &requirements).unwrap();
+            assert_eq!(issues.len(), 1, "range {range}");
+            assert_eq!(issues[0].reason, RequirementIssueReason::InvalidRange);
+            assert_eq!(issues[0].required, range);
+            assert_eq!(issues[0].installed.as_deref(), Some("19.0.0"));
+        }
+    }
+
+    #[test]
+    fn npm_range_syntax_is_valid() {
+        let project = project_with_react("19.0.0");
+
+        for range in [
+            "^18 || ^19",
+            ">=18 <20",
+            ">= 18 < 20",
+            "18.2 - 19.1",
+            "*",
+            "19.x",
+        ] {
+            let requirements = HashMap::from([("react".to_string(), range.to_string())]);
+            let issues = check_project_requirements(project.path(), &requirements).unwrap();
+            assert!(issues.is_empty(), "range {range}: {issues:?}");
+        }
+    }
+
+    #[test]
+    fn npm_ranges_are_matched() {
+        let react_19 = Version::new(19, 0, 0);
+        let cases = [
+            ("^17 || ^18", false),
+            (">=18 <19", false),
+            ("1.2 - 1.4", false),
+            ("18 - 19", true),
+            ("17.0.0 - 18.3.1", false),
+        ];
+        for (range, matches) in cases {
+            assert_eq!(
+                parse_version_req(range).unwrap().matches(&react_19),
+                matches,
+                "range {range}"
+            );
+        }
+        assert!(
+            parse_version_req("1.2 - 1.4")
+                .unwrap()
+                .matches(&Version::new(1, 4, 9))
+        );
+        assert!(parse_version_req(">=").is_none());
+    }
+
+    #[test]
+    fn installed_version_outside_npm_range_is_reported() {
+        let project = project_with_react("19.0.0");
+
+        assert_eq!(
+            installed_version_outside_range(project.path(), "react", ">=17 <19").as_deref(),
+            Some("19.0.0")
+        );
+        assert_eq!(
+            installed_version_outside_range(project.path(), "react", ">=18 <20"),
+            None
+        );
+        assert_eq!(
+            installed_version_outside_range(project.path(), "react", "^17 || ^18").as_deref(),
+            Some("19.0.0")
+        );
+    }
+
+    #[test]
+    fn malformed_range_for_missing_package_is_still_invalid() {
+        let project = project_with_react("19.0.0");
+        let requirements = HashMap::from([("tailwindcss".to_string(), "^4..1".to_string())]);
+
+        let issues = check_project_requirements(project.path(), &requirements).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].reason, RequirementIssueReason::InvalidRange);
+        assert_eq!(issues[0].installed, None);
+    }
+
+    #[test]
+    fn valid_requirement_ranges_are_checked_normally() {
+        let project = project_with_react("18.3.1");
+        let requirements = HashMap::from([
+            ("react".to_string(), "^19.0.0".to_string()),
+            ("tailwindcss".to_string(), "^4.1.0".to_string()),
+        ]);
+
+        let mut issues = check_project_requirements(project.path(), &requirements).unwrap();
+        issues.sort_by(|a, b| a.name.cmp(&b.name));
+        let reasons: Vec<_> = issues.iter().map(|issue| issue.reason.clone()).collect();
+        assert_eq!(
+            reasons,
+            [
+                RequirementIssueReason::Outdated,
+                RequirementIssueReason::Missing
+            ]
+        );
+    }
+
+    fn yarn_plan(
+        root: &Path,
+        workspace_package: Option<&str>,
+        scope: DependencyScope,
+    ) -> DependencyInstallPlan {
+        let mut context = PackageManagerContext::new(root);
+        context.package_manager = Some(PackageManagerKind::Yarn);
+        context.workspace_package = workspace_package.map(str::to_string);
+        let dependencies = HashMap::from([("react".to_string(), "^19.0.0".to_string())]);
+        plan_dependency_install(&dependencies, &context, scope)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn yarn_classic_adds_to_workspaces_root_with_w() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("package.json"), r#"{"workspaces":["apps/*"]}"#).unwrap();
+        fs::write(root.join("yarn.lock"), "# yarn lockfile v1\n").unwrap();
+
+        let plan = yarn_plan(root, None, DependencyScope::Dev);
+        assert_eq!(
+            plan.command_line(),
+            ["yarn", "add", "--dev", "-W", "react@^19.0.0"]
+        );
+        assert_eq!(plan.working_directory, root);
+        assert!(plan.env.is_empty());
+
+        let plan = yarn_plan(root, Some("web"), DependencyScope::Regular);
+        assert_eq!(
+            plan.command_line(),
+            ["yarn", "workspace", "web", "add", "react@^19.0.0"]
+        );
+    }
+
+    #[test]
+    fn yarn_berry_from_yarnrc_skips_w_and_npmrc() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("package.json"), r#"{"workspaces":["apps/*"]}"#).unwrap();
+        fs::write(root.join(".yarnrc.yml"), "nodeLinker: node-modules\n").unwrap();
+        fs::write(root.join(".npmrc"), "registry=https://example.test/\n").unwrap();
+
+        let plan = yarn_plan(root, None, DependencyScope::Dev);
+        assert_eq!(
+            plan.command_line(),
+            ["yarn", "add", "--dev", "react@^19.0.0"]
+        );
+        assert_eq!(
+            plan.env,
+            [(
+                "YARN_ENABLE_IMMUTABLE_INSTALLS".to_string(),
+                "false".to_string()
+            )]
+        );
+        assert_eq!(plan.npmrc, None);
+    }
+
+    #[test]
+    fn yarn_berry_from_package_manager_targets_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join("package.json"),
+            r#"{"packageManager":"yarn@4.1.0","workspaces":["apps/*"]}"#,
+        )
+        .unwrap();
+
+        let plan = yarn_plan(root, Some("web"), DependencyScope::Peer);
+        assert_eq!(
+            plan.command_line(),
+            ["yarn", "workspace", "web", "add", "--peer", "react@^19.0.0"]
+        );
+        assert_eq!(plan.working_directory, root);
+    }
+
+    #[test]
+    fn yarn_flavor_follows_lockfile_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("yarn.lock"), "__metadata:\n  version: 8\n").unwrap();
+        assert_eq!(yarn_flavor_from_project(root), Some(YarnFlavor::Berry));
+
+        fs::write(root.join("yarn.lock"), "# yarn lockfile v1\n").unwrap();
+        assert_eq!(yarn_flavor_from_project(root), Some(YarnFlavor::Classic));
+
+        fs::write(
+            root.join("package.json"),
+            r#"{"packageManager":"yarn@3.6.0"}"#,
+        )
+        .unwrap();
+        assert_eq!(yarn_flavor_from_project(root), Some(YarnFlavor::Berry));
+    }
+
+    fn bun_context(root: &Path) -> PackageManagerContext {
+        let mut context = PackageManagerContext::new(root);
+        context.package_manager = Some(PackageManagerKind::Bun);
+        context
+    }
+
+    #[test]
+    fn bun_targets_workspace_package_with_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut context = bun_context(dir.path());
+        context.workspace_package = Some("web".into());
+        context.workspace_root = Some(dir.path().join("apps/web"));
+        let dependencies = HashMap::from([("clsx".to_string(), "^2.1.0".to_string())]);
+
+        let plan = plan_dependency_install(&dependencies, &context, DependencyScope::Dev)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            plan.command_line(),
+            ["bun", "add", "--dev", "clsx@^2.1.0", "--filter", "web"]
+        );
+        assert_eq!(plan.working_directory, dir.path());
+    }
+
+    #[test]
+    fn bun_falls_back_to_cwd_without_a_package_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let web = dir.path().join("apps/web");
+        let mut context = bun_context(dir.path());
+        context.workspace_root = Some(web.clone());
+        let dependencies = HashMap::from([("clsx".to_string(), "^2.1.0".to_string())]);
+
+        let plan = plan_dependency_install(&dependencies, &context, DependencyScope::Regular)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            plan.command_line(),
+            [
+                "bun".to_string(),
+                "add".to_string(),
+                "clsx@^2.1.0".to_string(),
+                "--cwd".to_string(),
+                web.to_string_lossy().into_owned(),
+            ]
+        );
+        assert_eq!(plan.working_directory, dir.path());
+    }
+
+    #[test]
+    fn scoped_registries_from_npmrc_are_passed_to_the_install() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("package.json"), r#"{"workspaces":["apps/*"]}"#).unwrap();
+        fs::write(
+            root.join(".npmrc"),
+            "# private packages\n@acme:registry=https://npm.acme.dev/\n//npm.acme.dev/:_authToken=${ACME_TOKEN}\nregistry = https://registry.npmjs.org/\n@other:registry=${OTHER_REGISTRY}\n",
+        )
+        .unwrap();
+        let web = root.join("apps/web");
+        fs::create_dir_all(&web).unwrap();
+
+        let mut context = PackageManagerContext::new(root);
+        context.package_manager = Some(PackageManagerKind::Npm);
+        context.workspace_root = Some(web.clone());
+        let dependencies = HashMap::from([("@acme/icons".to_string(), "^1.2.0".to_string())]);
+
+        let plan = plan_dependency_install(&dependencies, &context, DependencyScope::Regular)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            plan.command_line(),
+            ["npm", "install", "@acme/icons@^1.2.0"]
+        );
+        assert!(!plan.args.iter().any(|arg| arg.starts_with("--registry")));
+        assert_eq!(plan.working_directory, web);
+        assert_eq!(plan.npmrc, Some(root.join(".npmrc")));
+        assert_eq!(
+            plan.env,
+            [
+                (
+                    "npm_config_@acme:registry".to_string(),
+                    "https://npm.acme.dev/".to_string()
+                ),
+                (
+                    "npm_config_registry".to_string(),
+                    "https://registry.npmjs.org/".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn nearest_npmrc_wins_and_search_stops_at_repo_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        let web = repo.join("apps/web");
+        fs::create_dir_all(&web).unwrap();
+        fs::write(
+            dir.path().join(".npmrc"),
+            "registry=https://outside.test/\n",
+        )
+        .unwrap();
+
+        assert_eq!(find_project_npmrc(&web, &repo), None);
+
+        fs::write(
+            repo.join(".npmrc"),
+            "@acme:registry=https://npm.acme.dev/\n",
+        )
+        .unwrap();
+        assert_eq!(find_project_npmrc(&web, &repo), Some(repo.join(".npmrc")));
+
+        fs::write(web.join(".npmrc"), "@acme:registry=https://web.acme.dev/\n").unwrap();
+        assert_eq!(find_project_npmrc(&web, &repo), Some(web.join(".npmrc")));
+    }
+
+    #[test]
+    fn inherited_registry_overrides_are_cleared() {
+        let keys = [
+            "PATH",
+            "npm_config_registry",
+            "npm_config_@acme:registry",
+            "NPM_CONFIG_@ACME:REGISTRY",
+            "npm_config_cache",
+        ]
+        .map(String::from);
+
+        assert_eq!(
+            inherited_registry_overrides(keys.into_iter()),
+            [
+                "NPM_CONFIG_@ACME:REGISTRY",
+                "npm_config_@acme:registry",
+                "npm_config_registry"
+            ]
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatterKind {
+    Biome,
+    Prettier,
+}
+
+impl FormatterKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FormatterKind::Biome => "biome",
+            FormatterKind::Prettier => "prettier",
+        }
+    }
+
+    fn package_name(&self) -> &'static str {
+        match self {
+            FormatterKind::Biome => "@biomejs/biome",
+            FormatterKind::Prettier => "prettier",
+        }
+    }
+
+    fn write_args(&self) -> Vec<String> {
+        match self {
+            FormatterKind::Biome => vec!["format".into(), "--write".into()],
+            FormatterKind::Prettier => vec!["--write".into()],
+        }
+    }
+}
+
+const FORMATTERS: [FormatterKind; 2] = [FormatterKind::Biome, FormatterKind::Prettier];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatPlan {
+    pub formatter: FormatterKind,
+    pub program: String,
+    pub args: Vec<String>,
+    pub working_directory: PathBuf,
+}
+
+impl FormatPlan {
+    pub fn command_line(&self) -> Vec<String> {
+        let mut line = Vec::with_capacity(1 + self.args.len());
+        line.push(self.program.clone());
+        line.extend(self.args.clone());
+        line
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .current_dir(&self.working_directory)
+            .output()
+            .with_context(|| format!("failed to spawn {}", self.formatter.as_str()))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(
+                "{} exited with status {}{}",
+                self.formatter.as_str(),
+                output.status,
+                stderr
+                    .lines()
+                    .find(|line| !line.trim().is_empty())
+                    .map(|line| format!(": {}", line.trim()))
+                    .unwrap_or_default()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Finds the formatter a project uses, preferring `package.json` scripts, then declared
+/// dependencies, then binaries in `node_modules/.bin`, searching upwards from `base`.
+pub fn detect_formatter(base: &Path) -> Option<FormatterKind> {
+    for dir in base.ancestors() {
+        if let Some(kind) = formatter_from_package_json(dir) {
+            return Some(kind);
+        }
+    }
+
+    base.ancestors().find_map(|dir| {
+        FORMATTERS
+            .into_iter()
+            .find(|kind| local_binary(dir, *kind).is_some())
+    })
+}
+
+fn formatter_from_package_json(dir: &Path) -> Option<FormatterKind> {
+    let contents = fs::read_to_string(dir.join("package.json")).ok()?;
+    let value: Value = serde_json::from_str(&contents).ok()?;
+
+    if let Some(scripts) = value.get("scripts").and_then(Value::as_object) {
+        for kind in FORMATTERS {
+            if scripts
+                .values()
+                .filter_map(Value::as_str)
+                .any(|script| script.contains(kind.as_str()))
+            {
+                return Some(kind);
+            }
+        }
+    }
+
+    FORMATTERS.into_iter().find(|kind| {
+        ["dependencies", "devDependencies"].iter().any(|section| {
+            value
+                .get(section)
+                .and_then(|deps| deps.get(kind.package_name()))
+                .is_some()
+        })
+    })
+}
+
+fn local_binary(dir: &Path, kind: FormatterKind) -> Option<PathBuf> {
+    let name = if cfg!(windows) {
+        format!("{}.cmd", kind.as_str())
+    } else {
+        kind.as_str().to_string()
+    };
+    let candidate = dir.join("node_modules").join(".bin").join(name);
+    candidate.exists().then_some(candidate)
+}
+
+/// Builds the command that formats `files` in place, run from `working_directory`.
+///
+/// Uses the nearest `node_modules/.bin` binary and falls back to `npx --no-install`.
+pub fn plan_format(
+    formatter: FormatterKind,
+    working_directory: &Path,
+    files: &[PathBuf],
+) -> Option<FormatPlan> {
+    if files.is_empty() {
+        return None;
+    }
+
+    let mut args = Vec::new();
+    let program = match working_directory
+        .ancestors()
+        .find_map(|dir| local_binary(dir, formatter))
+    {
+        Some(binary) => binary.to_string_lossy().into_owned(),
+        None => {
+            args.push("--no-install".into());
+            args.push(formatter.as_str().into());
+            "npx".into()
+        }
+    };
+    args.extend(formatter.write_args());
+    args.extend(files.iter().map(|file| file.to_string_lossy().into_owned()));
+
+    Some(FormatPlan {
+        formatter,
+        program,
+        args,
+        working_directory: working_directory.to_path_buf(),
+    })
+}
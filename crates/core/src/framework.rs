@@ -1,9 +1,12 @@
 use std::collections::HashMap;
+use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
+use crate::deps::read_installed_version;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrameworkKind {
     NextJs,
@@ -13,6 +16,18 @@ pub enum FrameworkKind {
     Unknown,
 }
 
+impl FrameworkKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FrameworkKind::NextJs => "nextjs",
+            FrameworkKind::ViteReact => "vite-react",
+            FrameworkKind::ReactRouter => "react-router",
+            FrameworkKind::TanstackStart => "tanstack-start",
+            FrameworkKind::Unknown => "unknown",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppStructure {
     AppRouter,
@@ -27,6 +42,8 @@ pub struct FrameworkDetails {
     pub has_framework_dependency: bool,
     pub app_structure: Option<AppStructure>,
     pub config_files: Vec<String>,
+    /// The app lives under `src/` (Next.js `src/app` or `src/pages`).
+    pub uses_src_dir: bool,
 }
 
 impl FrameworkDetails {
@@ -37,6 +54,7 @@ impl FrameworkDetails {
             has_framework_dependency: false,
             app_structure: None,
             config_files: Vec::new(),
+            uses_src_dir: false,
         }
     }
 }
@@ -55,6 +73,8 @@ struct PackageJson {
     dependencies: HashMap<String, String>,
     #[serde(default)]
     dev_dependencies: HashMap<String, String>,
+    #[serde(default)]
+    scripts: HashMap<String, String>,
 }
 
 fn read_package_json() -> Option<PackageJson> {
@@ -70,6 +90,16 @@ fn merge_dependencies(pkg: &PackageJson) -> HashMap<String, String> {
         .collect()
 }
 
+/// Version of the first of `packages` the project declares: the installed version from
+/// `node_modules` when available, otherwise the declared range.
+fn framework_version(deps: &HashMap<String, String>, packages: &[&str]) -> Option<String> {
+    let (name, range) = packages
+        .iter()
+        .find_map(|name| deps.get(*name).map(|range| (*name, range)))?;
+    let base = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    read_installed_version(&base, name).or_else(|| Some(range.clone()))
+}
+
 fn path_exists(path: &str) -> bool {
     Path::new(path).exists()
 }
@@ -82,14 +112,55 @@ fn find_existing_files(files: &[&str]) -> Vec<String> {
         .collect()
 }
 
+const NEXT_CONFIG_FILES: &[&str] = &[
+    "next.config.js",
+    "next.config.mjs",
+    "next.config.ts",
+    "next.config.cjs",
+];
+
+const REACT_ROUTER_CONFIG_FILES: &[&str] = &["react-router.config.ts", "react-router.config.js"];
+
+const START_CONFIG_FILES: &[&str] = &[
+    "start.config.ts",
+    "start.config.js",
+    "start.config.mts",
+    "start.config.mjs",
+    "start.config.cjs",
+];
+
+/// `vite.config.*` in the project root and the `config/`/`build/` directories where teams
+/// also keep it.
+const VITE_CONFIG_FILES: &[&str] = &[
+    "vite.config.js",
+    "vite.config.ts",
+    "vite.config.mjs",
+    "vite.config.cjs",
+    "config/vite.config.js",
+    "config/vite.config.ts",
+    "config/vite.config.mjs",
+    "config/vite.config.cjs",
+    "build/vite.config.js",
+    "build/vite.config.ts",
+    "build/vite.config.mjs",
+    "build/vite.config.cjs",
+];
+
+/// Every framework config file detection looks for, relative to the project root.
+pub fn framework_config_files() -> impl Iterator<Item = &'static str> {
+    [
+        NEXT_CONFIG_FILES,
+        VITE_CONFIG_FILES,
+        REACT_ROUTER_CONFIG_FILES,
+        START_CONFIG_FILES,
+    ]
+    .into_iter()
+    .flatten()
+    .copied()
+}
+
 fn detect_nextjs(deps: &HashMap<String, String>, has_react: bool) -> Option<FrameworkDetection> {
-    let next_config_files = [
-        "next.config.js",
-        "next.config.mjs",
-        "next.config.ts",
-        "next.config.cjs",
-    ];
-    let found_configs = find_existing_files(&next_config_files);
+    let found_configs = find_existing_files(NEXT_CONFIG_FILES);
     let has_next_dep = deps.contains_key("next");
 
     if !has_next_dep && found_configs.is_empty() {
@@ -127,21 +198,25 @@ fn detect_nextjs(deps: &HashMap<String, String>, has_react: bool) -> Option<Fram
     ];
 
     let mut app_structure = AppStructure::Unknown;
-    if app_router_paths.iter().any(|path| path_exists(path)) {
+    let mut router_path = None;
+    if let Some(path) = app_router_paths.iter().find(|path| path_exists(path)) {
         app_structure = AppStructure::AppRouter;
-    } else if pages_router_paths.iter().any(|path| path_exists(path)) {
+        router_path = Some(path);
+    } else if let Some(path) = pages_router_paths.iter().find(|path| path_exists(path)) {
         app_structure = AppStructure::PagesRouter;
+        router_path = Some(path);
     }
 
     Some(FrameworkDetection {
         framework: FrameworkKind::NextJs,
-        version: deps.get("next").cloned(),
+        version: framework_version(deps, &["next"]),
         details: FrameworkDetails {
             has_config: !found_configs.is_empty(),
             has_react_dependency: has_react,
             has_framework_dependency: has_next_dep,
             app_structure: Some(app_structure),
             config_files: found_configs,
+            uses_src_dir: router_path.is_some_and(|path| path.starts_with("src/")),
         },
     })
 }
@@ -150,8 +225,7 @@ fn detect_react_router(
     deps: &HashMap<String, String>,
     has_react: bool,
 ) -> Option<FrameworkDetection> {
-    let config_files = ["react-router.config.ts", "react-router.config.js"];
-    let found_configs = find_existing_files(&config_files);
+    let found_configs = find_existing_files(REACT_ROUTER_CONFIG_FILES);
 
     let has_react_router = deps.contains_key("react-router");
     let has_react_router_dev = deps.contains_key("@react-router/dev");
@@ -191,11 +265,10 @@ fn detect_react_router(
     }
 
     if is_framework && has_react {
-        let version = deps
-            .get("react-router")
-            .or_else(|| deps.get("@react-router/dev"))
-            .or_else(|| deps.get("@remix-run/react"))
-            .cloned();
+        let version = framework_version(
+            deps,
+            &["react-router", "@react-router/dev", "@remix-run/react"],
+        );
 
         return Some(FrameworkDetection {
             framework: FrameworkKind::ReactRouter,
@@ -208,6 +281,7 @@ fn detect_react_router(
                     || has_remix_run_react,
                 app_structure: None,
                 config_files: found_configs,
+                uses_src_dir: false,
             },
         });
     }
@@ -219,13 +293,6 @@ fn detect_tanstack_start(
     deps: &HashMap<String, String>,
     has_react: bool,
 ) -> Option<FrameworkDetection> {
-    let config_files = [
-        "start.config.ts",
-        "start.config.js",
-        "start.config.mts",
-        "start.config.mjs",
-        "start.config.cjs",
-    ];
     let start_dep_names = [
         "@tanstack/start",
         "@tanstack/start-client",
@@ -249,7 +316,7 @@ fn detect_tanstack_start(
     let has_start_dep = start_dep_names.iter().any(|name| deps.contains_key(*name));
     let has_router_dep = router_dep_names.iter().any(|name| deps.contains_key(*name));
 
-    let found_configs = find_existing_files(&config_files);
+    let found_configs = find_existing_files(START_CONFIG_FILES);
     let indicator_files = [
         "app/routes/__root.tsx",
         "app/routes/__root.ts",
@@ -295,14 +362,17 @@ fn detect_tanstack_start(
         return None;
     }
 
-    let version = deps
-        .get("@tanstack/start")
-        .or_else(|| deps.get("@tanstack/start-client"))
-        .or_else(|| deps.get("@tanstack/start-server"))
-        .or_else(|| deps.get("@tanstack/react-router"))
-        .or_else(|| deps.get("@tanstack/react-start"))
-        .or_else(|| deps.get("@tanstack/router"))
-        .cloned();
+    let version = framework_version(
+        deps,
+        &[
+            "@tanstack/start",
+            "@tanstack/start-client",
+            "@tanstack/start-server",
+            "@tanstack/react-router",
+            "@tanstack/react-start",
+            "@tanstack/router",
+        ],
+    );
 
     Some(FrameworkDetection {
         framework: FrameworkKind::TanstackStart,
@@ -313,20 +383,53 @@ fn detect_tanstack_start(
             has_framework_dependency: has_start_dep || has_router_dep,
             app_structure: None,
             config_files: found_configs,
+            uses_src_dir: false,
         },
     })
 }
 
+/// Config files passed to `vite` via `--config`/`-c` in package.json scripts.
+fn vite_configs_from_scripts(scripts: &HashMap<String, String>) -> Vec<String> {
+    let mut configs = Vec::new();
+    for script in scripts.values() {
+        let mut words = script.split_whitespace().peekable();
+        let mut in_vite = false;
+        while let Some(word) = words.next() {
+            if word == "vite" || word.ends_with("/vite") {
+                in_vite = true;
+            } else if matches!(word, "&&" | "||" | ";" | "|") {
+                in_vite = false;
+            } else if in_vite {
+                let path = match word {
+                    "--config" | "-c" => words.next(),
+                    _ => word.strip_prefix("--config="),
+                };
+                if let Some(path) = path {
+                    configs.push(
+                        path.trim_matches(['"', '\''])
+                            .trim_start_matches("./")
+                            .to_string(),
+                    );
+                }
+            }
+        }
+    }
+    configs
+}
+
 fn detect_vite_react(
     deps: &HashMap<String, String>,
+    scripts: &HashMap<String, String>,
     has_react: bool,
 ) -> Option<FrameworkDetection> {
-    let vite_config_files = [
-        "vite.config.js",
-        "vite.config.ts",
-        "vite.config.mjs",
-        "vite.config.cjs",
-    ];
+    let mut vite_config_files: Vec<String> =
+        VITE_CONFIG_FILES.iter().map(ToString::to_string).collect();
+    for config in vite_configs_from_scripts(scripts) {
+        if !vite_config_files.contains(&config) {
+            vite_config_files.push(config);
+        }
+    }
+    let vite_config_files: Vec<&str> = vite_config_files.iter().map(String::as_str).collect();
     let found_configs = find_existing_files(&vite_config_files);
 
     let has_vite = deps.contains_key("vite");
@@ -337,7 +440,12 @@ fn detect_vite_react(
     let has_vite_plugin =
         deps.contains_key("@vitejs/plugin-react") || deps.contains_key("@vitejs/plugin-react-swc");
 
-    let mut is_react_project = has_vite_plugin;
+    // The React plugin may be hoisted to a monorepo root, so also look inside the config.
+    let config_uses_react_plugin = found_configs.iter().any(|path| {
+        fs::read_to_string(path).is_ok_and(|content| content.contains("@vitejs/plugin-react"))
+    });
+
+    let mut is_react_project = has_vite_plugin || config_uses_react_plugin;
 
     if !is_react_project {
         let indicators = [
@@ -360,28 +468,30 @@ fn detect_vite_react(
         }
     }
 
-    if !is_react_project && path_exists("index.html") {
-        if let Ok(content) = fs::read_to_string("index.html") {
-            let has_root = content.contains("id=\"root\"") || content.contains("id='root'");
-            let has_vite_script = content.contains("/src/main.")
-                || content.contains("/src/index.")
-                || content.contains("type=\"module\"");
-            if has_root && has_vite_script {
-                is_react_project = true;
-            }
+    if !is_react_project
+        && path_exists("index.html")
+        && let Ok(content) = fs::read_to_string("index.html")
+    {
+        let has_root = content.contains("id=\"root\"") || content.contains("id='root'");
+        let has_vite_script = content.contains("/src/main.")
+            || content.contains("/src/index.")
+            || content.contains("type=\"module\"");
+        if has_root && has_vite_script {
+            is_react_project = true;
         }
     }
 
     if is_react_project && has_react {
         return Some(FrameworkDetection {
             framework: FrameworkKind::ViteReact,
-            version: deps.get("vite").cloned(),
+            version: framework_version(deps, &["vite"]),
             details: FrameworkDetails {
                 has_config: !found_configs.is_empty(),
                 has_react_dependency: has_react,
                 has_framework_dependency: has_vite,
                 app_structure: None,
                 config_files: found_configs,
+                uses_src_dir: false,
             },
         });
     }
@@ -427,7 +537,7 @@ pub fn detect_framework() -> FrameworkDetection {
         return detection;
     }
 
-    if let Some(detection) = detect_vite_react(&deps, has_react) {
+    if let Some(detection) = detect_vite_react(&deps, &pkg.scripts, has_react) {
         return detection;
     }
 
@@ -444,6 +554,7 @@ pub fn detect_framework() -> FrameworkDetection {
                     has_framework_dependency: false,
                     app_structure: None,
                     config_files: Vec::new(),
+                    uses_src_dir: false,
                 },
             };
         }
@@ -458,6 +569,7 @@ pub fn detect_framework() -> FrameworkDetection {
             has_framework_dependency: false,
             app_structure: None,
             config_files: Vec::new(),
+            uses_src_dir: false,
         },
     }
 }
@@ -12,30 +12,30 @@ pub fn file_exists<P: AsRef<Path>>(path: P) -> bool {
 
 pub fn ensure_parent_dir<P: AsRef<Path>>(path: P) -> io::Result<()> {
     let path = project_path(path);
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent)?;
-        }
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
     }
     Ok(())
 }
 
-pub fn write_file<P: AsRef<Path>>(path: P, contents: &str) -> io::Result<()> {
+pub fn write_file<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
     let path = project_path(path.as_ref());
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent)?;
-        }
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
     }
     fs::write(path, contents)
 }
 
 pub fn append_file<P: AsRef<Path>>(path: P, contents: &str) -> io::Result<()> {
     let path = project_path(path);
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent)?;
-        }
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
     }
     let mut file = fs::OpenOptions::new()
         .create(true)
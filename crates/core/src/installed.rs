@@ -0,0 +1,350 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::lockfile::{component_content_hash, content_hash};
+use crate::paths::component_install_path;
+use crate::registry::{RegistryClient, RegistryError};
+use crate::types::{ComponentFile, Config, Registry};
+
+/// A registry component with at least one of its files present on disk.
+#[derive(Debug, Clone)]
+pub struct InstalledComponent {
+    pub slug: String,
+    pub name: String,
+    pub files: Vec<InstalledFile>,
+}
+
+impl InstalledComponent {
+    pub fn is_modified(&self) -> bool {
+        self.files.iter().any(|file| file.modified)
+    }
+
+    /// Most recent modification time among the component's files, when the platform reports one.
+    pub fn last_modified(&self) -> Option<SystemTime> {
+        self.files.iter().filter_map(|file| file.modified_at).max()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InstalledFile {
+    /// Path of the file in the registry (`ComponentFile::path`).
+    pub registry_path: String,
+    /// Where the file was found on disk.
+    pub path: PathBuf,
+    /// `component_content_hash` of the file on disk (`content_hash` of the raw bytes for
+    /// binary assets).
+    pub content_hash: String,
+    /// Whether the registry file is a binary asset (see `ComponentFile::is_binary`).
+    pub binary: bool,
+    /// Whether the file differs from what was installed (or, without a lockfile record,
+    /// from the registry version). Only set by `mark_modified`.
+    pub modified: bool,
+    /// Filesystem modification time of the file on disk.
+    pub modified_at: Option<SystemTime>,
+}
+
+impl InstalledFile {
+    pub fn differs_from(&self, registry_content: &str) -> bool {
+        component_content_hash(registry_content) != self.content_hash
+    }
+}
+
+/// Finds the registry components installed in a single-workspace project rooted at `base`.
+pub fn detect_installed_components(
+    base: &Path,
+    config: &Config,
+    registry: &Registry,
+) -> io::Result<Vec<InstalledComponent>> {
+    scan_installed_components(registry, |slug, file| {
+        Ok::<_, io::Error>(base.join(component_install_path(&file.path, config, slug)))
+    })
+}
+
+/// Like `detect_installed_components`, with `locate` deciding where each registry file of
+/// a component would be written (e.g. across linked workspaces). Components are sorted by slug.
+pub fn scan_installed_components<E, F>(
+    registry: &Registry,
+    mut locate: F,
+) -> Result<Vec<InstalledComponent>, E>
+where
+    E: From<io::Error>,
+    F: FnMut(&str, &ComponentFile) -> Result<PathBuf, E>,
+{
+    let mut slugs: Vec<_> = registry.components.keys().collect();
+    slugs.sort();
+
+    let mut installed = Vec::new();
+    for slug in slugs {
+        let component = &registry.components[slug];
+        let mut files = Vec::new();
+        for file in &component.files {
+            let path = locate(slug, file)?;
+            if !path.exists() {
+                continue;
+            }
+
+            let binary = file.is_binary();
+            let hash = if binary {
+                content_hash(fs::read(&path)?)
+            } else {
+                component_content_hash(&fs::read_to_string(&path)?)
+            };
+            let modified_at = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            files.push(InstalledFile {
+                registry_path: file.path.clone(),
+                content_hash: hash,
+                binary,
+                path,
+                modified: false,
+                modified_at,
+            });
+        }
+
+        if !files.is_empty() {
+            installed.push(InstalledComponent {
+                slug: slug.clone(),
+                name: component.name.clone(),
+                files,
+            });
+        }
+    }
+
+    Ok(installed)
+}
+
+/// Sets `InstalledFile::modified`. Files with a lockfile hash from `recorded` are compared
+/// with it on disk alone; only the others are fetched and compared with their registry
+/// content after `normalize` (the import rewriting applied at install time). Files the
+/// registry no longer serves are left unmodified.
+pub async fn mark_modified<R, N>(
+    client: &RegistryClient,
+    components: &mut [InstalledComponent],
+    recorded: R,
+    normalize: N,
+) -> Result<(), RegistryError>
+where
+    R: Fn(&InstalledFile) -> Option<String>,
+    N: Fn(&InstalledFile, &str) -> String,
+{
+    for file in components
+        .iter_mut()
+        .flat_map(|component| &mut component.files)
+    {
+        if let Some(hash) = recorded(file) {
+            file.modified = hash != file.content_hash;
+            continue;
+        }
+
+        if file.binary {
+            match client.fetch_component_file_bytes(&file.registry_path).await {
+                Ok(bytes) => file.modified = content_hash(bytes) != file.content_hash,
+                Err(RegistryError::ComponentNotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+            continue;
+        }
+
+        match client.fetch_component_file(&file.registry_path).await {
+            Ok(content) => file.modified = file.differs_from(&normalize(file, &content)),
+            Err(RegistryError::ComponentNotFound(_)) => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{RegistryBundle, RegistryOptions};
+
+    const REGISTRY: &str = r#"{
+      "name": "nocta",
+      "version": "1.0.0",
+      "components": {
+        "card": {
+          "name": "Card",
+          "description": "",
+          "category": "layout",
+          "files": [{ "name": "card.tsx", "path": "components/card.tsx", "type": "component" }]
+        },
+        "button": {
+          "name": "Button",
+          "description": "",
+          "category": "form",
+          "files": [{ "name": "button.tsx", "path": "components/button.tsx", "type": "component" }]
+        },
+        "dialog": {
+          "name": "Dialog",
+          "description": "",
+          "category": "overlay",
+          "files": [{ "name": "dialog.tsx", "path": "components/dialog.tsx", "type": "component" }]
+        }
+      },
+      "categories": {}
+    }"#;
+
+    fn config() -> Config {
+        serde_json::from_str(
+            r#"{
+              "style": "default",
+              "tailwind": { "css": "app/globals.css" },
+              "aliases": { "components": "components/ui", "utils": "lib/utils" }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn project(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, contents) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    /// Serves `button` and `card` with `= 1` contents; `dialog` is not in the bundle.
+    fn bundle_client() -> RegistryClient {
+        let bundle = format!(
+            r#"{{
+              "registry": {},
+              "components": {{
+                "components/button.tsx": "ZXhwb3J0IGNvbnN0IEJ1dHRvbiA9IDE7Cg==",
+                "components/card.tsx": "ZXhwb3J0IGNvbnN0IENhcmQgPSAxOwo="
+              }}
+            }}"#,
+            REGISTRY
+        );
+        RegistryClient::from_bundle(
+            "https://registry.test",
+            RegistryBundle::parse(&bundle).unwrap(),
+            RegistryOptions::default(),
+        )
+    }
+
+    #[test]
+    fn detects_components_with_files_on_disk() {
+        let registry: Registry = serde_json::from_str(REGISTRY).unwrap();
+        let dir = project(&[
+            ("components/ui/card.tsx", "export const Card = 1;\n"),
+            ("components/ui/button.tsx", "export const Button = 1;\r\n"),
+        ]);
+
+        let installed = detect_installed_components(dir.path(), &config(), &registry).unwrap();
+
+        let slugs: Vec<_> = installed.iter().map(|c| c.slug.as_str()).collect();
+        assert_eq!(slugs, ["button", "card"]);
+        let button = &installed[0];
+        assert_eq!(button.name, "Button");
+        assert_eq!(button.files.len(), 1);
+        assert_eq!(button.files[0].registry_path, "components/button.tsx");
+        assert_eq!(
+            button.files[0].path,
+            dir.path().join("components/ui/button.tsx")
+        );
+        assert_eq!(
+            button.files[0].content_hash,
+            component_content_hash("export const Button = 1;")
+        );
+        assert!(!button.is_modified());
+        assert!(button.last_modified().is_some());
+    }
+
+    #[test]
+    fn scan_uses_the_located_paths() {
+        let registry: Registry = serde_json::from_str(REGISTRY).unwrap();
+        let dir = project(&[("web/dialog.tsx", "export const Dialog = 1;\n")]);
+
+        let installed = scan_installed_components(&registry, |slug, _| {
+            Ok::<_, io::Error>(dir.path().join("web").join(format!("{}.tsx", slug)))
+        })
+        .unwrap();
+
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].slug, "dialog");
+    }
+
+    #[test]
+    fn differs_from_ignores_line_endings() {
+        let registry: Registry = serde_json::from_str(REGISTRY).unwrap();
+        let dir = project(&[("components/ui/card.tsx", "export const Card = 1;\n")]);
+        let installed = detect_installed_components(dir.path(), &config(), &registry).unwrap();
+        let file = &installed[0].files[0];
+
+        assert!(!file.differs_from("export const Card = 1;\r\n"));
+        assert!(file.differs_from("export const Card = 2;\n"));
+    }
+
+    #[tokio::test]
+    async fn mark_modified_compares_with_registry_content() {
+        let client = bundle_client();
+        let registry: Registry = serde_json::from_str(REGISTRY).unwrap();
+        let dir = project(&[
+            ("components/ui/button.tsx", "export const Button = 1;\n"),
+            ("components/ui/card.tsx", "export const Card = 2;\n"),
+            ("components/ui/dialog.tsx", "export const Dialog = 2;\n"),
+        ]);
+        let mut installed = detect_installed_components(dir.path(), &config(), &registry).unwrap();
+
+        mark_modified(
+            &client,
+            &mut installed,
+            |_| None,
+            |_, content| content.to_string(),
+        )
+        .await
+        .unwrap();
+
+        let modified: Vec<_> = installed
+            .iter()
+            .map(|component| (component.slug.as_str(), component.is_modified()))
+            .collect();
+        // `dialog` is not served by the bundle, so it cannot be compared.
+        assert_eq!(
+            modified,
+            [("button", false), ("card", true), ("dialog", false)]
+        );
+    }
+
+    #[tokio::test]
+    async fn mark_modified_prefers_recorded_hashes() {
+        let client = bundle_client();
+        let registry: Registry = serde_json::from_str(REGISTRY).unwrap();
+        let dir = project(&[
+            ("components/ui/button.tsx", "export const Button = 2;\n"),
+            ("components/ui/card.tsx", "export const Card = 2;\n"),
+            ("components/ui/dialog.tsx", "export const Dialog = 2;\n"),
+        ]);
+        let mut installed = detect_installed_components(dir.path(), &config(), &registry).unwrap();
+
+        // `card` was installed as `= 2`, so it is unmodified even though the
+        // registry serves `= 1`; `dialog` has no record and the registry lacks it.
+        mark_modified(
+            &client,
+            &mut installed,
+            |file| match file.registry_path.as_str() {
+                "components/button.tsx" => Some(component_content_hash("export const Button = 1;")),
+                "components/card.tsx" => Some(component_content_hash("export const Card = 2;")),
+                _ => None,
+            },
+            |_, content| content.to_string(),
+        )
+        .await
+        .unwrap();
+
+        let modified: Vec<_> = installed
+            .iter()
+            .map(|component| (component.slug.as_str(), component.is_modified()))
+            .collect();
+        assert_eq!(
+            modified,
+            [("button", true), ("card", false), ("dialog", false)]
+        );
+    }
+}
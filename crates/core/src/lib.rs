@@ -2,13 +2,17 @@ pub mod cache;
 pub mod config;
 pub mod constants;
 pub mod deps;
+pub mod formatter;
 pub mod framework;
 pub mod fs;
+pub mod installed;
+pub mod lockfile;
 pub mod paths;
 pub mod registry;
 pub mod rollback;
 pub mod tailwind;
+pub mod trace;
 pub mod types;
 pub mod workspace;
 
-pub use registry::{RegistryClient, RegistryComponent, RegistryError};
+pub use registry::{RegistryBundle, RegistryClient, RegistryComponent, RegistryError, RegistrySet};
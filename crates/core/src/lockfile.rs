@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::config::ensure_parent_dir;
+
+pub const LOCK_FILE_NAME: &str = "nocta.lock.json";
+pub const LOCKFILE_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum LockfileError {
+    #[error("failed to read lockfile: {0}")]
+    Read(io::Error),
+    #[error("failed to parse lockfile: {0}")]
+    Parse(serde_json::Error),
+    #[error("failed to serialize lockfile: {0}")]
+    Serialize(serde_json::Error),
+    #[error("failed to write lockfile: {0}")]
+    Write(io::Error),
+}
+
+/// Record of every component installed into a project, keyed by `[@registry/]slug`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Lockfile {
+    pub lockfile_version: u32,
+    #[serde(default)]
+    pub components: BTreeMap<String, LockedComponent>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            lockfile_version: LOCKFILE_VERSION,
+            components: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedComponent {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    pub registry_version: String,
+    #[serde(default)]
+    pub files: Vec<LockedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedFile {
+    /// Path relative to the directory containing the lockfile, using `/` separators.
+    pub path: String,
+    pub hash: String,
+}
+
+impl Lockfile {
+    pub fn record(&mut self, key: String, component: LockedComponent) {
+        self.components.insert(key, component);
+    }
+
+    pub fn component(&self, key: &str) -> Option<&LockedComponent> {
+        self.components.get(key)
+    }
+}
+
+pub fn lock_key(registry: Option<&str>, slug: &str) -> String {
+    match registry {
+        Some(registry) => format!("@{}/{}", registry, slug),
+        None => slug.to_string(),
+    }
+}
+
+pub fn content_hash(content: impl AsRef<[u8]>) -> String {
+    let digest = Sha256::digest(content.as_ref());
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("sha256:{}", hex)
+}
+
+/// Hash of an installed component file, as recorded in the lockfile and compared when
+/// detecting local modifications. Pass the content after import normalization (the form
+/// `add` writes). A BOM, CRLF line endings and trailing newlines are ignored so checkouts
+/// with `core.autocrlf` or a formatter's final newline hash the same as the registry copy.
+pub fn component_content_hash(content: &str) -> String {
+    content_hash(normalize_component_text(content))
+}
+
+/// Whether `recorded` (a lockfile hash) is the `component_content_hash` of `content`.
+pub fn component_hash_matches(recorded: &str, content: &str) -> bool {
+    component_content_hash(content) == recorded
+}
+
+fn normalize_component_text(content: &str) -> String {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    content
+        .replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+/// Whether a component locked at registry version `locked` is older than `current`.
+/// Versions that are not semver count as behind whenever they differ.
+pub fn registry_version_behind(locked: &str, current: &str) -> bool {
+    let parse = |version: &str| Version::parse(version.trim().trim_start_matches('v')).ok();
+    match (parse(locked), parse(current)) {
+        (Some(locked), Some(current)) => locked < current,
+        _ => locked.trim() != current.trim(),
+    }
+}
+
+pub fn read_lockfile_from<P: AsRef<Path>>(path: P) -> Result<Option<Lockfile>, LockfileError> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let data = fs::read_to_string(path).map_err(LockfileError::Read)?;
+    if data.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let lockfile = serde_json::from_str::<Lockfile>(&data).map_err(LockfileError::Parse)?;
+    Ok(Some(lockfile))
+}
+
+pub fn write_lockfile_to<P: AsRef<Path>>(
+    path: P,
+    lockfile: &Lockfile,
+) -> Result<(), LockfileError> {
+    let path = path.as_ref();
+    ensure_parent_dir(path).map_err(LockfileError::Write)?;
+
+    let mut json = serde_json::to_string_pretty(lockfile).map_err(LockfileError::Serialize)?;
+    json.push('\n');
+    fs::write(path, json).map_err(LockfileError::Write)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_hash_is_sha256() {
+        assert_eq!(
+            content_hash(""),
+            "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            content_hash("abc"),
+            "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn component_hash_ignores_bom_crlf_and_trailing_newlines() {
+        let expected = content_hash("abc");
+        assert_eq!(component_content_hash("abc"), expected);
+        assert_eq!(component_content_hash("abc\n\n"), expected);
+        assert_eq!(component_content_hash("\u{feff}abc\r\n"), expected);
+        assert_ne!(component_content_hash("abd"), expected);
+    }
+
+    #[test]
+    fn component_hash_is_pinned() {
+        assert_eq!(
+            component_content_hash("export const Button = 1;\r\n"),
+            "sha256:7618a534311e45a04803dfbf709b51c8b11a20fec335a69aba7866e9cc604129"
+        );
+        assert!(component_hash_matches(&content_hash("abc"), "abc\n"));
+        assert!(!component_hash_matches(&content_hash("abc"), "abd"));
+    }
+}
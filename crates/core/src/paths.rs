@@ -1,3 +1,4 @@
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 
 use crate::types::Config;
@@ -5,6 +6,7 @@ use crate::types::Config;
 pub fn resolve_component_path(component_file_path: &str, config: &Config) -> PathBuf {
     let mut relative = component_file_path.trim_start_matches("./");
     relative = relative.trim_start_matches('/');
+    relative = strip_configured_prefixes(relative, config);
     relative = strip_known_prefixes(relative);
 
     let base_path = config.aliases.components.filesystem_path();
@@ -32,6 +34,71 @@ pub fn resolve_component_path(component_file_path: &str, config: &Config) -> Pat
     }
 }
 
+/// Where a registry file for component `slug` is written, relative to the workspace root.
+///
+/// Files nested in a folder named after the component (`components/<slug>/…`) are
+/// flattened into the components directory.
+///
+/// With `components.filenameCase` set, the file name of files in the components directory
+/// is converted to that case.
+pub fn component_install_path(component_file_path: &str, config: &Config, slug: &str) -> PathBuf {
+    let relative_path = resolve_component_path(component_file_path, config);
+    let relative_path =
+        flatten_relative_path_for_slug(&relative_path, config, slug).unwrap_or(relative_path);
+    apply_filename_case(relative_path, config)
+}
+
+fn apply_filename_case(relative_path: PathBuf, config: &Config) -> PathBuf {
+    let Some(case) = config.filename_case() else {
+        return relative_path;
+    };
+    let base = Path::new(config.aliases.components.filesystem_path());
+    if !relative_path.starts_with(base) {
+        return relative_path;
+    }
+    match relative_path.file_name().and_then(OsStr::to_str) {
+        Some(name) => relative_path.with_file_name(case.apply(name)),
+        None => relative_path,
+    }
+}
+
+fn flatten_relative_path_for_slug(
+    relative_path: &Path,
+    config: &Config,
+    slug: &str,
+) -> Option<PathBuf> {
+    let base = Path::new(config.aliases.components.filesystem_path());
+    let stripped = relative_path.strip_prefix(base).ok()?;
+    let mut components = stripped.components();
+    let first = components.next()?;
+
+    if first.as_os_str() != OsStr::new(slug) {
+        return None;
+    }
+
+    let remainder: PathBuf = components.collect();
+    if remainder.as_os_str().is_empty() {
+        return None;
+    }
+
+    Some(base.join(remainder))
+}
+
+/// Strips the first matching `strip_prefixes` entry from a registry path.
+fn strip_configured_prefixes<'a>(path: &'a str, config: &Config) -> &'a str {
+    config
+        .strip_prefixes
+        .iter()
+        .flatten()
+        .map(|prefix| prefix.trim_start_matches("./").trim_matches('/'))
+        .filter(|prefix| !prefix.is_empty())
+        .find_map(|prefix| {
+            path.strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix('/'))
+        })
+        .unwrap_or(path)
+}
+
 fn strip_known_prefixes(path: &str) -> &str {
     let mut current = path;
     for prefix in ["app/", "src/"] {
@@ -56,8 +123,7 @@ fn trim_alias_suffix<'a>(relative: &'a str, alias_suffix: &str) -> &'a str {
 
     if relative == alias_suffix {
         ""
-    } else if relative.starts_with(alias_suffix) {
-        let remainder = &relative[alias_suffix.len()..];
+    } else if let Some(remainder) = relative.strip_prefix(alias_suffix) {
         if remainder.starts_with('/') {
             remainder.trim_start_matches('/')
         } else {
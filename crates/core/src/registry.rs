@@ -1,20 +1,27 @@
-use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::rc::Rc;
 use std::sync::Arc;
 use std::time::Duration;
 
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use crc32fast::Hasher as Crc32Hasher;
+use flate2::read::GzDecoder;
 use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
 use reqwest::{Client, Error as ReqwestError, StatusCode};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use url::Url;
 
 use crate::cache;
 use crate::constants::registry as registry_constants;
-use crate::types::{CategoryInfo, Component, Registry};
+use crate::trace::{TraceRecord, append_trace};
+use crate::types::{CategoryInfo, Component, Registry, RegistryRequirements, RegistryStyle};
 
 #[derive(Debug, Clone)]
 struct ComponentManifest {
@@ -55,6 +62,74 @@ impl ComponentManifest {
     }
 }
 
+/// The registry's components archive, kept as downloaded. Entries are extracted on their
+/// first lookup and remembered, so installing a few components never unpacks the rest.
+#[derive(Debug)]
+struct ComponentArchive {
+    /// `componentsArchive` as declared by the registry, for error messages.
+    path: String,
+    bytes: Vec<u8>,
+    extracted: RefCell<HashMap<String, Option<Vec<u8>>>>,
+}
+
+impl ComponentArchive {
+    fn new(path: String, bytes: Vec<u8>) -> Self {
+        Self {
+            path,
+            bytes,
+            extracted: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Contents of `requested_path`, matched like `ComponentManifest::lookup`: by full
+    /// path, else by file name against entries at the archive root.
+    fn lookup(&self, requested_path: &str) -> Result<Option<Vec<u8>>, RegistryError> {
+        let key = normalize_manifest_key(requested_path);
+        if let Some(contents) = self.extracted.borrow().get(&key) {
+            return Ok(contents.clone());
+        }
+
+        let contents = self
+            .extract(&key)
+            .map_err(|err| RegistryError::AssetParse(self.path.clone(), err.to_string()))?;
+        self.extracted.borrow_mut().insert(key, contents.clone());
+        Ok(contents)
+    }
+
+    /// Streams through the tar archive (gunzipping it first when compressed) and reads only
+    /// the entry for `key`.
+    fn extract(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        let reader: Box<dyn Read + '_> = if self.bytes.starts_with(&[0x1f, 0x8b]) {
+            Box::new(GzDecoder::new(self.bytes.as_slice()))
+        } else {
+            // Already inflated by a `Content-Encoding: gzip` response.
+            Box::new(self.bytes.as_slice())
+        };
+
+        let file_name = key.rsplit('/').next().unwrap_or(key);
+        let mut fallback = None;
+        for entry in tar::Archive::new(reader).entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let path = normalize_manifest_key(&entry.path()?.to_string_lossy());
+            let exact = path == key;
+            if !exact && (fallback.is_some() || path.contains('/') || path != file_name) {
+                continue;
+            }
+
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents)?;
+            if exact {
+                return Ok(Some(contents));
+            }
+            fallback = Some(contents);
+        }
+        Ok(fallback)
+    }
+}
+
 fn normalize_manifest_key(path: &str) -> String {
     path.replace('\\', "/")
         .trim_start_matches("./")
@@ -62,22 +137,29 @@ fn normalize_manifest_key(path: &str) -> String {
         .to_string()
 }
 
-fn default_registry_ttl() -> Duration {
-    Duration::from_millis(
-        env::var(registry_constants::CACHE_TTL_ENV)
-            .ok()
-            .and_then(|value| value.parse().ok())
-            .unwrap_or(registry_constants::DEFAULT_CACHE_TTL_MS),
-    )
+fn env_ttl(name: &str) -> Option<Duration> {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .map(Duration::from_millis)
 }
 
-fn default_asset_ttl() -> Duration {
-    Duration::from_millis(
-        env::var(registry_constants::ASSET_CACHE_TTL_ENV)
-            .ok()
-            .and_then(|value| value.parse().ok())
-            .unwrap_or(registry_constants::DEFAULT_ASSET_CACHE_TTL_MS),
-    )
+/// Registry manifest TTL: env var, then the project-configured value, then the default.
+pub fn registry_ttl(configured: Option<Duration>) -> Duration {
+    env_ttl(registry_constants::CACHE_TTL_ENV)
+        .or(configured)
+        .unwrap_or(Duration::from_millis(
+            registry_constants::DEFAULT_CACHE_TTL_MS,
+        ))
+}
+
+/// Asset TTL: env var, then the project-configured value, then the default.
+pub fn asset_ttl(configured: Option<Duration>) -> Duration {
+    env_ttl(registry_constants::ASSET_CACHE_TTL_ENV)
+        .or(configured)
+        .unwrap_or(Duration::from_millis(
+            registry_constants::DEFAULT_ASSET_CACHE_TTL_MS,
+        ))
 }
 
 #[derive(Debug, Error)]
@@ -92,6 +174,16 @@ pub enum RegistryError {
     Decode(String, String),
     #[error("failed to parse registry asset `{0}`: {1}")]
     AssetParse(String, String),
+    #[error("registry `{0}` is not configured in nocta.config.json")]
+    UnknownRegistry(String),
+    #[error("`{0}` is not included in the registry bundle")]
+    NotInBundle(String),
+    #[error("component dependency cycle: {0}")]
+    DependencyCycle(String),
+    #[error("failed to read local override `{0}`: {1}")]
+    LocalOverride(String, String),
+    #[error("failed to create HTTP client: {0}")]
+    Client(String),
 }
 
 fn map_network_error(err: ReqwestError) -> RegistryError {
@@ -104,12 +196,100 @@ fn cache_namespace_for(base_url: &str) -> String {
     format!("registry/{:08x}", hasher.finalize())
 }
 
+/// Cache key for a `components.json` fetched from an override URL, so switching manifest
+/// hosts never serves the previous host's copy.
+fn manifest_override_cache_path(url: &str) -> String {
+    let mut hasher = Crc32Hasher::new();
+    hasher.update(url.trim().as_bytes());
+    format!(
+        "assets/manifests/{:08x}/{}",
+        hasher.finalize(),
+        registry_constants::COMPONENTS_MANIFEST
+    )
+}
+
+/// Resolves `path` against a registry base URL.
+///
+/// Path segments are appended to the base path, so `https://cdn.example.com/r` and
+/// `https://cdn.example.com/r/` behave alike, and the base query string is kept. A base
+/// whose query ends in an empty parameter (`https://cdn.example.com/r?path=`) receives
+/// `path` as that parameter's value instead. Bases that are not absolute URLs are joined
+/// with a plain `/`.
+fn join_registry_url(base: &str, path: &str) -> String {
+    let path = path.trim_start_matches('/');
+    let fallback = || format!("{}/{}", base.trim_end_matches('/'), path);
+    let Ok(mut url) = Url::parse(base.trim()) else {
+        return fallback();
+    };
+
+    if let Some(query) = url.query().filter(|query| query.ends_with('=')) {
+        let query = format!("{}{}", query, path);
+        url.set_query(Some(&query));
+        return url.into();
+    }
+
+    match url.path_segments_mut() {
+        Ok(mut segments) => {
+            segments
+                .pop_if_empty()
+                .extend(path.split('/').filter(|segment| !segment.is_empty()));
+        }
+        Err(()) => return fallback(),
+    }
+    url.into()
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
-struct HttpCacheMetadata {
+pub(crate) struct HttpCacheMetadata {
     etag: Option<String>,
     last_modified: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct RawRegistryBundle {
+    registry: serde_json::Value,
+    components: serde_json::Value,
+    #[serde(default)]
+    assets: HashMap<String, String>,
+}
+
+/// A pre-fetched registry served entirely from memory, for environments without network
+/// access. Parsed from a JSON object holding the `registry` (`registry.json`) and
+/// `components` (`components.json`) documents plus optional raw `assets` keyed by path.
+#[derive(Debug, Clone)]
+pub struct RegistryBundle {
+    registry: String,
+    assets: HashMap<String, String>,
+}
+
+impl RegistryBundle {
+    pub fn parse(text: &str) -> Result<Self, RegistryError> {
+        let raw: RawRegistryBundle =
+            serde_json::from_str(text).map_err(|err| RegistryError::Parse(err.to_string()))?;
+        let mut assets: HashMap<String, String> = raw
+            .assets
+            .into_iter()
+            .map(|(path, contents)| (normalize_manifest_key(&path), contents))
+            .collect();
+        assets.insert(
+            registry_constants::COMPONENTS_MANIFEST.into(),
+            raw.components.to_string(),
+        );
+
+        Ok(Self {
+            registry: raw.registry.to_string(),
+            assets,
+        })
+    }
+
+    fn asset(&self, path: &str) -> Result<String, RegistryError> {
+        self.assets
+            .get(&normalize_manifest_key(path))
+            .cloned()
+            .ok_or_else(|| RegistryError::NotInBundle(path.to_string()))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RegistrySummary {
     pub name: String,
@@ -119,44 +299,175 @@ pub struct RegistrySummary {
 
 #[derive(Debug, Clone)]
 pub struct RegistryComponent {
+    /// Named registry the component came from; `None` for the default registry.
+    pub registry: Option<String>,
     pub slug: String,
     pub component: Component,
 }
 
+/// Per-invocation behaviour shared by every registry client.
+#[derive(Debug, Clone, Default)]
+pub struct RegistryOptions {
+    /// Skip fresh cache entries and always revalidate with the registry.
+    pub force_refresh: bool,
+    /// Serve any cached entry, however stale, and only use the network on a cache miss.
+    pub prefer_offline: bool,
+    /// Maximum cache age accepted for registry and asset responses, overriding the TTL envs.
+    pub max_age: Option<Duration>,
+    /// Registry manifest TTL from `cache.registryTtlMs` in nocta.config.json.
+    pub registry_ttl: Option<Duration>,
+    /// Asset TTL from `cache.assetTtlMs` in nocta.config.json.
+    pub asset_ttl: Option<Duration>,
+    /// Directory whose files replace registry component files at the same relative path.
+    pub local_dir: Option<PathBuf>,
+    /// Accept invalid TLS certificates (self-signed mirrors). Disables server verification.
+    pub accept_invalid_certs: bool,
+    /// File that receives one JSON trace record per registry request (`--trace-file`).
+    pub trace_file: Option<PathBuf>,
+    /// Style from `style` in nocta.config.json; selects per-style component file variants.
+    pub style: Option<String>,
+    /// Absolute URL of `components.json` when it is served apart from `registry.json`
+    /// (`--components-manifest`). Ignored for bundles.
+    pub components_manifest_url: Option<String>,
+}
+
+impl RegistryOptions {
+    fn effective_registry_ttl(&self) -> Duration {
+        self.max_age
+            .unwrap_or_else(|| registry_ttl(self.registry_ttl))
+    }
+
+    fn effective_asset_ttl(&self) -> Duration {
+        self.max_age.unwrap_or_else(|| asset_ttl(self.asset_ttl))
+    }
+}
+
+/// Counts how registry fetches were served during a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub network: u64,
+    pub revalidated: u64,
+    pub stale_fallbacks: u64,
+}
+
+impl CacheStats {
+    pub fn total(&self) -> u64 {
+        self.hits + self.network + self.revalidated + self.stale_fallbacks
+    }
+}
+
 pub struct RegistryClient {
     client: Client,
     base_url: String,
     cache_namespace: String,
+    options: RegistryOptions,
+    stats: Rc<Cell<CacheStats>>,
     components_manifest: RefCell<Option<Arc<ComponentManifest>>>,
+    components_archive: RefCell<Option<Rc<ComponentArchive>>>,
     registry_cache: RefCell<Option<(String, Registry)>>,
+    /// Style variant paths that turned out not to exist, so they are not requested again.
+    style_variant_misses: RefCell<HashSet<String>>,
+    bundle: Option<Arc<RegistryBundle>>,
 }
 
 impl RegistryClient {
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be built; use [`RegistryClient::try_new`] to
+    /// handle that case.
     pub fn new(base_url: impl Into<String>) -> Self {
+        Self::try_new(base_url).expect("failed to create HTTP client")
+    }
+
+    pub fn try_new(base_url: impl Into<String>) -> Result<Self, RegistryError> {
+        Self::try_with_options(base_url, RegistryOptions::default())
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be built; use [`RegistryClient::try_with_options`]
+    /// to handle that case.
+    pub fn with_options(base_url: impl Into<String>, options: RegistryOptions) -> Self {
+        Self::try_with_options(base_url, options).expect("failed to create HTTP client")
+    }
+
+    pub fn try_with_options(
+        base_url: impl Into<String>,
+        options: RegistryOptions,
+    ) -> Result<Self, RegistryError> {
         let base_url = base_url.into();
-        Self {
-            client: Client::new(),
+        // Compressed responses are decoded before they reach the cache.
+        let client = Client::builder()
+            .gzip(true)
+            .brotli(true)
+            .deflate(true)
+            .danger_accept_invalid_certs(options.accept_invalid_certs)
+            .build()
+            .map_err(|err| RegistryError::Client(err.to_string()))?;
+        Ok(Self {
+            client,
             cache_namespace: cache_namespace_for(&base_url),
             base_url,
+            options,
+            stats: Rc::new(Cell::new(CacheStats::default())),
             components_manifest: RefCell::new(None),
+            components_archive: RefCell::new(None),
             registry_cache: RefCell::new(None),
-        }
+            style_variant_misses: RefCell::new(HashSet::new()),
+            bundle: None,
+        })
     }
 
-    fn base_url(&self) -> &str {
+    /// A client that serves every request from `bundle` and never touches the network
+    /// or the HTTP cache. `base_url` is only used for display and lockfile entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be built; use [`RegistryClient::try_from_bundle`]
+    /// to handle that case.
+    pub fn from_bundle(
+        base_url: impl Into<String>,
+        bundle: RegistryBundle,
+        options: RegistryOptions,
+    ) -> Self {
+        Self::try_from_bundle(base_url, bundle, options).expect("failed to create HTTP client")
+    }
+
+    pub fn try_from_bundle(
+        base_url: impl Into<String>,
+        bundle: RegistryBundle,
+        options: RegistryOptions,
+    ) -> Result<Self, RegistryError> {
+        let mut client = Self::try_with_options(base_url, options)?;
+        client.bundle = Some(Arc::new(bundle));
+        Ok(client)
+    }
+
+    pub fn options(&self) -> &RegistryOptions {
+        &self.options
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.stats.get()
+    }
+
+    fn record_stat(&self, update: impl FnOnce(&mut CacheStats)) {
+        let mut stats = self.stats.get();
+        update(&mut stats);
+        self.stats.set(stats);
+    }
+
+    pub fn base_url(&self) -> &str {
         self.base_url.trim_end_matches('/')
     }
 
     fn registry_url(&self) -> String {
-        format!(
-            "{}/{}",
-            self.base_url(),
-            registry_constants::REGISTRY_MANIFEST
-        )
+        join_registry_url(self.base_url(), registry_constants::REGISTRY_MANIFEST)
     }
 
     fn asset_url(&self, asset: &str) -> String {
-        format!("{}/{}", self.base_url(), asset.trim_start_matches('/'))
+        join_registry_url(self.base_url(), asset)
     }
 
     fn namespaced_path(&self, rel_path: &str) -> String {
@@ -167,15 +478,20 @@ impl RegistryClient {
         )
     }
 
-    fn read_cache(&self, path: &str, ttl: Duration, accept_stale: bool) -> Option<String> {
-        match cache::read_cache_text(path, Some(ttl), accept_stale) {
-            Ok(Some(text)) => Some(text),
+    fn read_cache(&self, path: &str, ttl: Duration, accept_stale: bool) -> Option<Vec<u8>> {
+        match cache::read_cache_bytes(path, Some(ttl), accept_stale) {
+            Ok(Some(bytes)) => Some(bytes),
             _ => None,
         }
     }
 
-    fn write_cache(&self, path: &str, contents: &str) {
-        let _ = cache::write_cache_text(path, contents);
+    fn read_stale_cache(&self, path: &str, ttl: Duration) -> Option<Vec<u8>> {
+        let _lock = cache::lock_cache_shared().ok();
+        self.read_cache(path, ttl, true)
+    }
+
+    fn write_cache(&self, path: &str, contents: &[u8]) {
+        let _ = cache::write_cache_bytes(path, contents);
     }
 
     fn load_cache_metadata(&self, cache_path: &str) -> HttpCacheMetadata {
@@ -202,26 +518,74 @@ impl RegistryClient {
         cache_relative: &str,
         ttl: Duration,
     ) -> Result<String, RegistryError> {
+        let bytes = self
+            .fetch_bytes_with_cache(url, cache_relative, ttl)
+            .await?;
+        String::from_utf8(bytes)
+            .map_err(|err| RegistryError::Decode(url.to_string(), err.to_string()))
+    }
+
+    async fn fetch_bytes_with_cache(
+        &self,
+        url: &str,
+        cache_relative: &str,
+        ttl: Duration,
+    ) -> Result<Vec<u8>, RegistryError> {
         let cache_path = self.namespaced_path(cache_relative);
+        let mut trace = TraceRecord::new(url, &cache_path);
+        let result = self
+            .fetch_bytes_traced(url, &cache_path, ttl, &mut trace)
+            .await;
 
-        if let Some(fresh) = self.read_cache(&cache_path, ttl, false) {
-            return Ok(fresh);
+        if let Some(trace_file) = self.options.trace_file.as_deref() {
+            match &result {
+                Ok(bytes) => trace.bytes = Some(bytes.len()),
+                Err(err) => {
+                    trace.decision = "error";
+                    trace.error = Some(err.to_string());
+                }
+            }
+            append_trace(trace_file, &trace);
         }
+        result
+    }
 
-        let metadata = self.load_cache_metadata(&cache_path);
+    async fn fetch_bytes_traced(
+        &self,
+        url: &str,
+        cache_path: &str,
+        ttl: Duration,
+        trace: &mut TraceRecord,
+    ) -> Result<Vec<u8>, RegistryError> {
+        let metadata = {
+            let _lock = cache::lock_cache_shared().ok();
+            if !self.options.force_refresh
+                && let Some(cached) = self.read_cache(cache_path, ttl, self.options.prefer_offline)
+            {
+                self.record_stat(|stats| stats.hits += 1);
+                trace.decision = "hit";
+                return Ok(cached);
+            }
+            self.load_cache_metadata(cache_path)
+        };
         let mut request = self.client.get(url);
         if let Some(etag) = &metadata.etag {
             request = request.header(IF_NONE_MATCH, etag);
+            trace.header(IF_NONE_MATCH.as_str(), etag);
         }
         if let Some(last_modified) = &metadata.last_modified {
             request = request.header(IF_MODIFIED_SINCE, last_modified);
+            trace.header(IF_MODIFIED_SINCE.as_str(), last_modified);
         }
 
         match request.send().await {
             Ok(response) => {
                 let status = response.status();
+                trace.status = Some(status.as_u16());
                 if status == StatusCode::NOT_MODIFIED {
-                    if let Some(cached) = self.read_cache(&cache_path, ttl, true) {
+                    if let Some(cached) = self.read_stale_cache(cache_path, ttl) {
+                        self.record_stat(|stats| stats.revalidated += 1);
+                        trace.decision = "revalidated";
                         return Ok(cached);
                     }
 
@@ -231,7 +595,9 @@ impl RegistryClient {
                 }
 
                 if !status.is_success() {
-                    if let Some(cached) = self.read_cache(&cache_path, ttl, true) {
+                    if let Some(cached) = self.read_stale_cache(cache_path, ttl) {
+                        self.record_stat(|stats| stats.stale_fallbacks += 1);
+                        trace.decision = "staleFallback";
                         return Ok(cached);
                     }
                     return Err(RegistryError::Network(format!(
@@ -251,20 +617,26 @@ impl RegistryClient {
                     .and_then(|value| value.to_str().ok())
                     .map(|value| value.to_string());
 
-                match response.text().await {
+                match response.bytes().await {
                     Ok(body) => {
-                        self.write_cache(&cache_path, &body);
+                        let body = body.to_vec();
+                        let _lock = cache::lock_cache_exclusive().ok();
+                        self.write_cache(cache_path, &body);
                         self.store_cache_metadata(
-                            &cache_path,
+                            cache_path,
                             HttpCacheMetadata {
                                 etag,
                                 last_modified,
                             },
                         );
+                        self.record_stat(|stats| stats.network += 1);
+                        trace.decision = "network";
                         Ok(body)
                     }
                     Err(err) => {
-                        if let Some(cached) = self.read_cache(&cache_path, ttl, true) {
+                        if let Some(cached) = self.read_stale_cache(cache_path, ttl) {
+                            self.record_stat(|stats| stats.stale_fallbacks += 1);
+                            trace.decision = "staleFallback";
                             Ok(cached)
                         } else {
                             Err(RegistryError::Network(err.to_string()))
@@ -273,7 +645,9 @@ impl RegistryClient {
                 }
             }
             Err(err) => {
-                if let Some(cached) = self.read_cache(&cache_path, ttl, true) {
+                if let Some(cached) = self.read_stale_cache(cache_path, ttl) {
+                    self.record_stat(|stats| stats.stale_fallbacks += 1);
+                    trace.decision = "staleFallback";
                     Ok(cached)
                 } else {
                     Err(map_network_error(err))
@@ -283,17 +657,21 @@ impl RegistryClient {
     }
 
     pub async fn fetch_registry(&self) -> Result<Registry, RegistryError> {
-        let body = self
-            .fetch_with_cache(
-                &self.registry_url(),
-                registry_constants::CACHE_PATH,
-                default_registry_ttl(),
-            )
-            .await?;
-        if let Some((cached_body, registry)) = self.registry_cache.borrow().as_ref() {
-            if cached_body == &body {
-                return Ok(registry.clone());
+        let body = match &self.bundle {
+            Some(bundle) => bundle.registry.clone(),
+            None => {
+                self.fetch_with_cache(
+                    &self.registry_url(),
+                    registry_constants::CACHE_PATH,
+                    self.options.effective_registry_ttl(),
+                )
+                .await?
             }
+        };
+        if let Some((cached_body, registry)) = self.registry_cache.borrow().as_ref()
+            && cached_body == &body
+        {
+            return Ok(registry.clone());
         }
 
         let registry = serde_json::from_str::<Registry>(&body)
@@ -321,11 +699,31 @@ impl RegistryClient {
         Ok(registry.categories)
     }
 
-    pub async fn registry_requirements(&self) -> Result<HashMap<String, String>, RegistryError> {
+    pub async fn registry_requirements(&self) -> Result<RegistryRequirements, RegistryError> {
         let registry = self.fetch_registry().await?;
         Ok(registry.requirements)
     }
 
+    pub async fn styles(&self) -> Result<Vec<RegistryStyle>, RegistryError> {
+        let registry = self.fetch_registry().await?;
+        Ok(registry.styles)
+    }
+
+    /// Path of the CSS token bundle for `style`, falling back to the shared bundle when the
+    /// registry does not declare the style or gives it no bundle of its own.
+    pub async fn css_bundle_path(&self, style: &str) -> Result<String, RegistryError> {
+        let path = self
+            .registry_field(|registry| {
+                registry
+                    .styles
+                    .iter()
+                    .find(|candidate| candidate.name == style)
+                    .and_then(|candidate| candidate.css.clone())
+            })
+            .await?;
+        Ok(path.unwrap_or_else(|| registry_constants::CSS_BUNDLE_PATH.to_string()))
+    }
+
     pub async fn fetch_component(&self, name: &str) -> Result<Component, RegistryError> {
         let registry = self.fetch_registry().await?;
         registry
@@ -335,69 +733,30 @@ impl RegistryClient {
             .ok_or_else(|| RegistryError::ComponentNotFound(name.to_string()))
     }
 
+    /// Resolves `component` plus its dependencies from this registry, dependencies first.
+    /// `registryDependencies` on other registries fail with `UnknownRegistry`.
+    #[deprecated(
+        since = "0.3.1",
+        note = "use `RegistrySet::fetch_component_with_dependencies`, which also resolves named registries"
+    )]
     pub async fn fetch_component_with_dependencies(
         &self,
         component: &str,
     ) -> Result<Vec<RegistryComponent>, RegistryError> {
-        let registry = self.fetch_registry().await?;
-        let mut ordered = Vec::new();
-        let mut visiting = HashSet::new();
-        let mut visited = HashSet::new();
-
-        self.collect_component_with_dependencies(
-            &registry.components,
-            component,
-            &mut visiting,
-            &mut visited,
-            &mut ordered,
-        )?;
-
-        Ok(ordered)
-    }
-
-    fn collect_component_with_dependencies(
-        &self,
-        components: &HashMap<String, Component>,
-        component: &str,
-        visiting: &mut HashSet<String>,
-        visited: &mut HashSet<String>,
-        ordered: &mut Vec<RegistryComponent>,
-    ) -> Result<(), RegistryError> {
-        if visited.contains(component) {
-            return Ok(());
-        }
-
-        if !visiting.insert(component.to_string()) {
-            return Ok(());
-        }
-
-        let current = components
-            .get(component)
-            .ok_or_else(|| RegistryError::ComponentNotFound(component.to_string()))?;
-
-        if !current.internal_dependencies.is_empty() {
-            for dep in &current.internal_dependencies {
-                self.collect_component_with_dependencies(
-                    components, dep, visiting, visited, ordered,
-                )?;
-            }
-        }
-
-        visiting.remove(component);
-        visited.insert(component.to_string());
-        ordered.push(RegistryComponent {
-            slug: component.to_string(),
-            component: current.clone(),
-        });
-        Ok(())
+        RegistrySet::try_new(self, None)?
+            .fetch_component_with_dependencies(None, component)
+            .await
     }
 
     pub async fn fetch_registry_asset(&self, asset_path: &str) -> Result<String, RegistryError> {
         let normalized = asset_path.trim_start_matches('/');
+        if let Some(bundle) = &self.bundle {
+            return bundle.asset(normalized);
+        }
         let url = self.asset_url(normalized);
         let cache_path = format!("assets/{}", normalized);
-        self.fetch_with_cache(&url, &cache_path, default_asset_ttl())
-            .await
+        let ttl = self.options.effective_asset_ttl();
+        self.fetch_with_cache(&url, &cache_path, ttl).await
     }
 
     async fn load_components_manifest(&self) -> Result<Arc<ComponentManifest>, RegistryError> {
@@ -405,9 +764,20 @@ impl RegistryClient {
             return Ok(Arc::clone(manifest));
         }
 
-        let manifest_text = self
-            .fetch_registry_asset(registry_constants::COMPONENTS_MANIFEST)
-            .await?;
+        let manifest_text = match self.options.components_manifest_url.as_deref() {
+            Some(url) if self.bundle.is_none() => {
+                self.fetch_with_cache(
+                    url,
+                    &manifest_override_cache_path(url),
+                    self.options.effective_asset_ttl(),
+                )
+                .await?
+            }
+            _ => {
+                self.fetch_registry_asset(registry_constants::COMPONENTS_MANIFEST)
+                    .await?
+            }
+        };
         let manifest: HashMap<String, String> =
             serde_json::from_str(&manifest_text).map_err(|err| {
                 RegistryError::AssetParse(
@@ -421,7 +791,155 @@ impl RegistryClient {
         Ok(manifest)
     }
 
+    /// Downloads the registry's `componentsArchive`, if it declares one. Bundles never carry
+    /// archives since their assets are text.
+    async fn load_components_archive(&self) -> Result<Option<Rc<ComponentArchive>>, RegistryError> {
+        if let Some(archive) = self.components_archive.borrow().as_ref() {
+            return Ok(Some(Rc::clone(archive)));
+        }
+        if self.bundle.is_some() {
+            return Ok(None);
+        }
+        let Some(archive_path) = self
+            .registry_field(|registry| registry.components_archive.clone())
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let normalized = archive_path.trim_start_matches('/');
+        let bytes = self
+            .fetch_bytes_with_cache(
+                &self.asset_url(normalized),
+                &format!("assets/{}", normalized),
+                self.options.effective_asset_ttl(),
+            )
+            .await?;
+        let archive = Rc::new(ComponentArchive::new(archive_path, bytes));
+        self.components_archive.replace(Some(Rc::clone(&archive)));
+        Ok(Some(archive))
+    }
+
+    async fn files_base(&self) -> Result<Option<String>, RegistryError> {
+        self.registry_field(|registry| registry.files_base.clone())
+            .await
+    }
+
+    async fn registry_field<T>(
+        &self,
+        field: impl FnOnce(&Registry) -> Option<T>,
+    ) -> Result<Option<T>, RegistryError> {
+        if self.registry_cache.borrow().is_none() {
+            self.fetch_registry().await?;
+        }
+        Ok(self
+            .registry_cache
+            .borrow()
+            .as_ref()
+            .and_then(|(_, registry)| field(registry)))
+    }
+
+    /// Registry path of the configured style's variant of `path`. Only styles after the
+    /// first can have variants, since the first style is what the shared files implement.
+    /// Styles that list their `variants` only have those; otherwise any file may have one,
+    /// unless an earlier lookup found it missing.
+    async fn style_variant_path(&self, path: &str) -> Result<Option<String>, RegistryError> {
+        let Some(style) = self.options.style.as_deref() else {
+            return Ok(None);
+        };
+        let key = normalize_manifest_key(path);
+        let has_variant = self
+            .registry_field(|registry| {
+                let declared = registry
+                    .styles
+                    .iter()
+                    .skip(1)
+                    .find(|candidate| candidate.name == style)?;
+                Some(declared.variants.as_ref().is_none_or(|variants| {
+                    variants
+                        .iter()
+                        .any(|variant| normalize_manifest_key(variant) == key)
+                }))
+            })
+            .await?
+            .unwrap_or(false);
+        let variant = format!("styles/{}/{}", style, key);
+        if !has_variant || self.style_variant_misses.borrow().contains(&variant) {
+            return Ok(None);
+        }
+        Ok(Some(variant))
+    }
+
+    /// Fetches a component source file, preferring the configured style's variant and
+    /// then the registry's per-file endpoint (see `Registry::files_base`) so the full
+    /// components manifest is only downloaded when that route is unavailable.
     pub async fn fetch_component_file(&self, path: &str) -> Result<String, RegistryError> {
+        if let Some(bytes) = self.read_local_override(path)? {
+            return String::from_utf8(bytes)
+                .map_err(|err| RegistryError::LocalOverride(path.to_string(), err.to_string()));
+        }
+
+        if let Some(variant) = self.style_variant_path(path).await? {
+            match self.fetch_registry_file(&variant).await {
+                Err(RegistryError::ComponentNotFound(_)) => {
+                    self.style_variant_misses.borrow_mut().insert(variant);
+                }
+                result => return result,
+            }
+        }
+        self.fetch_registry_file(path).await
+    }
+
+    async fn fetch_registry_file(&self, path: &str) -> Result<String, RegistryError> {
+        if let Some(base) = self.files_base().await? {
+            let direct = format!(
+                "{}/{}",
+                base.trim_matches('/'),
+                normalize_manifest_key(path)
+            );
+            match self.fetch_registry_asset(&direct).await {
+                Ok(contents) => return Ok(contents),
+                Err(RegistryError::Network(_) | RegistryError::NotInBundle(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        let bytes = self.fetch_registry_file_bytes(path).await?;
+        String::from_utf8(bytes).map_err(|err| {
+            RegistryError::Decode(
+                path.to_string(),
+                format!("{} (binary files must be fetched as bytes)", err),
+            )
+        })
+    }
+
+    /// Fetches a component file as raw bytes, for binary assets such as fonts and images.
+    ///
+    /// Served from the registry's components archive when it declares one, otherwise from
+    /// the base64 components manifest; the per-file endpoint only carries text.
+    pub async fn fetch_component_file_bytes(&self, path: &str) -> Result<Vec<u8>, RegistryError> {
+        if let Some(bytes) = self.read_local_override(path)? {
+            return Ok(bytes);
+        }
+
+        if let Some(variant) = self.style_variant_path(path).await? {
+            match self.fetch_registry_file_bytes(&variant).await {
+                Err(RegistryError::ComponentNotFound(_)) => {
+                    self.style_variant_misses.borrow_mut().insert(variant);
+                }
+                result => return result,
+            }
+        }
+        self.fetch_registry_file_bytes(path).await
+    }
+
+    async fn fetch_registry_file_bytes(&self, path: &str) -> Result<Vec<u8>, RegistryError> {
+        if let Some(archive) = self.load_components_archive().await?
+            && let Some(bytes) = archive.lookup(path)?
+        {
+            return Ok(bytes);
+        }
+
         let manifest = self.load_components_manifest().await?;
         let encoded = manifest
             .lookup(path)
@@ -431,9 +949,524 @@ impl RegistryClient {
         BASE64_STANDARD
             .decode(encoded)
             .map_err(|err| RegistryError::Decode(path.to_string(), err.to_string()))
-            .and_then(|bytes| {
-                String::from_utf8(bytes)
-                    .map_err(|err| RegistryError::Decode(path.to_string(), err.to_string()))
+    }
+
+    /// Reads `path` from the local override directory (`RegistryOptions::local_dir`),
+    /// returning `None` when no override is configured or the file does not exist there.
+    fn read_local_override(&self, path: &str) -> Result<Option<Vec<u8>>, RegistryError> {
+        let Some(dir) = self.options.local_dir.as_ref() else {
+            return Ok(None);
+        };
+        let key = normalize_manifest_key(path);
+        // Registry paths are relative; never let one escape the override directory.
+        if key.split('/').any(|segment| segment == "..") {
+            return Ok(None);
+        }
+
+        let candidate = dir.join(&key);
+        if !candidate.is_file() {
+            return Ok(None);
+        }
+        fs::read(&candidate)
+            .map(Some)
+            .map_err(|err| RegistryError::LocalOverride(path.to_string(), err.to_string()))
+    }
+}
+
+/// Splits a `@<registry>/<slug>` request into its registry name and slug.
+pub fn split_scoped_slug(input: &str) -> (Option<&str>, &str) {
+    if let Some(scoped) = input.strip_prefix('@')
+        && let Some((registry, slug)) = scoped.split_once('/')
+        && !registry.is_empty()
+        && !slug.is_empty()
+    {
+        return (Some(registry), slug);
+    }
+
+    (None, input)
+}
+
+/// The default registry client plus any named registries declared in config.
+pub struct RegistrySet<'a> {
+    default: &'a RegistryClient,
+    named: HashMap<String, RegistryClient>,
+}
+
+impl<'a> RegistrySet<'a> {
+    /// # Panics
+    ///
+    /// Panics if an HTTP client cannot be built; use [`RegistrySet::try_new`] to handle
+    /// that case.
+    pub fn new(default: &'a RegistryClient, registries: Option<&BTreeMap<String, String>>) -> Self {
+        Self::try_new(default, registries).expect("failed to create HTTP client")
+    }
+
+    pub fn try_new(
+        default: &'a RegistryClient,
+        registries: Option<&BTreeMap<String, String>>,
+    ) -> Result<Self, RegistryError> {
+        let named = registries
+            .map(|entries| {
+                entries
+                    .iter()
+                    .map(|(name, url)| {
+                        // The manifest override belongs to the default registry only.
+                        let options = RegistryOptions {
+                            components_manifest_url: None,
+                            ..default.options.clone()
+                        };
+                        let mut client = RegistryClient::try_with_options(url.clone(), options)?;
+                        client.stats = Rc::clone(&default.stats);
+                        Ok((name.clone(), client))
+                    })
+                    .collect::<Result<_, RegistryError>>()
             })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Self { default, named })
+    }
+
+    pub fn client(&self, name: Option<&str>) -> Result<&RegistryClient, RegistryError> {
+        match name {
+            None => Ok(self.default),
+            Some(name) => self
+                .named
+                .get(name)
+                .ok_or_else(|| RegistryError::UnknownRegistry(name.to_string())),
+        }
+    }
+
+    /// Resolves `component` from `registry` plus its internal and cross-registry
+    /// dependencies, ordered so every dependency precedes its dependents.
+    pub async fn fetch_component_with_dependencies(
+        &self,
+        registry: Option<&str>,
+        component: &str,
+    ) -> Result<Vec<RegistryComponent>, RegistryError> {
+        enum Step {
+            Visit(ComponentKey),
+            Emit(ComponentKey, Box<Component>),
+        }
+
+        let mut registries: HashMap<Option<String>, Registry> = HashMap::new();
+        let mut ordered = Vec::new();
+        // Components whose dependencies are still being resolved, outermost first.
+        let mut visiting: Vec<ComponentKey> = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![Step::Visit((
+            registry.map(str::to_string),
+            component.to_string(),
+        ))];
+
+        while let Some(step) = stack.pop() {
+            let key = match step {
+                Step::Emit(key, component) => {
+                    visiting.pop();
+                    visited.insert(key.clone());
+                    ordered.push(RegistryComponent {
+                        registry: key.0,
+                        slug: key.1,
+                        component: *component,
+                    });
+                    continue;
+                }
+                Step::Visit(key) => key,
+            };
+
+            // Shared dependencies resolved through another path are fine; reaching a
+            // component that is still being resolved means the registry has a cycle.
+            if visited.contains(&key) {
+                continue;
+            }
+            if let Some(start) = visiting.iter().position(|entry| entry == &key) {
+                let cycle: Vec<String> = visiting[start..]
+                    .iter()
+                    .chain(std::iter::once(&key))
+                    .map(qualified_slug)
+                    .collect();
+                return Err(RegistryError::DependencyCycle(cycle.join(" -> ")));
+            }
+
+            if !registries.contains_key(&key.0) {
+                let fetched = self.client(key.0.as_deref())?.fetch_registry().await?;
+                registries.insert(key.0.clone(), fetched);
+            }
+            let current = registries[&key.0]
+                .components
+                .get(&key.1)
+                .cloned()
+                .ok_or_else(|| RegistryError::ComponentNotFound(qualified_slug(&key)))?;
+
+            let mut dependencies: Vec<ComponentKey> = current
+                .internal_dependencies
+                .iter()
+                .map(|dep| (key.0.clone(), dep.clone()))
+                .collect();
+            dependencies.extend(current.registry_dependencies.iter().map(|dep| {
+                let (scope, slug) = split_scoped_slug(dep);
+                (scope.map(str::to_string), slug.to_string())
+            }));
+
+            visiting.push(key.clone());
+            stack.push(Step::Emit(key, Box::new(current)));
+            stack.extend(dependencies.into_iter().rev().map(Step::Visit));
+        }
+
+        Ok(ordered)
+    }
+}
+
+type ComponentKey = (Option<String>, String);
+
+fn qualified_slug((registry, slug): &ComponentKey) -> String {
+    match registry {
+        Some(registry) => format!("@{}/{}", registry, slug),
+        None => slug.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_registry_url_appends_to_subpaths() {
+        assert_eq!(
+            join_registry_url("https://cdn.example.com/r", "registry.json"),
+            "https://cdn.example.com/r/registry.json"
+        );
+        assert_eq!(
+            join_registry_url(
+                "https://cdn.example.com/a/b",
+                "/files/components/button.tsx"
+            ),
+            "https://cdn.example.com/a/b/files/components/button.tsx"
+        );
+        assert_eq!(
+            join_registry_url("https://cdn.example.com", "registry.json"),
+            "https://cdn.example.com/registry.json"
+        );
+    }
+
+    #[test]
+    fn join_registry_url_ignores_trailing_slashes() {
+        assert_eq!(
+            join_registry_url("https://cdn.example.com/r/", "registry.json"),
+            "https://cdn.example.com/r/registry.json"
+        );
+        assert_eq!(
+            join_registry_url("https://cdn.example.com/", "registry.json"),
+            "https://cdn.example.com/registry.json"
+        );
+    }
+
+    #[test]
+    fn join_registry_url_keeps_query_components() {
+        assert_eq!(
+            join_registry_url("https://cdn.example.com/r?token=abc", "registry.json"),
+            "https://cdn.example.com/r/registry.json?token=abc"
+        );
+        assert_eq!(
+            join_registry_url("https://cdn.example.com/r?path=", "components/button.tsx"),
+            "https://cdn.example.com/r?path=components/button.tsx"
+        );
+    }
+
+    #[test]
+    fn join_registry_url_falls_back_for_relative_bases() {
+        assert_eq!(
+            join_registry_url("./registry/", "registry.json"),
+            "./registry/registry.json"
+        );
+    }
+
+    /// A bundle-backed client with a `default` and a `rounded` style, using `style`.
+    /// `rounded` ships a variant of `button.tsx` only and lists `variants` when given.
+    fn client_with_styles(style: &str, variants: Option<&[&str]>) -> RegistryClient {
+        let bundle = serde_json::json!({
+            "registry": {
+                "name": "test",
+                "version": "1.0.0",
+                "components": {},
+                "categories": {},
+                "styles": [
+                    { "name": "default" },
+                    { "name": "rounded", "css": "css/rounded.css", "variants": variants },
+                ],
+            },
+            "components": {
+                "components/button.tsx": BASE64_STANDARD.encode("shared button"),
+                "components/card.tsx": BASE64_STANDARD.encode("shared card"),
+                "styles/rounded/components/button.tsx": BASE64_STANDARD.encode("rounded button"),
+                "styles/default/components/card.tsx": BASE64_STANDARD.encode("default card"),
+            },
+        });
+        let bundle = RegistryBundle::parse(&bundle.to_string()).unwrap();
+        let options = RegistryOptions {
+            style: Some(style.to_string()),
+            ..RegistryOptions::default()
+        };
+        RegistryClient::from_bundle("https://registry.test", bundle, options)
+    }
+
+    #[tokio::test]
+    async fn style_variants_replace_shared_files() {
+        let client = client_with_styles("rounded", None);
+        assert_eq!(
+            client
+                .fetch_component_file("components/button.tsx")
+                .await
+                .unwrap(),
+            "rounded button"
+        );
+        assert_eq!(
+            client
+                .fetch_component_file("components/card.tsx")
+                .await
+                .unwrap(),
+            "shared card"
+        );
+        assert_eq!(
+            client
+                .fetch_component_file_bytes("components/card.tsx")
+                .await
+                .unwrap(),
+            b"shared card"
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_style_variants_are_not_requested_again() {
+        let client = client_with_styles("rounded", None);
+        assert_eq!(
+            client
+                .style_variant_path("components/card.tsx")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("styles/rounded/components/card.tsx")
+        );
+        client
+            .fetch_component_file("components/card.tsx")
+            .await
+            .unwrap();
+        assert_eq!(
+            client
+                .style_variant_path("components/card.tsx")
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn declared_style_variants_limit_the_lookups() {
+        let client = client_with_styles("rounded", Some(&["components/button.tsx"]));
+        assert_eq!(
+            client
+                .style_variant_path("components/card.tsx")
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            client
+                .fetch_component_file("./components/button.tsx")
+                .await
+                .unwrap(),
+            "rounded button"
+        );
+    }
+
+    #[tokio::test]
+    async fn the_first_style_uses_the_shared_files() {
+        let client = client_with_styles("default", None);
+        assert_eq!(
+            client
+                .style_variant_path("components/card.tsx")
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            client
+                .fetch_component_file("components/card.tsx")
+                .await
+                .unwrap(),
+            "shared card"
+        );
+    }
+
+    #[tokio::test]
+    async fn css_bundle_path_falls_back_to_the_shared_bundle() {
+        let client = client_with_styles("rounded", None);
+        assert_eq!(
+            client.css_bundle_path("rounded").await.unwrap(),
+            "css/rounded.css"
+        );
+        assert_eq!(
+            client.css_bundle_path("default").await.unwrap(),
+            registry_constants::CSS_BUNDLE_PATH
+        );
+        assert_eq!(
+            client.css_bundle_path("unknown").await.unwrap(),
+            registry_constants::CSS_BUNDLE_PATH
+        );
+    }
+
+    /// A bundle-backed client whose components depend on each other as listed.
+    fn client_with_graph(graph: &[(&str, &[&str])]) -> RegistryClient {
+        let components: serde_json::Map<String, serde_json::Value> = graph
+            .iter()
+            .map(|(slug, deps)| {
+                let component = serde_json::json!({
+                    "name": slug,
+                    "description": "",
+                    "category": "test",
+                    "files": [],
+                    "internalDependencies": deps,
+                });
+                (slug.to_string(), component)
+            })
+            .collect();
+        let bundle = serde_json::json!({
+            "registry": {
+                "name": "test",
+                "version": "1.0.0",
+                "components": components,
+                "categories": {},
+            },
+            "components": {},
+        });
+        let bundle = RegistryBundle::parse(&bundle.to_string()).unwrap();
+        RegistryClient::from_bundle("https://registry.test", bundle, RegistryOptions::default())
+    }
+
+    async fn resolve(client: &RegistryClient, slug: &str) -> Result<Vec<String>, RegistryError> {
+        let set = RegistrySet::try_new(client, None)?;
+        let resolved = set.fetch_component_with_dependencies(None, slug).await?;
+        Ok(resolved.into_iter().map(|entry| entry.slug).collect())
+    }
+
+    #[tokio::test]
+    async fn diamond_dependencies_resolve_once_before_dependents() {
+        let client = client_with_graph(&[
+            ("dialog", &["button", "popover"]),
+            ("button", &["slot"]),
+            ("popover", &["slot"]),
+            ("slot", &[]),
+        ]);
+
+        assert_eq!(
+            resolve(&client, "dialog").await.unwrap(),
+            ["slot", "button", "popover", "dialog"]
+        );
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn client_resolution_matches_the_registry_set() {
+        let client = client_with_graph(&[
+            ("dialog", &["button"]),
+            ("button", &["slot"]),
+            ("slot", &[]),
+        ]);
+
+        let slugs: Vec<String> = client
+            .fetch_component_with_dependencies("dialog")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.slug)
+            .collect();
+        assert_eq!(slugs, resolve(&client, "dialog").await.unwrap());
+        assert_eq!(slugs, ["slot", "button", "dialog"]);
+    }
+
+    #[tokio::test]
+    async fn dependency_cycle_is_reported() {
+        let client = client_with_graph(&[
+            ("dialog", &["button"]),
+            ("button", &["popover"]),
+            ("popover", &["button"]),
+        ]);
+
+        match resolve(&client, "dialog").await {
+            Err(RegistryError::DependencyCycle(cycle)) => {
+                assert_eq!(cycle, "button -> popover -> button");
+            }
+            other => panic!("expected a dependency cycle, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn self_dependency_is_a_cycle() {
+        let client = client_with_graph(&[("button", &["button"])]);
+
+        assert!(matches!(
+            resolve(&client, "button").await,
+            Err(RegistryError::DependencyCycle(cycle)) if cycle == "button -> button"
+        ));
+    }
+
+    fn archive(entries: &[(&str, &str)]) -> ComponentArchive {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        for (path, contents) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, contents.as_bytes())
+                .unwrap();
+        }
+        let bytes = builder.into_inner().unwrap().finish().unwrap();
+        ComponentArchive::new("components.tar.gz".into(), bytes)
+    }
+
+    #[test]
+    fn archive_extracts_only_requested_entries() {
+        let archive = archive(&[
+            ("./components/button.tsx", "button"),
+            ("components/card.tsx", "card"),
+            ("dialog.tsx", "dialog"),
+        ]);
+
+        assert_eq!(
+            archive.lookup("/components/button.tsx").unwrap().as_deref(),
+            Some(b"button".as_slice())
+        );
+        assert_eq!(
+            archive
+                .lookup("components/ui/dialog.tsx")
+                .unwrap()
+                .as_deref(),
+            Some(b"dialog".as_slice())
+        );
+        assert_eq!(archive.lookup("components/missing.tsx").unwrap(), None);
+
+        let mut extracted: Vec<_> = archive.extracted.borrow().keys().cloned().collect();
+        extracted.sort();
+        assert_eq!(
+            extracted,
+            [
+                "components/button.tsx",
+                "components/missing.tsx",
+                "components/ui/dialog.tsx"
+            ]
+        );
+    }
+
+    #[test]
+    fn corrupt_archive_fails_on_lookup() {
+        let archive = ComponentArchive::new("components.tar.gz".into(), vec![0x1f, 0x8b, 0]);
+
+        assert!(matches!(
+            archive.lookup("components/button.tsx"),
+            Err(RegistryError::AssetParse(path, _)) if path == "components.tar.gz"
+        ));
     }
 }
@@ -4,6 +4,11 @@ use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
+/// Deletes every path in `paths` (directories recursively), ignoring failures.
+#[deprecated(
+    since = "0.3.1",
+    note = "deletes paths without restoring previous contents; snapshot files before writing and restore them instead"
+)]
 pub fn rollback_changes<P: AsRef<Path>>(paths: &[P]) -> Result<()> {
     let mut unique = HashSet::new();
     for path in paths {
@@ -5,7 +5,6 @@ use std::process::Command;
 
 use anyhow::{Context, Result};
 
-use crate::constants::registry::CSS_BUNDLE_PATH;
 use crate::fs as project_fs;
 use crate::registry::RegistryClient;
 
@@ -25,13 +24,20 @@ fn css_full_path(css_path: &str) -> PathBuf {
     current_dir().join(css_path)
 }
 
+fn is_tailwind_import(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with("@import")
+        && (trimmed.contains("\"tailwindcss\"") || trimmed.contains("'tailwindcss'"))
+}
+
+fn contains_tailwind_import(css: &str) -> bool {
+    css.lines().any(is_tailwind_import)
+}
+
 fn strip_tailwind_import(snippet: &str) -> String {
     snippet
         .lines()
-        .filter(|line| {
-            let trimmed = line.trim();
-            !(trimmed.starts_with("@import") && trimmed.contains("tailwindcss"))
-        })
+        .filter(|line| !is_tailwind_import(line))
         .collect::<Vec<_>>()
         .join("\n")
         .trim_start_matches('\n')
@@ -96,12 +102,17 @@ fn insert_snippet(existing: &str, snippet: &str) -> String {
     result
 }
 
-pub async fn add_design_tokens_to_css(registry: &RegistryClient, css_path: &str) -> Result<bool> {
+pub async fn add_design_tokens_to_css(
+    registry: &RegistryClient,
+    css_path: &str,
+    style: &str,
+) -> Result<bool> {
     let full_path = css_full_path(css_path);
+    let bundle_path = registry.css_bundle_path(style).await?;
     let registry_css = registry
-        .fetch_registry_asset(CSS_BUNDLE_PATH)
+        .fetch_registry_asset(&bundle_path)
         .await
-        .with_context(|| format!("failed to fetch registry CSS asset '{}'", CSS_BUNDLE_PATH))?;
+        .with_context(|| format!("failed to fetch registry CSS asset '{}'", bundle_path))?;
     let trimmed_registry_css = registry_css.trim_start();
 
     let css_content = if full_path.exists() {
@@ -115,10 +126,7 @@ pub async fn add_design_tokens_to_css(registry: &RegistryClient, css_path: &str)
         return Ok(false);
     }
 
-    let has_tailwind_import = css_content.contains("@import \"tailwindcss\"")
-        || css_content.contains("@import 'tailwindcss'");
-
-    let normalized_snippet = if has_tailwind_import {
+    let normalized_snippet = if contains_tailwind_import(&css_content) {
         strip_tailwind_import(trimmed_registry_css)
     } else {
         trimmed_registry_css.to_string()
@@ -133,6 +141,15 @@ pub async fn add_design_tokens_to_css(registry: &RegistryClient, css_path: &str)
     Ok(true)
 }
 
+/// Returns whether the project CSS file at `css_path` imports Tailwind (`@import "tailwindcss"`).
+///
+/// A missing or unreadable file counts as having no import.
+pub fn css_has_tailwind_import(css_path: &str) -> bool {
+    fs::read_to_string(css_full_path(css_path))
+        .map(|content| contains_tailwind_import(&content))
+        .unwrap_or(false)
+}
+
 pub fn check_tailwind_installation() -> TailwindCheck {
     let declared_version = read_declared_tailwind_version();
 
@@ -177,16 +194,14 @@ fn read_installed_tailwind_version() -> Option<String> {
 
     loop {
         let path = dir.join("node_modules/tailwindcss/package.json");
-        if let Ok(data) = fs::read_to_string(&path) {
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&data) {
-                if let Some(version) = json
-                    .get("version")
-                    .and_then(|value| value.as_str())
-                    .map(|value| value.to_string())
-                {
-                    return Some(version);
-                }
-            }
+        if let Ok(data) = fs::read_to_string(&path)
+            && let Ok(json) = serde_json::from_str::<serde_json::Value>(&data)
+            && let Some(version) = json
+                .get("version")
+                .and_then(|value| value.as_str())
+                .map(|value| value.to_string())
+        {
+            return Some(version);
         }
 
         if !dir.pop() {
@@ -1,7 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::framework::FrameworkKind;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ComponentFile {
@@ -11,6 +15,78 @@ pub struct ComponentFile {
     pub file_type: String,
     #[serde(default, alias = "workspace", skip_serializing_if = "Option::is_none")]
     pub target: Option<String>,
+    /// Frameworks this file is meant for (`nextjs`, `vite-react`, `react-router`,
+    /// `tanstack-start`); files without one are written for every framework.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framework: Option<FrameworkConstraint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FrameworkConstraint {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl FrameworkConstraint {
+    pub fn matches(&self, framework: FrameworkKind) -> bool {
+        let id = framework.as_str();
+        match self {
+            FrameworkConstraint::One(name) => name.eq_ignore_ascii_case(id),
+            FrameworkConstraint::Many(names) => {
+                names.iter().any(|name| name.eq_ignore_ascii_case(id))
+            }
+        }
+    }
+}
+
+/// Drops files constrained to other frameworks than `framework`. When a file constrained
+/// to `framework` shares its path with an unconstrained one, the constrained variant wins.
+pub fn select_framework_files(
+    files: Vec<ComponentFile>,
+    framework: FrameworkKind,
+) -> Vec<ComponentFile> {
+    let specific: HashSet<String> = files
+        .iter()
+        .filter(|file| {
+            file.framework
+                .as_ref()
+                .is_some_and(|constraint| constraint.matches(framework))
+        })
+        .map(|file| file.path.clone())
+        .collect();
+
+    files
+        .into_iter()
+        .filter(|file| match &file.framework {
+            Some(constraint) => constraint.matches(framework),
+            None => !specific.contains(&file.path),
+        })
+        .collect()
+}
+
+/// File types written as raw bytes, regardless of extension.
+const BINARY_FILE_TYPES: &[&str] = &["binary", "font", "image"];
+
+const BINARY_EXTENSIONS: &[&str] = &[
+    "avif", "bmp", "eot", "gif", "ico", "jpeg", "jpg", "mp3", "mp4", "otf", "pdf", "png", "ttf",
+    "wasm", "wav", "webm", "webp", "woff", "woff2", "zip",
+];
+
+impl ComponentFile {
+    /// Whether the file is a binary asset, written byte-for-byte instead of being decoded
+    /// as UTF-8 and import-normalized.
+    pub fn is_binary(&self) -> bool {
+        BINARY_FILE_TYPES.contains(&self.file_type.as_str()) || has_binary_extension(&self.path)
+    }
+}
+
+/// Whether `path` ends in an extension of a known binary format (fonts, images, media).
+pub fn has_binary_extension(path: impl AsRef<std::path::Path>) -> bool {
+    path.as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| BINARY_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,8 +100,14 @@ pub struct Component {
     pub dependencies: HashMap<String, String>,
     #[serde(default)]
     pub dev_dependencies: HashMap<String, String>,
+    /// Ranges the component expects of packages the project provides (e.g. `react`); checked, never installed.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub peer_dependencies: HashMap<String, String>,
     #[serde(default)]
     pub internal_dependencies: Vec<String>,
+    /// Components from other registries, written as `@<registry>/<slug>` (bare slugs use the default registry).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub registry_dependencies: Vec<String>,
     #[serde(default)]
     pub exports: Vec<String>,
     #[serde(default)]
@@ -36,6 +118,18 @@ pub struct Component {
     pub sizes: Vec<String>,
 }
 
+impl Component {
+    /// Whether installing the component for `framework` writes any file, i.e. not every
+    /// file is constrained to other frameworks.
+    pub fn supports_framework(&self, framework: FrameworkKind) -> bool {
+        self.files.iter().any(|file| {
+            file.framework
+                .as_ref()
+                .is_none_or(|constraint| constraint.matches(framework))
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CategoryInfo {
     pub name: String,
@@ -43,6 +137,88 @@ pub struct CategoryInfo {
     pub components: Vec<String>,
 }
 
+/// A style preset a registry offers, selected with `init --style` and stored as
+/// `Config::style`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryStyle {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// CSS token bundle for this style, relative to the registry base URL. Defaults to
+    /// the shared `css/index.css`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub css: Option<String>,
+    /// Registry paths of the component files this style replaces with a copy under
+    /// `styles/<name>/`. When omitted, every file is looked up there first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variants: Option<Vec<String>>,
+}
+
+/// Packages `init` installs, by the scope they belong in. Registries may also publish a
+/// flat `{ "name": "range" }` map, which is read as regular dependencies.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", from = "RequirementsShape")]
+pub struct RegistryRequirements {
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub dependencies: HashMap<String, String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub dev_dependencies: HashMap<String, String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub peer_dependencies: HashMap<String, String>,
+}
+
+impl RegistryRequirements {
+    /// Every required package regardless of scope.
+    pub fn all(&self) -> HashMap<String, String> {
+        self.dependencies
+            .iter()
+            .chain(&self.dev_dependencies)
+            .chain(&self.peer_dependencies)
+            .map(|(name, range)| (name.clone(), range.clone()))
+            .collect()
+    }
+
+    /// Whether the registry assigned any package a dev or peer scope.
+    pub fn is_scoped(&self) -> bool {
+        !self.dev_dependencies.is_empty() || !self.peer_dependencies.is_empty()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RequirementsShape {
+    Flat(HashMap<String, String>),
+    #[serde(rename_all = "camelCase")]
+    Scoped {
+        #[serde(default)]
+        dependencies: HashMap<String, String>,
+        #[serde(default)]
+        dev_dependencies: HashMap<String, String>,
+        #[serde(default)]
+        peer_dependencies: HashMap<String, String>,
+    },
+}
+
+impl From<RequirementsShape> for RegistryRequirements {
+    fn from(shape: RequirementsShape) -> Self {
+        match shape {
+            RequirementsShape::Flat(dependencies) => Self {
+                dependencies,
+                ..Self::default()
+            },
+            RequirementsShape::Scoped {
+                dependencies,
+                dev_dependencies,
+                peer_dependencies,
+            } => Self {
+                dependencies,
+                dev_dependencies,
+                peer_dependencies,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Registry {
     pub name: String,
@@ -50,10 +226,29 @@ pub struct Registry {
     pub version: String,
     pub components: HashMap<String, Component>,
     pub categories: HashMap<String, CategoryInfo>,
-    pub requirements: HashMap<String, String>,
+    #[serde(default)]
+    pub requirements: RegistryRequirements,
+    /// Style presets, the first being the default. Component files may ship per-style
+    /// variants under `styles/<name>/`, e.g. `styles/new-york/components/button.tsx`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub styles: Vec<RegistryStyle>,
+    /// Path (relative to the registry base URL) serving each component file as plain text,
+    /// e.g. `files` for `files/components/button.tsx`. Registries without it only ship
+    /// the bundled components manifest.
+    #[serde(default, rename = "filesBase", skip_serializing_if = "Option::is_none")]
+    pub files_base: Option<String>,
+    /// Path (relative to the registry base URL) of a `.tar.gz` archive holding every
+    /// component file as a real entry, e.g. `components.tar.gz`. Preferred over the
+    /// base64 components manifest when present.
+    #[serde(
+        default,
+        rename = "componentsArchive",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub components_archive: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Config {
     #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
@@ -67,14 +262,157 @@ pub struct Config {
     pub exports: Option<ExportsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub workspace: Option<WorkspaceConfig>,
+    /// Default registry base URL for this project, used when neither `--registry-url` nor
+    /// `NOCTA_REGISTRY_URL` is set. Takes precedence over the workspace manifest's `registry`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registries: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheConfig>,
+    /// Run the project's formatter (biome or prettier) over files written by `add`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<bool>,
+    /// Leading registry path segments (e.g. `registry/`) dropped before mapping component
+    /// files into the project, in addition to the built-in `app/` and `src/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strip_prefixes: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<DependenciesConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<ComponentsConfig>,
+}
+
+impl Config {
+    /// Case applied to installed component file names, if configured.
+    pub fn filename_case(&self) -> Option<FilenameCase> {
+        self.components
+            .as_ref()
+            .and_then(|components| components.filename_case)
+    }
+}
+
+/// How `add` names component files on disk.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentsConfig {
+    /// Rewrites component file names (and `@/` imports of them) to this case; unset keeps
+    /// the registry's names.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename_case: Option<FilenameCase>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FilenameCase {
+    /// `button-group.tsx`
+    Kebab,
+    /// `ButtonGroup.tsx`
+    Pascal,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl FilenameCase {
+    /// Converts the part of `name` before its first `.`, keeping extensions and suffixes
+    /// such as `.stories.tsx`. `index` files are left alone.
+    pub fn apply(self, name: &str) -> String {
+        let (stem, rest) = match name.find('.') {
+            Some(index) => name.split_at(index),
+            None => (name, ""),
+        };
+        if stem.is_empty() || stem.eq_ignore_ascii_case("index") {
+            return name.to_string();
+        }
+
+        let words = split_words(stem);
+        let converted = match self {
+            FilenameCase::Kebab => words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("-"),
+            FilenameCase::Pascal => words
+                .iter()
+                .map(|word| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) => first
+                            .to_uppercase()
+                            .chain(chars.flat_map(char::to_lowercase))
+                            .collect(),
+                        None => String::new(),
+                    }
+                })
+                .collect(),
+        };
+        format!("{}{}", converted, rest)
+    }
+}
+
+/// Splits on `-`, `_`, spaces, lower-to-upper case boundaries (`ButtonGroup`) and the end
+/// of an acronym (`HTMLInput` is `HTML` + `Input`).
+fn split_words(stem: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    let mut chars = stem.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if matches!(ch, '-' | '_' | ' ') {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        let acronym_end = ch.is_uppercase()
+            && current.chars().last().is_some_and(char::is_uppercase)
+            && chars.peek().is_some_and(|next| next.is_lowercase());
+        if ch.is_uppercase() && (previous_lower || acronym_end) && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = ch.is_lowercase() || ch.is_ascii_digit();
+        current.push(ch);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Controls which component dependencies `add` installs.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DependenciesConfig {
+    /// Packages never installed automatically, e.g. ones the project vendors or aliases.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore: Option<Vec<String>>,
+}
+
+/// Project-pinned cache TTLs. The `NOCTA_*_CACHE_TTL_MS` envs take precedence.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_ttl_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_ttl_ms: Option<u64>,
+}
+
+impl CacheConfig {
+    pub fn registry_ttl(&self) -> Option<Duration> {
+        self.registry_ttl_ms.map(Duration::from_millis)
+    }
+
+    pub fn asset_ttl(&self) -> Option<Duration> {
+        self.asset_ttl_ms.map(Duration::from_millis)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct TailwindConfig {
     pub css: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct Aliases {
     #[serde(default)]
     pub components: AliasTarget,
@@ -82,17 +420,52 @@ pub struct Aliases {
     pub utils: AliasTarget,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct AliasPrefixes {
     pub components: Option<String>,
     pub utils: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportsConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub components: Option<ExportsTargetConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utils: Option<ExportsTargetConfig>,
+    /// Formatting of generated export lines; unset fields follow the existing barrel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<BarrelStyle>,
+    /// Subpath exports `init` adds to a shared UI package's `package.json`, e.g.
+    /// `{ "./*": "./src/components/ui/*" }`, next to the `"."` entry for the barrel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subpaths: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BarrelStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quotes: Option<QuoteStyle>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub semicolons: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteStyle {
+    #[default]
+    Double,
+    Single,
+}
+
+impl QuoteStyle {
+    pub fn as_char(&self) -> char {
+        match self {
+            QuoteStyle::Double => '"',
+            QuoteStyle::Single => '\'',
+        }
+    }
 }
 
 impl ExportsConfig {
@@ -103,9 +476,26 @@ impl ExportsConfig {
     pub fn components_mut(&mut self) -> Option<&mut ExportsTargetConfig> {
         self.components.as_mut()
     }
+
+    pub fn utils(&self) -> Option<&ExportsTargetConfig> {
+        self.utils.as_ref()
+    }
+
+    pub fn utils_mut(&mut self) -> Option<&mut ExportsTargetConfig> {
+        self.utils.as_mut()
+    }
+
+    /// Barrel that re-exports registry files of `file_type`: `component` files go to
+    /// `components`, everything else (utilities, hooks) to `utils`.
+    pub fn target_for_file_type(&self, file_type: &str) -> Option<&ExportsTargetConfig> {
+        match file_type {
+            "component" => self.components(),
+            _ => self.utils(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportsTargetConfig {
     pub barrel: String,
@@ -126,19 +516,27 @@ impl ExportsTargetConfig {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ExportStrategy {
+    #[default]
     Named,
+    /// Re-exports the component's default export under its primary export name.
+    Default,
 }
 
-impl Default for ExportStrategy {
-    fn default() -> Self {
-        ExportStrategy::Named
+impl ExportStrategy {
+    pub const ALL: [ExportStrategy; 2] = [ExportStrategy::Named, ExportStrategy::Default];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExportStrategy::Named => "named",
+            ExportStrategy::Default => "default",
+        }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum AliasTarget {
     Path(String),
@@ -202,7 +600,7 @@ impl From<&str> for AliasTarget {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum WorkspaceKind {
     App,
@@ -210,7 +608,7 @@ pub enum WorkspaceKind {
     Library,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct WorkspaceLink {
     pub kind: WorkspaceKind,
@@ -224,7 +622,7 @@ fn workspace_root_default() -> String {
     ".".into()
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct WorkspaceConfig {
     pub kind: WorkspaceKind,
@@ -235,3 +633,127 @@ pub struct WorkspaceConfig {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub linked_workspaces: Vec<WorkspaceLink>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packages(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(name, range)| (name.to_string(), range.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn flat_requirements_are_regular_dependencies() {
+        let requirements: RegistryRequirements =
+            serde_json::from_str(r#"{ "react": "^19.0.0", "clsx": "^2.1.0" }"#).unwrap();
+        assert_eq!(
+            requirements,
+            RegistryRequirements {
+                dependencies: packages(&[("react", "^19.0.0"), ("clsx", "^2.1.0")]),
+                ..RegistryRequirements::default()
+            }
+        );
+        assert!(!requirements.is_scoped());
+    }
+
+    #[test]
+    fn scoped_requirements_keep_their_scopes() {
+        let requirements: RegistryRequirements = serde_json::from_str(
+            r#"{
+                "dependencies": { "clsx": "^2.1.0" },
+                "devDependencies": { "tailwindcss": "^4.1.0" },
+                "peerDependencies": { "react": ">=18" }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(requirements.dependencies, packages(&[("clsx", "^2.1.0")]));
+        assert_eq!(
+            requirements.dev_dependencies,
+            packages(&[("tailwindcss", "^4.1.0")])
+        );
+        assert_eq!(
+            requirements.peer_dependencies,
+            packages(&[("react", ">=18")])
+        );
+        assert!(requirements.is_scoped());
+        assert_eq!(requirements.all().len(), 3);
+    }
+
+    #[test]
+    fn scoped_requirements_may_omit_scopes() {
+        let requirements: RegistryRequirements =
+            serde_json::from_str(r#"{ "devDependencies": { "tailwindcss": "^4.1.0" } }"#).unwrap();
+        assert!(requirements.dependencies.is_empty());
+        assert!(requirements.peer_dependencies.is_empty());
+        assert!(requirements.is_scoped());
+
+        let empty: RegistryRequirements = serde_json::from_str("{}").unwrap();
+        assert_eq!(empty, RegistryRequirements::default());
+    }
+
+    #[test]
+    fn requirements_serialize_in_the_scoped_shape() {
+        let requirements = RegistryRequirements {
+            dev_dependencies: packages(&[("tailwindcss", "^4.1.0")]),
+            ..RegistryRequirements::default()
+        };
+        let json = serde_json::to_string(&requirements).unwrap();
+        assert_eq!(json, r#"{"devDependencies":{"tailwindcss":"^4.1.0"}}"#);
+        assert_eq!(
+            serde_json::from_str::<RegistryRequirements>(&json).unwrap(),
+            requirements
+        );
+    }
+
+    #[test]
+    fn split_words_handles_separators_case_boundaries_and_acronyms() {
+        assert_eq!(split_words("button-group"), ["button", "group"]);
+        assert_eq!(
+            split_words("button_group item"),
+            ["button", "group", "item"]
+        );
+        assert_eq!(split_words("ButtonGroup"), ["Button", "Group"]);
+        assert_eq!(split_words("buttonGroup"), ["button", "Group"]);
+        assert_eq!(split_words("HTMLInput"), ["HTML", "Input"]);
+        assert_eq!(split_words("useHTML"), ["use", "HTML"]);
+        assert_eq!(split_words("h2Heading"), ["h2", "Heading"]);
+        assert_eq!(split_words("--button--"), ["button"]);
+    }
+
+    #[test]
+    fn filename_case_converts_the_stem_and_keeps_suffixes() {
+        assert_eq!(
+            FilenameCase::Pascal.apply("button-group.tsx"),
+            "ButtonGroup.tsx"
+        );
+        assert_eq!(
+            FilenameCase::Pascal.apply("button-group.stories.tsx"),
+            "ButtonGroup.stories.tsx"
+        );
+        assert_eq!(FilenameCase::Pascal.apply("button-group"), "ButtonGroup");
+        assert_eq!(
+            FilenameCase::Kebab.apply("ButtonGroup.tsx"),
+            "button-group.tsx"
+        );
+        assert_eq!(
+            FilenameCase::Kebab.apply("button-group.tsx"),
+            "button-group.tsx"
+        );
+    }
+
+    #[test]
+    fn filename_case_keeps_acronyms_as_one_word() {
+        assert_eq!(FilenameCase::Pascal.apply("HTMLInput.tsx"), "HtmlInput.tsx");
+        assert_eq!(FilenameCase::Kebab.apply("HTMLInput.tsx"), "html-input.tsx");
+    }
+
+    #[test]
+    fn filename_case_leaves_index_and_dotfiles_alone() {
+        assert_eq!(FilenameCase::Pascal.apply("index.ts"), "index.ts");
+        assert_eq!(FilenameCase::Kebab.apply("Index.tsx"), "Index.tsx");
+        assert_eq!(FilenameCase::Pascal.apply(".eslintrc"), ".eslintrc");
+    }
+}
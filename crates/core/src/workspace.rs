@@ -6,7 +6,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 
-use crate::config::ensure_parent_dir;
+use crate::config::{CONFIG_FILE_NAME, ensure_parent_dir};
+use crate::framework::framework_config_files;
 use crate::types::WorkspaceKind;
 
 pub const WORKSPACE_MANIFEST_FILE: &str = "nocta.workspace.json";
@@ -47,6 +48,8 @@ pub struct PackageManagerContext {
     pub workspace_root: Option<PathBuf>,
     pub workspace_package: Option<String>,
     pub package_manager: Option<PackageManagerKind>,
+    /// Extra arguments appended to every install command (`--install-arg`, `NOCTA_INSTALL_ARGS`).
+    pub extra_install_args: Vec<String>,
 }
 
 impl PackageManagerContext {
@@ -56,6 +59,7 @@ impl PackageManagerContext {
             workspace_root: None,
             workspace_package: None,
             package_manager: None,
+            extra_install_args: Vec::new(),
         }
     }
 
@@ -95,6 +99,10 @@ pub struct WorkspaceManifest {
     pub package_manager: Option<PackageManagerKind>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub repo_root: Option<String>,
+    /// Registry base URL shared by every workspace in the repo, used when neither
+    /// `--registry-url` nor `NOCTA_REGISTRY_URL` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
 }
 
 #[derive(Debug, Error)]
@@ -137,32 +145,40 @@ pub fn write_workspace_manifest(
     fs::write(path, json).map_err(WorkspaceManifestError::Write)
 }
 
+/// Walks up from `start` to the nearest monorepo root. Without monorepo markers, prefers
+/// the nearest `nocta.config.json` directory, then the nearest app `package.json`.
 pub fn find_repo_root(start: &Path) -> Option<PathBuf> {
-    let absolute_start = if start.is_absolute() {
-        start.to_path_buf()
-    } else {
-        match start.canonicalize() {
-            Ok(path) => path,
-            Err(_) => {
-                if let Ok(cwd) = std::env::current_dir() {
-                    cwd.join(start)
-                } else {
-                    start.to_path_buf()
-                }
+    let absolute_start = match start.canonicalize() {
+        Ok(path) => path,
+        Err(_) if start.is_absolute() => start.to_path_buf(),
+        Err(_) => {
+            if let Ok(cwd) = std::env::current_dir() {
+                cwd.join(start)
+            } else {
+                start.to_path_buf()
             }
         }
     };
 
     let mut current = absolute_start.clone();
-    let mut fallback: Option<PathBuf> = None;
+    let mut config_dir: Option<PathBuf> = None;
+    let mut framework_package: Option<PathBuf> = None;
+    let mut package_dir: Option<PathBuf> = None;
 
     loop {
         if matches_repo_root(&current) {
             return Some(current);
         }
 
+        if current.join(CONFIG_FILE_NAME).exists() {
+            config_dir.get_or_insert_with(|| current.clone());
+        }
+
         if current.join("package.json").exists() {
-            fallback.get_or_insert_with(|| current.clone());
+            if has_framework_config(&current) {
+                framework_package.get_or_insert_with(|| current.clone());
+            }
+            package_dir.get_or_insert_with(|| current.clone());
         }
 
         if !current.pop() {
@@ -170,7 +186,16 @@ pub fn find_repo_root(start: &Path) -> Option<PathBuf> {
         }
     }
 
-    fallback.or_else(|| Some(absolute_start))
+    config_dir
+        .or(framework_package)
+        .or(package_dir)
+        .or(Some(absolute_start))
+}
+
+/// Whether `path` holds a framework config, which marks an app root when no monorepo
+/// markers exist.
+fn has_framework_config(path: &Path) -> bool {
+    framework_config_files().any(|file| path.join(file).exists())
 }
 
 fn matches_repo_root(path: &Path) -> bool {
@@ -184,7 +209,7 @@ fn has_workspace_manifest(path: &Path) -> bool {
     path.join(WORKSPACE_MANIFEST_FILE).exists()
 }
 
-fn package_json_has_workspaces(path: &Path) -> bool {
+pub fn package_json_has_workspaces(path: &Path) -> bool {
     let pkg_path = path.join("package.json");
     if !pkg_path.exists() {
         return false;
@@ -202,10 +227,8 @@ fn package_json_has_workspaces(path: &Path) -> bool {
                             .and_then(Value::as_array)
                             .map(|packages| !packages.is_empty())
                             .unwrap_or(true)
-                    } else if workspaces.is_string() {
-                        true
                     } else {
-                        false
+                        workspaces.is_string()
                     }
                 } else {
                     false
@@ -240,20 +263,16 @@ pub fn detect_package_manager(root: &Path) -> Option<PackageManagerKind> {
     }
 
     let pkg_path = root.join("package.json");
-    if pkg_path.exists() {
-        if let Ok(contents) = fs::read_to_string(pkg_path) {
-            if let Ok(value) = serde_json::from_str::<Value>(&contents) {
-                if let Some(manager) = value
-                    .get("packageManager")
-                    .and_then(Value::as_str)
-                    .and_then(|spec| spec.split('@').next())
-                {
-                    if let Some(kind) = PackageManagerKind::from_name(manager) {
-                        return Some(kind);
-                    }
-                }
-            }
-        }
+    if pkg_path.exists()
+        && let Ok(contents) = fs::read_to_string(pkg_path)
+        && let Ok(value) = serde_json::from_str::<Value>(&contents)
+        && let Some(manager) = value
+            .get("packageManager")
+            .and_then(Value::as_str)
+            .and_then(|spec| spec.split('@').next())
+        && let Some(kind) = PackageManagerKind::from_name(manager)
+    {
+        return Some(kind);
     }
 
     None
@@ -275,10 +294,10 @@ pub fn resolve_workspace_by_package<'a>(
     })
 }
 
-pub fn resolve_workspace_by_kind<'a>(
-    manifest: &'a WorkspaceManifest,
+pub fn resolve_workspace_by_kind(
+    manifest: &WorkspaceManifest,
     kind: WorkspaceKind,
-) -> Option<&'a WorkspaceManifestEntry> {
+) -> Option<&WorkspaceManifestEntry> {
     manifest.workspaces.iter().find(|entry| entry.kind == kind)
 }
 
@@ -291,3 +310,92 @@ pub fn resolve_workspace_by_config<'a>(
         .iter()
         .find(|entry| entry.config == config_path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(files: &[&str]) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        for file in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let contents = if file.ends_with("package.json") {
+                "{}"
+            } else {
+                ""
+            };
+            fs::write(path, contents).unwrap();
+        }
+        (dir, root)
+    }
+
+    #[test]
+    fn nested_config_beats_outer_package_json() {
+        let (_dir, root) = layout(&[
+            "package.json",
+            "site/package.json",
+            "site/nocta.config.json",
+            "site/src/components/.keep",
+        ]);
+
+        assert_eq!(
+            find_repo_root(&root.join("site/src/components")),
+            Some(root.join("site"))
+        );
+    }
+
+    #[test]
+    fn app_package_json_beats_nearer_plain_package_json() {
+        let (_dir, root) = layout(&[
+            "package.json",
+            "web/package.json",
+            "web/next.config.mjs",
+            "web/tools/codegen/package.json",
+        ]);
+
+        assert_eq!(
+            find_repo_root(&root.join("web/tools/codegen")),
+            Some(root.join("web"))
+        );
+    }
+
+    #[test]
+    fn vite_config_in_a_config_directory_marks_the_app() {
+        let (_dir, root) = layout(&[
+            "package.json",
+            "web/package.json",
+            "web/config/vite.config.ts",
+            "web/tools/codegen/package.json",
+        ]);
+
+        assert_eq!(
+            find_repo_root(&root.join("web/tools/codegen")),
+            Some(root.join("web"))
+        );
+    }
+
+    #[test]
+    fn nearest_plain_package_json_is_the_last_resort() {
+        let (_dir, root) = layout(&["package.json", "lib/package.json", "lib/src/.keep"]);
+
+        assert_eq!(
+            find_repo_root(&root.join("lib/src")),
+            Some(root.join("lib"))
+        );
+    }
+
+    #[test]
+    fn monorepo_markers_win_over_nested_configs() {
+        let (_dir, root) = layout(&[
+            "pnpm-workspace.yaml",
+            "package.json",
+            "apps/web/package.json",
+            "apps/web/next.config.js",
+            "apps/web/nocta.config.json",
+        ]);
+
+        assert_eq!(find_repo_root(&root.join("apps/web")), Some(root.clone()));
+    }
+}